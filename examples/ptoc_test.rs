@@ -1,6 +1,6 @@
 /// Simple PTOC test example
 use poc_protection_functions::{
-    PtocConfig, Ptoc, ProtectionFunction,
+    PtocConfig, Ptoc, ProtectionFunction, CurveType, MeasurementFrame,
 };
 
 fn main() {
@@ -11,6 +11,7 @@ fn main() {
         iset: 100.0,
         tset: 100,
         enabled: true,
+        ..PtocConfig::default()
     };
     
     let mut ptoc = Ptoc::new(config);
@@ -22,28 +23,28 @@ fn main() {
 
     // Test scenario 1: Current below pickup
     println!("Test 1: Current below pickup (50A)");
-    let result = ptoc.process(50.0, 0);
+    let result = ptoc.process(&MeasurementFrame::balanced(50.0, 0));
     println!("  Result: {:?}", result);
     println!("  State: {:?}", ptoc.state());
     println!();
 
     // Test scenario 2: Current exceeds pickup
     println!("Test 2: Current exceeds pickup (150A at t=0)");
-    let result = ptoc.process(150.0, 0);
+    let result = ptoc.process(&MeasurementFrame::balanced(150.0, 0));
     println!("  Result: {:?}", result);
     println!("  State: {:?}", ptoc.state());
     println!();
 
     // Test scenario 3: Still overcurrent, but delay not expired
     println!("Test 3: Still overcurrent at t=50ms");
-    let result = ptoc.process(150.0, 50_000); // 50ms = 50000 microseconds
+    let result = ptoc.process(&MeasurementFrame::balanced(150.0, 50_000)); // 50ms = 50000 microseconds
     println!("  Result: {:?}", result);
     println!("  State: {:?}", ptoc.state());
     println!();
 
     // Test scenario 4: Delay expired, should trip
     println!("Test 4: Still overcurrent at t=100ms (delay expired)");
-    let result = ptoc.process(150.0, 100_000); // 100ms = 100000 microseconds
+    let result = ptoc.process(&MeasurementFrame::balanced(150.0, 100_000)); // 100ms = 100000 microseconds
     println!("  Result: {:?}", result);
     println!("  State: {:?}", ptoc.state());
     println!();
@@ -51,13 +52,37 @@ fn main() {
     // Test scenario 5: Reset and try again
     println!("Test 5: Reset and test with current drop");
     ptoc.reset();
-    ptoc.process(150.0, 0); // Pickup at t=0
+    ptoc.process(&MeasurementFrame::balanced(150.0, 0)); // Pickup at t=0
     println!("  Pickup at t=0");
-    let result = ptoc.process(50.0, 50_000); // Current drops before delay
+    let result = ptoc.process(&MeasurementFrame::balanced(50.0, 50_000)); // Current drops before delay
     println!("  Current drops to 50A at t=50ms");
     println!("  Result: {:?}", result);
     println!("  State: {:?}", ptoc.state());
     println!();
 
+    // Test scenario 6: Inverse time curve
+    println!("Test 6: IEC Very Inverse, TMS 0.1, at 2x and 5x pickup");
+    let mut inverse = Ptoc::new(PtocConfig {
+        iset: 100.0,
+        curve: CurveType::IecVeryInverse,
+        tms: 0.1,
+        ..PtocConfig::default()
+    });
+    println!("  Operate time at 200A: {:?}", inverse.operate_time(200.0));
+    println!("  Operate time at 500A: {:?}", inverse.operate_time(500.0));
+    inverse.process(&MeasurementFrame::balanced(500.0, 0));
+    let result = inverse.process(&MeasurementFrame::balanced(500.0, 340_000));
+    println!("  Result at t=340ms with 500A: {:?}", result);
+    println!();
+
+    // Test scenario 7: Phase-selective trip
+    println!("Test 7: Phase B fault (300A), phases A and C at load (50A)");
+    ptoc.reset();
+    ptoc.process(&MeasurementFrame::from_phase_currents([50.0, 300.0, 50.0], 0));
+    let result = ptoc.process(&MeasurementFrame::from_phase_currents([50.0, 300.0, 50.0], 100_000));
+    println!("  Result: {:?}", result);
+    println!("  Tripped phases: {:?}", ptoc.trip_phases());
+    println!();
+
     println!("Test complete!");
 }
@@ -1,15 +1,85 @@
 /// Configuration structures for protection functions and I/O
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::io::vlan::MAX_VLAN_ID;
+use crate::io::{GapPolicy, GooseDataMember, SvChannel, VlanTag, DEFAULT_GOOSE_PRIORITY};
+use crate::measurement::MeasuringMode;
+use crate::protection::{
+    CurveType, DirectionMode, EarthPolarisation, PiocMeasuringMode, ResetMode, ResidualSource, SefCriterion,
+};
 
 /// Configuration for PTOC (Time Overcurrent Protection)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PtocConfig {
     /// Pickup current in primary Amperes
     pub iset: f64,
-    /// Definite time delay in milliseconds
+    /// Definite time delay in milliseconds (used with `CurveType::DefiniteTime`)
     pub tset: u64,
     /// Enable/disable the protection function
     pub enabled: bool,
+    /// Operating characteristic (definite time or inverse curve)
+    #[serde(default)]
+    pub curve: CurveType,
+    /// Time multiplier setting for inverse curves (TMS / TD)
+    #[serde(default = "default_tms")]
+    pub tms: f64,
+    /// Dropout ratio as a fraction of Iset (e.g. 0.95)
+    #[serde(default = "default_dropout_ratio")]
+    pub dropout_ratio: f64,
+    /// Reset behaviour of the time delay after dropout
+    #[serde(default)]
+    pub reset_mode: ResetMode,
+    /// Reset time in milliseconds
+    ///
+    /// Hold time for `ResetMode::DefiniteTime`; reset constant tr for
+    /// `ResetMode::Inverse` when the curve does not define one.
+    #[serde(default)]
+    pub reset_time: u64,
+    /// Operating quantity: true RMS or fundamental (DFT) magnitude
+    #[serde(default)]
+    pub measuring_mode: MeasuringMode,
+    /// Directional supervision by RDIR (non-directional, forward or reverse)
+    #[serde(default)]
+    pub direction: DirectionMode,
+}
+
+fn default_tms() -> f64 {
+    1.0
+}
+
+fn default_dropout_ratio() -> f64 {
+    0.95
+}
+
+/// Check a dropout ratio: a fraction of the pickup setting in (0, 1]
+fn validate_dropout_ratio(dropout_ratio: f64) -> Result<(), Box<dyn std::error::Error>> {
+    if !(dropout_ratio > 0.0 && dropout_ratio <= 1.0) {
+        return Err(format!("dropout_ratio {} must be in (0, 1]", dropout_ratio).into());
+    }
+    Ok(())
+}
+
+impl PtocConfig {
+    /// Check the settings for values the measuring element cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if !(self.tms.is_finite() && self.tms > 0.0) {
+            return Err(format!("tms {} must be above 0", self.tms).into());
+        }
+        if let CurveType::UserDefined { a, b, p } = self.curve {
+            if !(a.is_finite() && a >= 0.0 && b.is_finite() && b >= 0.0 && p.is_finite() && p > 0.0) {
+                return Err(format!(
+                    "curve constants A = {}, B = {} must be finite and not negative, p = {} above 0",
+                    a, b, p
+                )
+                .into());
+            }
+        }
+        validate_dropout_ratio(self.dropout_ratio)?;
+        if self.reset_mode == ResetMode::Inverse && self.curve.reset_constant().is_none() && self.reset_time == 0 {
+            return Err(format!("inverse reset with {:?} requires a reset_time above 0", self.curve).into());
+        }
+        Ok(())
+    }
 }
 
 impl Default for PtocConfig {
@@ -18,6 +88,72 @@ impl Default for PtocConfig {
             iset: 100.0,  // 100A default pickup
             tset: 100,    // 100ms default delay
             enabled: true,
+            curve: CurveType::DefiniteTime,
+            tms: default_tms(),
+            dropout_ratio: default_dropout_ratio(),
+            reset_mode: ResetMode::Instantaneous,
+            reset_time: 0,
+            measuring_mode: MeasuringMode::Rms,
+            direction: DirectionMode::NonDirectional,
+        }
+    }
+}
+
+/// Configuration for PIOC (Instantaneous Overcurrent Protection)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiocConfig {
+    /// Pickup current in primary Amperes (RMS; sample peaks are compared with √2 × Iset)
+    pub iset: f64,
+    /// Additional operate delay in milliseconds (0 = instantaneous)
+    #[serde(default)]
+    pub tset: u64,
+    /// Enable/disable the protection function
+    pub enabled: bool,
+    /// Operating quantity: half-cycle sample peak or half-cycle Fourier magnitude
+    #[serde(default)]
+    pub measuring_mode: PiocMeasuringMode,
+    /// Consecutive samples above Iset required to start
+    #[serde(default = "default_confirm_samples")]
+    pub confirm_samples: usize,
+    /// Time constant of the DC offset removed before measuring in milliseconds (0 = no filter)
+    ///
+    /// Set to the primary time constant of the protected network, (X/R) / ω.
+    #[serde(default = "default_dc_time_constant")]
+    pub dc_time_constant: f64,
+    /// Dropout ratio as a fraction of Iset (e.g. 0.95)
+    #[serde(default = "default_dropout_ratio")]
+    pub dropout_ratio: f64,
+    /// Directional supervision by RDIR (non-directional, forward or reverse)
+    #[serde(default)]
+    pub direction: DirectionMode,
+}
+
+fn default_confirm_samples() -> usize {
+    3
+}
+
+fn default_dc_time_constant() -> f64 {
+    40.0
+}
+
+impl PiocConfig {
+    /// Check the settings for values the measuring elements cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        validate_dropout_ratio(self.dropout_ratio)
+    }
+}
+
+impl Default for PiocConfig {
+    fn default() -> Self {
+        Self {
+            iset: 2000.0, // 2000A default pickup
+            tset: 0,
+            enabled: true,
+            measuring_mode: PiocMeasuringMode::Peak,
+            confirm_samples: default_confirm_samples(),
+            dc_time_constant: default_dc_time_constant(),
+            dropout_ratio: default_dropout_ratio(),
+            direction: DirectionMode::NonDirectional,
         }
     }
 }
@@ -47,6 +183,31 @@ impl CtConfig {
     }
 }
 
+/// Configuration for VT (Voltage Transformer) scaling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VtConfig {
+    /// Primary voltage rating (e.g., 20000 for 20000/100 V VT)
+    pub primary: f64,
+    /// Secondary voltage rating (typically 100 or 110)
+    pub secondary: f64,
+}
+
+impl Default for VtConfig {
+    fn default() -> Self {
+        Self {
+            primary: 20000.0,
+            secondary: 100.0,
+        }
+    }
+}
+
+impl VtConfig {
+    /// Get the VT ratio (primary/secondary)
+    pub fn ratio(&self) -> f64 {
+        self.primary / self.secondary
+    }
+}
+
 /// Configuration for ADC (Analog-to-Digital Converter) scaling
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdcConfig {
@@ -65,6 +226,265 @@ impl Default for AdcConfig {
     }
 }
 
+impl AdcConfig {
+    /// Default voltage channel scaling (10 mV per count, IEC 61850-9-2LE)
+    pub fn voltage() -> Self {
+        Self {
+            scale_factor: 0.01,
+            offset: 0.0,
+        }
+    }
+}
+
+fn default_voltage_adc() -> AdcConfig {
+    AdcConfig::voltage()
+}
+
+/// Configuration for RDIR (Directional Element)
+///
+/// Angles are the angle by which the operating current leads the polarising
+/// voltage at maximum sensitivity; voltages and currents are primary values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdirConfig {
+    /// Characteristic angle of the phase elements in degrees (quadrature polarisation)
+    #[serde(default = "default_phase_characteristic_angle")]
+    pub characteristic_angle: f64,
+    /// Polarising quantity of the earth element
+    #[serde(default)]
+    pub earth_polarisation: EarthPolarisation,
+    /// Characteristic angle of the earth element in degrees
+    #[serde(default = "default_earth_characteristic_angle")]
+    pub earth_characteristic_angle: f64,
+    /// Half width of the forward and reverse sectors in degrees
+    #[serde(default = "default_sector_width")]
+    pub sector_width: f64,
+    /// Minimum polarising phase-to-phase voltage in volts
+    #[serde(default = "default_min_polarising_voltage")]
+    pub min_voltage: f64,
+    /// Minimum phase current in amperes
+    #[serde(default = "default_min_directional_current")]
+    pub min_current: f64,
+    /// Minimum 3V0 or 3V2 polarising voltage of the earth element in volts
+    #[serde(default = "default_min_residual_voltage")]
+    pub min_residual_voltage: f64,
+    /// Minimum 3I0 or 3I2 operating current of the earth element in amperes
+    #[serde(default = "default_min_residual_current")]
+    pub min_residual_current: f64,
+    /// Time the pre-fault voltage is used after the polarising voltage collapsed (ms)
+    #[serde(default = "default_memory_time")]
+    pub memory_time: u64,
+}
+
+fn default_phase_characteristic_angle() -> f64 {
+    45.0
+}
+
+fn default_earth_characteristic_angle() -> f64 {
+    -60.0
+}
+
+fn default_sector_width() -> f64 {
+    85.0
+}
+
+fn default_min_polarising_voltage() -> f64 {
+    1000.0
+}
+
+fn default_min_directional_current() -> f64 {
+    10.0
+}
+
+fn default_min_residual_voltage() -> f64 {
+    500.0
+}
+
+fn default_min_residual_current() -> f64 {
+    5.0
+}
+
+fn default_memory_time() -> u64 {
+    500
+}
+
+impl Default for RdirConfig {
+    fn default() -> Self {
+        Self {
+            characteristic_angle: default_phase_characteristic_angle(),
+            earth_polarisation: EarthPolarisation::ZeroSequence,
+            earth_characteristic_angle: default_earth_characteristic_angle(),
+            sector_width: default_sector_width(),
+            min_voltage: default_min_polarising_voltage(),
+            min_current: default_min_directional_current(),
+            min_residual_voltage: default_min_residual_voltage(),
+            min_residual_current: default_min_residual_current(),
+            memory_time: default_memory_time(),
+        }
+    }
+}
+
+/// Configuration for the earth-fault (residual overcurrent) stages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EarthFaultConfig {
+    /// Residual current: calculated 3I0 or the measured neutral channel
+    #[serde(default)]
+    pub source: ResidualSource,
+    /// Earth-fault stages (IE>, IE>>, ...) evaluated on the residual current
+    #[serde(default)]
+    pub stages: Vec<PtocConfig>,
+}
+
+/// Configuration for the broken-conductor function (I2/I1)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenConductorConfig {
+    /// Pickup ratio of negative to positive sequence current (e.g. 0.2)
+    pub ratio_set: f64,
+    /// Definite time delay in milliseconds
+    pub tset: u64,
+    /// Enable/disable the protection function
+    pub enabled: bool,
+    /// Minimum positive sequence current in primary Amperes
+    #[serde(default = "default_broken_conductor_min_current")]
+    pub min_current: f64,
+    /// Dropout ratio as a fraction of the pickup ratio (e.g. 0.95)
+    #[serde(default = "default_dropout_ratio")]
+    pub dropout_ratio: f64,
+}
+
+fn default_broken_conductor_min_current() -> f64 {
+    20.0
+}
+
+impl BrokenConductorConfig {
+    /// Check the settings for values the measuring element cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        validate_dropout_ratio(self.dropout_ratio)
+    }
+}
+
+impl Default for BrokenConductorConfig {
+    fn default() -> Self {
+        Self {
+            ratio_set: 0.2,
+            tset: 10000,
+            enabled: true,
+            min_current: default_broken_conductor_min_current(),
+            dropout_ratio: default_dropout_ratio(),
+        }
+    }
+}
+
+/// Configuration for PSDE (Sensitive Directional Earth Fault)
+///
+/// Currents are primary amperes of the neutral channel (core-balance CT),
+/// voltages are primary volts of 3V0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PsdeConfig {
+    /// Pickup of the active or reactive residual current component in primary Amperes
+    pub iset: f64,
+    /// Definite time delay in milliseconds
+    pub tset: u64,
+    /// Enable/disable the protection function
+    pub enabled: bool,
+    /// Wattmetric (I0·cos φ) or varmetric (I0·sin φ) criterion
+    #[serde(default)]
+    pub criterion: SefCriterion,
+    /// Forward (faulted feeder), reverse or non-directional operation
+    #[serde(default)]
+    pub direction: DirectionMode,
+    /// Minimum 3V0 releasing the function in volts
+    #[serde(default = "default_psde_min_residual_voltage")]
+    pub min_residual_voltage: f64,
+    /// Correction of the CT and VT phase errors in degrees, subtracted from φ
+    #[serde(default)]
+    pub angle_correction: f64,
+    /// Residual voltage: calculated 3V0 or the measured neutral voltage channel (open delta)
+    #[serde(default)]
+    pub voltage_source: ResidualSource,
+    /// Dropout ratio as a fraction of Iset (e.g. 0.95)
+    #[serde(default = "default_dropout_ratio")]
+    pub dropout_ratio: f64,
+}
+
+fn default_psde_min_residual_voltage() -> f64 {
+    2000.0
+}
+
+impl PsdeConfig {
+    /// Check the settings for values the measuring element cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        validate_dropout_ratio(self.dropout_ratio)
+    }
+}
+
+impl Default for PsdeConfig {
+    fn default() -> Self {
+        Self {
+            iset: 1.0,
+            tset: 500,
+            enabled: true,
+            criterion: SefCriterion::Wattmetric,
+            direction: DirectionMode::NonDirectional,
+            min_residual_voltage: default_psde_min_residual_voltage(),
+            angle_correction: 0.0,
+            voltage_source: ResidualSource::Calculated,
+            dropout_ratio: default_dropout_ratio(),
+        }
+    }
+}
+
+/// Configuration for PTEF (Transient / Intermittent Earth Fault)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PtefConfig {
+    /// Enable/disable the protection function
+    pub enabled: bool,
+    /// Instantaneous neutral current starting a pulse in primary Amperes
+    pub pulse_threshold: f64,
+    /// Number of pulses within the window causing a trip
+    #[serde(default = "default_pulse_count")]
+    pub pulse_count: usize,
+    /// Counting window in milliseconds
+    #[serde(default = "default_pulse_window")]
+    pub window: u64,
+    /// Time after a pulse in which further threshold crossings belong to it (ms)
+    #[serde(default = "default_blanking_time")]
+    pub blanking_time: u64,
+}
+
+fn default_pulse_count() -> usize {
+    4
+}
+
+fn default_pulse_window() -> u64 {
+    1000
+}
+
+fn default_blanking_time() -> u64 {
+    5
+}
+
+impl PtefConfig {
+    /// Check the settings for values the function cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.pulse_count == 0 {
+            return Err("ptef.pulse_count must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for PtefConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pulse_threshold: 10.0,
+            pulse_count: default_pulse_count(),
+            window: default_pulse_window(),
+            blanking_time: default_blanking_time(),
+        }
+    }
+}
+
 /// Configuration for GOOSE output
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GooseConfig {
@@ -80,6 +500,68 @@ pub struct GooseConfig {
     pub dat_set: String,
     /// Network interface name (e.g., "eth0")
     pub interface: String,
+    /// Heartbeat interval T0 in milliseconds
+    #[serde(default = "default_t0")]
+    pub t0: u64,
+    /// Minimum retransmission interval T1 after a state change in milliseconds
+    #[serde(default = "default_t1")]
+    pub t1: u64,
+    /// Retransmission interval multiplier, from T1 up to T0
+    #[serde(default = "default_backoff_factor")]
+    pub backoff_factor: f64,
+    /// Dataset members in dataset order (e.g. Op.general, Op.phsA, q, t)
+    #[serde(default = "default_goose_dataset")]
+    pub dataset: Vec<GooseDataMember>,
+    /// VLAN ID of published frames (0 = priority tagged only)
+    #[serde(default)]
+    pub vlan_id: u16,
+    /// 802.1p priority of published frames (0..7)
+    #[serde(default = "default_vlan_priority")]
+    pub vlan_priority: u8,
+    /// Configuration revision of the dataset (from the SCD file)
+    #[serde(default = "default_conf_rev")]
+    pub conf_rev: u32,
+}
+
+fn default_t0() -> u64 {
+    1000
+}
+
+fn default_t1() -> u64 {
+    2
+}
+
+fn default_backoff_factor() -> f64 {
+    2.0
+}
+
+fn default_goose_dataset() -> Vec<GooseDataMember> {
+    vec![GooseDataMember::OpGeneral]
+}
+
+fn default_vlan_priority() -> u8 {
+    DEFAULT_GOOSE_PRIORITY
+}
+
+fn default_conf_rev() -> u32 {
+    1
+}
+
+impl GooseConfig {
+    /// Check the settings for values that cannot be published
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        VlanTag::try_new(self.vlan_id, self.vlan_priority).map_err(|e| format!("goose: {}", e))?;
+        if self.t1 == 0 {
+            return Err("goose: t1 must be above 0 ms".into());
+        }
+        if self.t0 < self.t1 {
+            return Err(format!("goose: t0 {} ms must not be below t1 {} ms", self.t0, self.t1).into());
+        }
+        if !(self.backoff_factor.is_finite() && self.backoff_factor > 1.0) {
+            return Err(format!("goose: backoff_factor {} must be above 1", self.backoff_factor).into());
+        }
+        Ok(())
+    }
 }
 
 impl Default for GooseConfig {
@@ -91,6 +573,65 @@ impl Default for GooseConfig {
             gocb_ref: "IED1LD0/LLN0$GO$PTOC1".to_string(),
             dat_set: "IED1LD0/LLN0$PTOC1".to_string(),
             interface: "eth0".to_string(),
+            t0: default_t0(),
+            t1: default_t1(),
+            backoff_factor: default_backoff_factor(),
+            dataset: default_goose_dataset(),
+            vlan_id: 0,
+            vlan_priority: default_vlan_priority(),
+            conf_rev: default_conf_rev(),
+        }
+    }
+}
+
+/// Subscription to one GOOSE control block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GooseSubscriptionConfig {
+    /// GOOSE Control Block Reference of the publisher
+    pub gocb_ref: String,
+    /// GOOSE ID (None = accept any)
+    #[serde(default)]
+    pub goid: Option<String>,
+    /// Destination MAC address (format: "01:0C:CD:01:00:00")
+    pub dst_mac: String,
+    /// Application ID (None = accept any)
+    #[serde(default)]
+    pub appid: Option<u16>,
+    /// Expected configuration revision (None = accept any)
+    #[serde(default)]
+    pub conf_rev: Option<u32>,
+    /// Accept messages with the simulation bit set (IED in test mode)
+    #[serde(default)]
+    pub accept_simulation: bool,
+}
+
+impl Default for GooseSubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            gocb_ref: "IED2LD0/LLN0$GO$Status".to_string(),
+            goid: None,
+            dst_mac: "01:0C:CD:01:00:01".to_string(),
+            appid: None,
+            conf_rev: None,
+            accept_simulation: false,
+        }
+    }
+}
+
+/// Configuration for GOOSE input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GooseSubscriberConfig {
+    /// Network interface name (e.g., "eth0")
+    pub interface: String,
+    /// Subscribed GOOSE control blocks
+    pub subscriptions: Vec<GooseSubscriptionConfig>,
+}
+
+impl Default for GooseSubscriberConfig {
+    fn default() -> Self {
+        Self {
+            interface: "eth0".to_string(),
+            subscriptions: Vec::new(),
         }
     }
 }
@@ -104,6 +645,117 @@ pub struct SvConfig {
     pub interface: String,
     /// Multicast MAC address to subscribe to
     pub multicast_mac: String,
+    /// APPID of the stream (None = accept any)
+    #[serde(default)]
+    pub appid: Option<u16>,
+    /// svID (MsvID) of the stream (None = accept any)
+    #[serde(default)]
+    pub sv_id: Option<String>,
+    /// Expected configuration revision (None = accept any)
+    #[serde(default)]
+    pub conf_rev: Option<u32>,
+    /// Source MAC address of the merging unit (None = accept any)
+    #[serde(default)]
+    pub src_mac: Option<String>,
+    /// VLAN ID of the stream (None = accept tagged and untagged frames)
+    #[serde(default)]
+    pub vlan_id: Option<u16>,
+    /// Channel carried by each dataset entry, in dataset order
+    #[serde(default = "default_channel_layout")]
+    pub channel_layout: Vec<SvChannel>,
+    /// Nominal system frequency in Hz (sample counter wraps once per second)
+    #[serde(default = "default_nominal_frequency")]
+    pub nominal_frequency: f64,
+    /// Handling of samples missing from the stream
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Memory-mapped receive ring (Linux PACKET_MMAP)
+    #[serde(default)]
+    pub packet_ring: PacketRingConfig,
+    /// Request NIC hardware receive timestamps (falls back to kernel software timestamps)
+    #[serde(default)]
+    pub hardware_timestamps: bool,
+}
+
+/// Memory-mapped packet ring for SV reception (TPACKET_V3)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PacketRingConfig {
+    /// Receive through the ring instead of one recv call per frame
+    pub enabled: bool,
+    /// Size of one ring block in bytes (multiple of 4096)
+    #[serde(default = "default_ring_block_size")]
+    pub block_size: usize,
+    /// Number of blocks in the ring
+    #[serde(default = "default_ring_block_count")]
+    pub block_count: usize,
+    /// Time after which a partly filled block is handed to user space (ms)
+    #[serde(default = "default_ring_block_timeout")]
+    pub block_timeout_ms: u32,
+}
+
+fn default_ring_block_size() -> usize {
+    4096
+}
+
+fn default_ring_block_count() -> usize {
+    256
+}
+
+fn default_ring_block_timeout() -> u32 {
+    1
+}
+
+impl Default for PacketRingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            block_size: default_ring_block_size(),
+            block_count: default_ring_block_count(),
+            block_timeout_ms: default_ring_block_timeout(),
+        }
+    }
+}
+
+fn default_channel_layout() -> Vec<SvChannel> {
+    SvChannel::LE_LAYOUT.to_vec()
+}
+
+fn default_nominal_frequency() -> f64 {
+    50.0
+}
+
+/// Highest sample rate the 16-bit sample counter (smpCnt) can count
+const MAX_SAMPLES_PER_SECOND: u32 = 65536;
+
+impl SvConfig {
+    /// Get the sample rate in samples per second (smpCnt wraps at this value)
+    pub fn samples_per_second(&self) -> u32 {
+        (self.samples_per_cycle as f64 * self.nominal_frequency).round() as u32
+    }
+
+    /// Check the settings for values the measurement cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // The half-cycle Fourier window needs at least one sample
+        if self.samples_per_cycle < 2 {
+            return Err(format!("sv: samples_per_cycle {} must be at least 2", self.samples_per_cycle).into());
+        }
+        if !(self.nominal_frequency.is_finite() && self.nominal_frequency > 0.0) {
+            return Err(format!("sv: nominal_frequency {} must be above 0", self.nominal_frequency).into());
+        }
+        // smpCnt is a 16-bit counter wrapping at the sample rate
+        let samples_per_second = self.samples_per_second();
+        if samples_per_second == 0 || samples_per_second > MAX_SAMPLES_PER_SECOND {
+            return Err(format!(
+                "sv: sample rate {} must be in 1..={} samples per second",
+                samples_per_second, MAX_SAMPLES_PER_SECOND
+            )
+            .into());
+        }
+        if let Some(vlan_id) = self.vlan_id.filter(|&id| id > MAX_VLAN_ID) {
+            return Err(format!("sv: VLAN ID {} out of range (0..{})", vlan_id, MAX_VLAN_ID).into());
+        }
+        Ok(())
+    }
 }
 
 impl Default for SvConfig {
@@ -112,40 +764,199 @@ impl Default for SvConfig {
             samples_per_cycle: 80,
             interface: "eth0".to_string(),
             multicast_mac: "01:0C:CD:04:00:00".to_string(),
+            appid: None,
+            sv_id: None,
+            conf_rev: None,
+            src_mac: None,
+            vlan_id: None,
+            channel_layout: default_channel_layout(),
+            nominal_frequency: default_nominal_frequency(),
+            gap_policy: GapPolicy::Block,
+            packet_ring: PacketRingConfig::default(),
+            hardware_timestamps: false,
         }
     }
 }
 
+/// Real-time scheduling of the protection loop (Linux)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeConfig {
+    /// Run the protection loop under SCHED_FIFO
+    pub enabled: bool,
+    /// SCHED_FIFO priority (1..99)
+    #[serde(default = "default_rt_priority")]
+    pub priority: i32,
+    /// CPUs the protection loop may run on (empty = no restriction)
+    #[serde(default)]
+    pub cpu_affinity: Vec<usize>,
+    /// Lock all current and future memory with mlockall
+    #[serde(default = "default_lock_memory")]
+    pub lock_memory: bool,
+    /// Stack size touched in advance so it is never faulted in later (bytes)
+    #[serde(default = "default_prefault_stack")]
+    pub prefault_stack: usize,
+}
+
+fn default_rt_priority() -> i32 {
+    80
+}
+
+fn default_lock_memory() -> bool {
+    true
+}
+
+fn default_prefault_stack() -> usize {
+    512 * 1024
+}
+
+impl Default for RealtimeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            priority: default_rt_priority(),
+            cpu_affinity: Vec::new(),
+            lock_memory: default_lock_memory(),
+            prefault_stack: default_prefault_stack(),
+        }
+    }
+}
+
+/// PTOC stage list as stored in JSON: a single stage object or a list of stages
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PtocStagesConfig {
+    Single(PtocConfig),
+    Stages(Vec<PtocConfig>),
+}
+
+/// Deserialize PTOC stages, accepting the single-stage format of older configuration files
+fn deserialize_ptoc_stages<'de, D>(deserializer: D) -> Result<Vec<PtocConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match PtocStagesConfig::deserialize(deserializer)? {
+        PtocStagesConfig::Single(stage) => vec![stage],
+        PtocStagesConfig::Stages(stages) => stages,
+    })
+}
+
 /// Complete system configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemConfig {
-    pub ptoc: PtocConfig,
+    /// Overcurrent stages (I>, I>>, ...) in order, evaluated on the same input
+    #[serde(deserialize_with = "deserialize_ptoc_stages")]
+    pub ptoc: Vec<PtocConfig>,
+    /// Instantaneous overcurrent stages, evaluated on every sample
+    #[serde(default)]
+    pub pioc: Vec<PiocConfig>,
     pub ct: CtConfig,
+    /// CT of the measured neutral channel (In); the phase CT when absent
+    #[serde(default)]
+    pub neutral_ct: Option<CtConfig>,
     pub adc: AdcConfig,
+    /// Voltage transformer of the voltage channels
+    #[serde(default)]
+    pub vt: VtConfig,
+    /// ADC scaling of the voltage channels (counts to secondary volts)
+    #[serde(default = "default_voltage_adc")]
+    pub voltage_adc: AdcConfig,
+    /// Directional element supervising directional stages
+    #[serde(default)]
+    pub rdir: RdirConfig,
+    /// Earth-fault overcurrent on the residual current
+    #[serde(default)]
+    pub earth_fault: EarthFaultConfig,
+    /// Sensitive directional earth-fault stages on the neutral channel
+    #[serde(default)]
+    pub psde: Vec<PsdeConfig>,
+    /// Intermittent earth-fault detection on the neutral channel
+    #[serde(default)]
+    pub ptef: Option<PtefConfig>,
+    /// Negative-sequence overcurrent stages (I2>, I2>>, ...)
+    #[serde(default)]
+    pub negative_sequence: Vec<PtocConfig>,
+    /// Broken-conductor detection on I2/I1
+    #[serde(default)]
+    pub broken_conductor: Option<BrokenConductorConfig>,
     pub goose: GooseConfig,
     pub sv: SvConfig,
+    /// Subscribed GOOSE messages (interlocking, breaker status)
+    #[serde(default)]
+    pub goose_subscriber: GooseSubscriberConfig,
+    /// IED test mode: published GOOSE carries the simulation bit and test quality
+    #[serde(default)]
+    pub test_mode: bool,
+    /// Real-time scheduling of the protection loop
+    #[serde(default)]
+    pub realtime: RealtimeConfig,
 }
 
 impl Default for SystemConfig {
     fn default() -> Self {
         Self {
-            ptoc: PtocConfig::default(),
+            ptoc: vec![PtocConfig::default()],
+            pioc: Vec::new(),
             ct: CtConfig::default(),
+            neutral_ct: None,
             adc: AdcConfig::default(),
+            vt: VtConfig::default(),
+            voltage_adc: default_voltage_adc(),
+            rdir: RdirConfig::default(),
+            earth_fault: EarthFaultConfig::default(),
+            psde: Vec::new(),
+            ptef: None,
+            negative_sequence: Vec::new(),
+            broken_conductor: None,
             goose: GooseConfig::default(),
             sv: SvConfig::default(),
+            goose_subscriber: GooseSubscriberConfig::default(),
+            test_mode: false,
+            realtime: RealtimeConfig::default(),
         }
     }
 }
 
 impl SystemConfig {
     /// Load configuration from JSON file
+    ///
+    /// # Returns
+    /// The configuration, or an error if it cannot be read or fails `validate`
     pub fn from_json_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let content = std::fs::read_to_string(path)?;
-        let config = serde_json::from_str(&content)?;
+        let config: Self = serde_json::from_str(&content)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check all settings for values the protection functions cannot operate with
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let stages = [
+            ("ptoc", &self.ptoc),
+            ("earth_fault.stages", &self.earth_fault.stages),
+            ("negative_sequence", &self.negative_sequence),
+        ];
+        for (name, stages) in stages {
+            for (index, stage) in stages.iter().enumerate() {
+                stage.validate().map_err(|e| format!("{}[{}]: {}", name, index, e))?;
+            }
+        }
+        for (index, stage) in self.pioc.iter().enumerate() {
+            stage.validate().map_err(|e| format!("pioc[{}]: {}", index, e))?;
+        }
+        for (index, stage) in self.psde.iter().enumerate() {
+            stage.validate().map_err(|e| format!("psde[{}]: {}", index, e))?;
+        }
+        if let Some(detector) = &self.broken_conductor {
+            detector.validate().map_err(|e| format!("broken_conductor: {}", e))?;
+        }
+        self.goose.validate()?;
+        self.sv.validate()?;
+        if let Some(ptef) = &self.ptef {
+            ptef.validate()?;
+        }
+        Ok(())
+    }
+
     /// Save configuration to JSON file
     pub fn to_json_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let content = serde_json::to_string_pretty(self)?;
@@ -153,3 +964,134 @@ impl SystemConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(SystemConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_tms_and_curve_constants_rejected() {
+        for tms in [0.0, -0.1, f64::NAN] {
+            let config = SystemConfig {
+                ptoc: vec![PtocConfig { tms, ..PtocConfig::default() }],
+                ..SystemConfig::default()
+            };
+            assert!(config.validate().is_err());
+        }
+
+        let curve = |a: f64, p: f64| PtocConfig {
+            curve: CurveType::UserDefined { a, b: 0.0, p },
+            ..PtocConfig::default()
+        };
+        assert!(curve(0.14, 0.02).validate().is_ok());
+        assert!(curve(-0.14, 0.02).validate().is_err());
+        assert!(curve(f64::INFINITY, 0.02).validate().is_err());
+        assert!(curve(0.14, f64::NAN).validate().is_err());
+    }
+
+    #[test]
+    fn test_dropout_ratio_rejected() {
+        for dropout_ratio in [0.0, 1.05, f64::NAN] {
+            let stage = PtocConfig { dropout_ratio, ..PtocConfig::default() };
+            assert!(stage.validate().is_err());
+
+            let config = SystemConfig {
+                pioc: vec![PiocConfig { dropout_ratio, ..PiocConfig::default() }],
+                ..SystemConfig::default()
+            };
+            assert!(config.validate().unwrap_err().to_string().starts_with("pioc[0]"));
+        }
+        assert!(PtocConfig { dropout_ratio: 1.0, ..PtocConfig::default() }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_inverse_reset_requires_reset_time() {
+        let stage = PtocConfig {
+            curve: CurveType::IecStandardInverse,
+            reset_mode: ResetMode::Inverse,
+            ..PtocConfig::default()
+        };
+        let config = SystemConfig {
+            negative_sequence: vec![stage.clone()],
+            ..SystemConfig::default()
+        };
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.starts_with("negative_sequence[0]"));
+
+        // Explicit reset constant, or one defined by the IEEE curves
+        assert!(PtocConfig { reset_time: 1000, ..stage.clone() }.validate().is_ok());
+        assert!(PtocConfig { curve: CurveType::IeeeVeryInverse, ..stage }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_vlan_out_of_range_rejected() {
+        let mut config = SystemConfig::default();
+        config.goose.vlan_id = 5000;
+        assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.goose.vlan_priority = 8;
+        assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.sv.vlan_id = Some(4095);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_sample_rate_rejected() {
+        for samples_per_cycle in [0, 1] {
+            let mut config = SystemConfig::default();
+            config.sv.samples_per_cycle = samples_per_cycle;
+            assert!(config.validate().is_err());
+        }
+        for nominal_frequency in [0.0, -50.0, f64::NAN, f64::INFINITY] {
+            let mut config = SystemConfig::default();
+            config.sv.nominal_frequency = nominal_frequency;
+            assert!(config.validate().unwrap_err().to_string().starts_with("sv:"));
+        }
+
+        // Rounds to 0 samples per second, or exceeds the 16-bit sample counter
+        for (samples_per_cycle, nominal_frequency) in [(2, 0.1), (1024, 65.0)] {
+            let mut config = SystemConfig::default();
+            config.sv.samples_per_cycle = samples_per_cycle;
+            config.sv.nominal_frequency = nominal_frequency;
+            assert!(config.validate().is_err());
+        }
+        let mut config = SystemConfig::default();
+        config.sv.samples_per_cycle = 1024;
+        config.sv.nominal_frequency = 64.0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_goose_retransmission_settings_rejected() {
+        let invalid = [
+            GooseConfig { t1: 0, ..GooseConfig::default() },
+            GooseConfig { t0: 1, t1: 2, ..GooseConfig::default() },
+            GooseConfig { backoff_factor: 1.0, ..GooseConfig::default() },
+            GooseConfig { backoff_factor: f64::NAN, ..GooseConfig::default() },
+        ];
+        for goose in invalid {
+            let config = SystemConfig { goose, ..SystemConfig::default() };
+            assert!(config.validate().unwrap_err().to_string().starts_with("goose:"));
+        }
+    }
+
+    #[test]
+    fn test_ptef_pulse_count_zero_rejected() {
+        let config = SystemConfig {
+            ptef: Some(PtefConfig {
+                pulse_count: 0,
+                ..PtefConfig::default()
+            }),
+            ..SystemConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,568 @@
+/// Real-time protection engine: SV samples to measurements, protection and GOOSE outputs
+use crate::config::SystemConfig;
+use crate::io::{GooseOutputs, SampleData, SvChannel};
+use crate::measurement::{
+    ChannelMeasurement, CurrentScaler, FourierFilter, MeasurementFrame, Phase, Quality, RmsCalculator,
+    VoltageScaler,
+};
+use crate::metrics::EngineMetrics;
+use crate::protection::{
+    BrokenConductor, Directions, MultiStagePtoc, NegativeSequencePtoc, Pioc, ProtectionFunction, ProtectionResult,
+    Psde, Ptef, Rdir, ResidualPtoc,
+};
+use std::time::Instant;
+
+/// Protection engine evaluating every received sample
+///
+/// Each sample is scaled to primary units (the neutral current with its own
+/// CT) and the RMS value and fundamental phasor of every channel are updated
+/// over a sliding one-cycle window.
+/// Channels are invalid until the first full window has been received. The
+/// functions are then evaluated on the resulting measurement frame:
+///
+/// - RDIR first, so directional stages use the directions of the same frame
+/// - PTOC and PIOC on the phase currents (PIOC on the sampled values)
+/// - Earth-fault stages on the measured neutral or calculated residual current
+/// - PSDE and PTEF on the measured neutral current
+/// - Negative-sequence and broken-conductor functions on the symmetrical
+///   components of the phase currents
+///
+/// The processing time of every sample is recorded in the engine metrics;
+/// the application records the transfer time of each published state change.
+pub struct ProtectionEngine {
+    current_scaler: CurrentScaler,
+    neutral_scaler: CurrentScaler,
+    voltage_scaler: VoltageScaler,
+    currents: [ChannelFilter; 4],
+    voltages: [ChannelFilter; 4],
+    rdir: Rdir,
+    ptoc: MultiStagePtoc,
+    pioc: Vec<Pioc>,
+    earth_fault: Vec<ResidualPtoc>,
+    psde: Vec<Psde>,
+    ptef: Option<Ptef>,
+    negative_sequence: Vec<NegativeSequencePtoc>,
+    broken_conductor: Option<BrokenConductor>,
+    frame: MeasurementFrame,
+    result: ProtectionResult,
+    metrics: EngineMetrics,
+}
+
+impl ProtectionEngine {
+    /// Create an engine from the system configuration
+    pub fn new(config: &SystemConfig) -> Self {
+        let samples_per_cycle = config.sv.samples_per_cycle;
+        Self {
+            current_scaler: CurrentScaler::new(config.adc.clone(), config.ct.clone()),
+            neutral_scaler: CurrentScaler::new(
+                config.adc.clone(),
+                config.neutral_ct.clone().unwrap_or_else(|| config.ct.clone()),
+            ),
+            voltage_scaler: VoltageScaler::new(config.voltage_adc.clone(), config.vt.clone()),
+            currents: std::array::from_fn(|_| ChannelFilter::new(samples_per_cycle)),
+            voltages: std::array::from_fn(|_| ChannelFilter::new(samples_per_cycle)),
+            rdir: Rdir::new(config.rdir.clone(), config.sv.nominal_frequency),
+            ptoc: MultiStagePtoc::new(config.ptoc.clone()),
+            pioc: config
+                .pioc
+                .iter()
+                .map(|stage| Pioc::new(stage.clone(), samples_per_cycle, config.sv.nominal_frequency))
+                .collect(),
+            earth_fault: config
+                .earth_fault
+                .stages
+                .iter()
+                .map(|stage| ResidualPtoc::new(stage.clone(), config.earth_fault.source))
+                .collect(),
+            psde: config.psde.iter().cloned().map(Psde::new).collect(),
+            ptef: config.ptef.clone().map(Ptef::new),
+            negative_sequence: config.negative_sequence.iter().cloned().map(NegativeSequencePtoc::new).collect(),
+            broken_conductor: config.broken_conductor.clone().map(BrokenConductor::new),
+            frame: MeasurementFrame::default(),
+            result: ProtectionResult::NoTrip,
+            metrics: EngineMetrics::new(),
+        }
+    }
+
+    /// Process one sample
+    ///
+    /// # Arguments
+    /// * `sample` - Sample of all channels at one sampling instant
+    ///
+    /// # Returns
+    /// Protection outputs to publish
+    pub fn process_sample(&mut self, sample: &SampleData) -> GooseOutputs {
+        let start = Instant::now();
+        self.frame.timestamp = sample.timestamp;
+
+        for phase in Phase::ALL {
+            let index = phase.index();
+
+            let channel = SvChannel::current(phase);
+            let scaler = if phase == Phase::N { &self.neutral_scaler } else { &self.current_scaler };
+            let current = scaler.scale_to_primary(sample.value(channel));
+            self.frame.currents[index] = self.currents[index].add_sample(current, sample.channel_quality(channel));
+
+            let channel = SvChannel::voltage(phase);
+            let voltage = self.voltage_scaler.scale_to_primary(sample.value(channel));
+            self.frame.voltages[index] = self.voltages[index].add_sample(voltage, sample.channel_quality(channel));
+        }
+
+        let directions = self.rdir.process(&self.frame);
+        self.ptoc.set_directions(directions);
+        self.result = self.ptoc.process(&self.frame);
+        for stage in &mut self.pioc {
+            stage.set_directions(directions);
+            self.result = self.result.combine(stage.process(&self.frame));
+        }
+        for stage in &mut self.earth_fault {
+            stage.set_directions(directions);
+            self.result = self.result.combine(stage.process(&self.frame));
+        }
+        for stage in &mut self.psde {
+            self.result = self.result.combine(stage.process(&self.frame));
+        }
+        if let Some(ptef) = &mut self.ptef {
+            self.result = self.result.combine(ptef.process(&self.frame));
+        }
+        for stage in &mut self.negative_sequence {
+            stage.set_directions(directions);
+            self.result = self.result.combine(stage.process(&self.frame));
+        }
+        if let Some(detector) = &mut self.broken_conductor {
+            self.result = self.result.combine(detector.process(&self.frame));
+        }
+        let outputs = self.outputs();
+
+        self.metrics.processing_time.record(start.elapsed().as_micros() as u64);
+        outputs
+    }
+
+    /// Record the transfer time of a published state change
+    ///
+    /// A send time before the receive time (clock step) is not recorded but
+    /// counted as skipped.
+    ///
+    /// # Arguments
+    /// * `receive_time` - Software receive time of the sample that caused the change
+    ///   (microseconds, system clock)
+    /// * `sent_time` - Time the GOOSE send completed (microseconds, system clock)
+    pub fn record_transfer_time(&mut self, receive_time: u64, sent_time: u64) {
+        match sent_time.checked_sub(receive_time) {
+            Some(transfer_time) => self.metrics.record_transfer_time(transfer_time),
+            None => self.metrics.skip_transfer_time(),
+        }
+    }
+
+    /// Get the transfer time and processing time histograms
+    pub fn metrics(&self) -> &EngineMetrics {
+        &self.metrics
+    }
+
+    /// Clear the transfer time and processing time histograms
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+
+    /// Get the protection outputs of the last processed sample
+    ///
+    /// Outputs are invalid while one of the phase currents is invalid.
+    pub fn outputs(&self) -> GooseOutputs {
+        let invalid = Phase::PHASES
+            .iter()
+            .any(|&phase| self.frame.current(phase).quality.is_invalid());
+        let mut op = self.ptoc.trip_phases();
+        let mut start = self.ptoc.start_phases();
+        for stage in &self.pioc {
+            op = op.union(stage.trip_phases());
+            start = start.union(stage.start_phases());
+        }
+        for stage in &self.earth_fault {
+            op = op.union(stage.trip_phases());
+            start = start.union(stage.start_phases());
+        }
+        for stage in &self.psde {
+            op = op.union(stage.trip_phases());
+            start = start.union(stage.start_phases());
+        }
+        if let Some(ptef) = &self.ptef {
+            op = op.union(ptef.trip_phases());
+            start = start.union(ptef.start_phases());
+        }
+        for stage in &self.negative_sequence {
+            op = op.union(stage.trip_phases());
+            start = start.union(stage.start_phases());
+        }
+        if let Some(detector) = &self.broken_conductor {
+            op = op.union(detector.trip_phases());
+            start = start.union(detector.start_phases());
+        }
+        GooseOutputs {
+            op,
+            start,
+            quality: if invalid { Quality::INVALID } else { Quality::GOOD },
+        }
+    }
+
+    /// Get the measurement frame of the last processed sample
+    pub fn frame(&self) -> &MeasurementFrame {
+        &self.frame
+    }
+
+    /// Get the combined result of the last evaluation
+    pub fn result(&self) -> ProtectionResult {
+        self.result
+    }
+
+    /// Get the fault directions of the last processed sample
+    pub fn directions(&self) -> Directions {
+        self.rdir.directions()
+    }
+
+    /// Get the overcurrent stages
+    pub fn ptoc(&self) -> &MultiStagePtoc {
+        &self.ptoc
+    }
+
+    /// Get the instantaneous overcurrent stages
+    pub fn pioc(&self) -> &[Pioc] {
+        &self.pioc
+    }
+
+    /// Get the earth-fault stages
+    pub fn earth_fault(&self) -> &[ResidualPtoc] {
+        &self.earth_fault
+    }
+
+    /// Get the sensitive directional earth-fault stages
+    pub fn psde(&self) -> &[Psde] {
+        &self.psde
+    }
+
+    /// Get the intermittent earth-fault function, if configured
+    pub fn ptef(&self) -> Option<&Ptef> {
+        self.ptef.as_ref()
+    }
+
+    /// Get the negative-sequence overcurrent stages
+    pub fn negative_sequence(&self) -> &[NegativeSequencePtoc] {
+        &self.negative_sequence
+    }
+
+    /// Get the broken-conductor function, if configured
+    pub fn broken_conductor(&self) -> Option<&BrokenConductor> {
+        self.broken_conductor.as_ref()
+    }
+
+    /// Reset measurements and protection functions
+    pub fn reset(&mut self) {
+        self.currents.iter_mut().for_each(ChannelFilter::reset);
+        self.voltages.iter_mut().for_each(ChannelFilter::reset);
+        self.rdir.reset();
+        self.ptoc.reset();
+        self.pioc.iter_mut().for_each(Pioc::reset);
+        self.earth_fault.iter_mut().for_each(ResidualPtoc::reset);
+        self.psde.iter_mut().for_each(Psde::reset);
+        self.ptef.iter_mut().for_each(Ptef::reset);
+        self.negative_sequence.iter_mut().for_each(NegativeSequencePtoc::reset);
+        self.broken_conductor.iter_mut().for_each(BrokenConductor::reset);
+        self.frame = MeasurementFrame::default();
+        self.result = ProtectionResult::NoTrip;
+    }
+}
+
+/// Sliding one-cycle RMS and fundamental phasor of one channel
+struct ChannelFilter {
+    rms: RmsCalculator,
+    dft: FourierFilter,
+}
+
+impl ChannelFilter {
+    fn new(samples_per_cycle: usize) -> Self {
+        Self {
+            rms: RmsCalculator::new(samples_per_cycle),
+            dft: FourierFilter::full_cycle(samples_per_cycle),
+        }
+    }
+
+    /// Add a sample in primary units; invalid until the window is full
+    fn add_sample(&mut self, value: f64, quality: Quality) -> ChannelMeasurement {
+        let rms = self.rms.add_sample(value);
+        let phasor = self.dft.add_sample(value);
+        let quality = if self.rms.is_full() && self.dft.is_full() {
+            quality
+        } else {
+            Quality::INVALID
+        };
+        ChannelMeasurement {
+            rms,
+            phasor,
+            quality,
+            instantaneous: value,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.rms.reset();
+        self.dft.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BrokenConductorConfig, CtConfig, EarthFaultConfig, PiocConfig, PsdeConfig, PtocConfig};
+    use crate::protection::{ResidualSource, SefCriterion};
+    use crate::protection::{Direction, DirectionMode};
+
+    const SAMPLE_PERIOD_US: u64 = 250;
+
+    /// Feed a balanced three-phase sine with the given primary RMS current
+    fn feed(engine: &mut ProtectionEngine, current: f64, start: u64, count: u64) -> GooseOutputs {
+        // 400/1 CT and 1 mA per count: 1 A primary = 2.5 counts
+        let peak = current * 2.5 * std::f64::consts::SQRT_2;
+        let mut outputs = GooseOutputs::default();
+        for n in start..start + count {
+            let angle = 2.0 * std::f64::consts::PI * n as f64 / 80.0;
+            let entries = [0.0, -120.0_f64, 120.0]
+                .iter()
+                .map(|shift| ((peak * (angle + shift.to_radians()).sin()) as i32, 0))
+                .chain([(0, 0)]);
+            let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, n as u16, n * SAMPLE_PERIOD_US);
+            outputs = engine.process_sample(&sample);
+        }
+        outputs
+    }
+
+    /// Feed balanced 20 kV voltages and currents lagging them by `lag` degrees
+    fn feed_with_voltages(engine: &mut ProtectionEngine, current: f64, lag: f64, start: u64, count: u64) -> GooseOutputs {
+        // 20000/100 V VT and 10 mV per count: 1 V primary = 0.5 counts
+        let current_peak = current * 2.5 * std::f64::consts::SQRT_2;
+        let voltage_peak = 11547.0 * 0.5 * std::f64::consts::SQRT_2;
+        let mut outputs = GooseOutputs::default();
+        for n in start..start + count {
+            let angle = 2.0 * std::f64::consts::PI * n as f64 / 80.0;
+            let shifts = [0.0, -120.0_f64, 120.0];
+            let currents = shifts
+                .iter()
+                .map(|shift| ((current_peak * (angle + (shift - lag).to_radians()).sin()) as i32, 0));
+            let voltages = shifts
+                .iter()
+                .map(|shift| ((voltage_peak * (angle + shift.to_radians()).sin()) as i32, 0));
+            let entries = currents.chain([(0, 0)]).chain(voltages).chain([(0, 0)]);
+            let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, n as u16, n * SAMPLE_PERIOD_US);
+            outputs = engine.process_sample(&sample);
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_invalid_until_window_full() {
+        let mut engine = ProtectionEngine::new(&SystemConfig::default());
+        assert!(feed(&mut engine, 50.0, 0, 40).quality.is_invalid());
+        assert!(feed(&mut engine, 50.0, 40, 40).quality.is_good());
+        assert!((engine.frame().current(Phase::A).rms - 50.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_no_trip_below_pickup() {
+        let mut engine = ProtectionEngine::new(&SystemConfig::default());
+        let outputs = feed(&mut engine, 50.0, 0, 800);
+        assert!(!outputs.op.general());
+        assert!(!outputs.start.general());
+        assert_eq!(engine.result(), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_trip_after_delay() {
+        let mut engine = ProtectionEngine::new(&SystemConfig::default());
+        feed(&mut engine, 50.0, 0, 80);
+
+        // 150 A, Tset 100 ms: started after the first cycle, tripped after 100 ms
+        let outputs = feed(&mut engine, 150.0, 80, 200);
+        assert!(outputs.start.general());
+        assert!(!outputs.op.general());
+
+        let outputs = feed(&mut engine, 150.0, 280, 400);
+        assert!(outputs.op.phs_a && outputs.op.phs_b && outputs.op.phs_c);
+        assert_eq!(engine.result(), ProtectionResult::Trip);
+
+        engine.reset();
+        assert!(!engine.outputs().op.general());
+    }
+
+    #[test]
+    fn test_trip_clears_after_fault() {
+        let mut engine = ProtectionEngine::new(&SystemConfig::default());
+        assert!(feed(&mut engine, 150.0, 0, 800).op.general());
+
+        // Fault cleared: Op drops out once the RMS window sees the load current
+        let outputs = feed(&mut engine, 50.0, 800, 80);
+        assert!(!outputs.op.general());
+        assert!(!outputs.start.general());
+        assert_eq!(engine.result(), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_pioc_trips_before_ptoc() {
+        let config = SystemConfig {
+            pioc: vec![PiocConfig { iset: 1000.0, ..PiocConfig::default() }],
+            ..SystemConfig::default()
+        };
+        let mut engine = ProtectionEngine::new(&config);
+        feed(&mut engine, 50.0, 0, 80);
+
+        // 3000 A: PIOC trips within half a cycle, PTOC (100 ms) is only started
+        let outputs = feed(&mut engine, 3000.0, 80, 40);
+        assert!(outputs.op.general());
+        assert!(engine.pioc()[0].state().is_tripped());
+        assert!(!engine.ptoc().stages()[0].state().is_tripped());
+    }
+
+    #[test]
+    fn test_directional_stage() {
+        let config = SystemConfig {
+            ptoc: vec![PtocConfig {
+                tset: 20,
+                direction: DirectionMode::Forward,
+                ..PtocConfig::default()
+            }],
+            ..SystemConfig::default()
+        };
+
+        // Reverse fault: started nowhere, no trip
+        let mut engine = ProtectionEngine::new(&config);
+        let outputs = feed_with_voltages(&mut engine, 500.0, 240.0, 0, 400);
+        assert_eq!(engine.directions().get(Phase::A), Direction::Backward);
+        assert!(!outputs.start.general() && !outputs.op.general());
+
+        // Forward fault
+        let mut engine = ProtectionEngine::new(&config);
+        let outputs = feed_with_voltages(&mut engine, 500.0, 60.0, 0, 400);
+        assert_eq!(engine.directions().phases, [Direction::Forward; 3]);
+        assert!(outputs.op.general());
+    }
+
+    #[test]
+    fn test_earth_fault_measured_neutral() {
+        // 50/1 core-balance CT on In: 1 A primary = 20 counts
+        let config = SystemConfig {
+            neutral_ct: Some(CtConfig { primary: 50.0, secondary: 1.0 }),
+            earth_fault: EarthFaultConfig {
+                source: ResidualSource::Measured,
+                stages: vec![PtocConfig { iset: 10.0, tset: 50, ..PtocConfig::default() }],
+            },
+            ..SystemConfig::default()
+        };
+        let mut engine = ProtectionEngine::new(&config);
+        let mut outputs = GooseOutputs::default();
+        for n in 0..400u64 {
+            let angle = 2.0 * std::f64::consts::PI * n as f64 / 80.0;
+            let neutral = (15.0 * 20.0 * std::f64::consts::SQRT_2 * angle.sin()) as i32;
+            let entries = [(0, 0), (0, 0), (0, 0), (neutral, 0)];
+            let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, n as u16, n * SAMPLE_PERIOD_US);
+            outputs = engine.process_sample(&sample);
+        }
+        assert!((engine.frame().current(Phase::N).rms - 15.0).abs() < 0.1);
+        assert!(outputs.op.neut && !outputs.op.phs_a);
+        assert!(engine.earth_fault()[0].state().is_tripped());
+    }
+
+    #[test]
+    fn test_earth_fault_calculated_balanced_load() {
+        let config = SystemConfig {
+            earth_fault: EarthFaultConfig {
+                source: ResidualSource::Calculated,
+                stages: vec![PtocConfig { iset: 10.0, tset: 0, ..PtocConfig::default() }],
+            },
+            ..SystemConfig::default()
+        };
+        // Balanced 90 A load has no residual current
+        let mut engine = ProtectionEngine::new(&config);
+        let outputs = feed(&mut engine, 90.0, 0, 400);
+        assert!(!outputs.start.neut && !outputs.op.neut);
+    }
+
+    #[test]
+    fn test_psde_core_balance_ct() {
+        // 60/1 core-balance CT; phase A earthed over a Petersen coil
+        let config = SystemConfig {
+            neutral_ct: Some(CtConfig { primary: 60.0, secondary: 1.0 }),
+            psde: vec![PsdeConfig {
+                iset: 1.0,
+                tset: 100,
+                criterion: SefCriterion::Wattmetric,
+                direction: DirectionMode::Forward,
+                ..PsdeConfig::default()
+            }],
+            ..SystemConfig::default()
+        };
+        let mut engine = ProtectionEngine::new(&config);
+
+        // Healthy phases at line voltage (0.5 counts/V), 2 A active 3I0 in phase with −3V0
+        let voltage_peak = 20000.0 * 0.5 * std::f64::consts::SQRT_2;
+        let neutral_peak = 2.0 / 60.0 * 1000.0 * std::f64::consts::SQRT_2;
+        let mut outputs = GooseOutputs::default();
+        for n in 0..800u64 {
+            let angle = 2.0 * std::f64::consts::PI * n as f64 / 80.0;
+            let voltages = [0.0, -150.0_f64, 150.0].map(|shift| {
+                let peak = if shift == 0.0 { 0.0 } else { voltage_peak };
+                ((peak * (angle + shift.to_radians()).sin()) as i32, 0)
+            });
+            let neutral = (neutral_peak * angle.sin()) as i32;
+            let entries = [(0, 0), (0, 0), (0, 0), (neutral, 0)].into_iter().chain(voltages);
+            let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, n as u16, n * SAMPLE_PERIOD_US);
+            outputs = engine.process_sample(&sample);
+        }
+        assert!(outputs.op.neut);
+        assert!(engine.psde()[0].state().is_tripped());
+    }
+
+    #[test]
+    fn test_open_phase() {
+        let config = SystemConfig {
+            negative_sequence: vec![PtocConfig { iset: 40.0, tset: 50, ..PtocConfig::default() }],
+            broken_conductor: Some(BrokenConductorConfig { tset: 50, ..BrokenConductorConfig::default() }),
+            ..SystemConfig::default()
+        };
+        let mut engine = ProtectionEngine::new(&config);
+        feed(&mut engine, 60.0, 0, 80);
+        assert!(!engine.outputs().start.general());
+
+        // Phase C open at 60 A load: I2 = I1 = 30 A, below the I2 stage
+        let peak = 60.0 * 2.5 * std::f64::consts::SQRT_2;
+        let mut outputs = GooseOutputs::default();
+        for n in 80..480u64 {
+            let angle = 2.0 * std::f64::consts::PI * n as f64 / 80.0;
+            let current = |shift: f64| (peak * 3.0_f64.sqrt() / 2.0 * (angle + shift.to_radians()).sin()) as i32;
+            let entries = [(current(-30.0), 0), (current(150.0), 0), (0, 0), (0, 0)];
+            let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, n as u16, n * SAMPLE_PERIOD_US);
+            outputs = engine.process_sample(&sample);
+        }
+        let sequence = engine.frame().current_sequence();
+        assert!((sequence.negative.magnitude() - 30.0).abs() < 0.5);
+        assert!(outputs.op.general());
+        assert!(engine.broken_conductor().unwrap().state().is_tripped());
+        assert!(engine.negative_sequence()[0].state().is_idle());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut engine = ProtectionEngine::new(&SystemConfig::default());
+        feed(&mut engine, 50.0, 0, 80);
+        assert_eq!(engine.metrics().processing_time.count(), 80);
+
+        engine.record_transfer_time(1_000_000, 1_001_250);
+        engine.record_transfer_time(2_000_000, 2_004_000);
+        assert_eq!(engine.metrics().transfer_time.max(), Some(4_000));
+        assert_eq!(engine.metrics().tt6_violations(), 1);
+
+        // Send time before the receive time after a clock step
+        engine.record_transfer_time(3_000_000, 2_999_000);
+        assert_eq!(engine.metrics().transfer_time.count(), 2);
+        assert_eq!(engine.metrics().skipped_transfer_times(), 1);
+
+        engine.reset_metrics();
+        assert_eq!(engine.metrics().processing_time.count(), 0);
+    }
+}
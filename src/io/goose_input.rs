@@ -0,0 +1,716 @@
+/// GOOSE input handling using iec_61850_lib
+use crate::config::{GooseSubscriberConfig, GooseSubscriptionConfig};
+use crate::measurement::Quality;
+use super::goose_output::{iec61850_to_timestamp, parse_mac_address};
+use std::error::Error;
+use socket2::{Socket, Domain, Type, Protocol};
+use iec_61850_lib::decode_basics::decode_ethernet_header;
+use iec_61850_lib::decode_goose::decode_goose_pdu;
+use iec_61850_lib::types::{EthernetHeader, IECData, IECGoosePdu};
+
+#[cfg(target_os = "linux")]
+use super::network_utils::{get_interface_index, bind_to_interface, MAX_ETHERNET_FRAME_SIZE, MIN_ETHERNET_FRAME_SIZE};
+
+/// Double point status (Dbpos), e.g. breaker position from XCBR.Pos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dbpos {
+    /// Intermediate state (00)
+    Intermediate,
+    /// Off / open (01)
+    Off,
+    /// On / closed (10)
+    On,
+    /// Bad state (11)
+    Bad,
+}
+
+/// Decoded value of a GOOSE dataset member
+#[derive(Debug, Clone, PartialEq)]
+pub enum GooseValue {
+    /// Single point status, e.g. Op.general or a blocking signal
+    Boolean(bool),
+    /// Double point status
+    Dbpos(Dbpos),
+    /// Quality (13-bit bit string)
+    Quality(Quality),
+    /// UTC time in microseconds
+    Timestamp(u64),
+    /// Signed integer (also enumerations)
+    Integer(i64),
+    /// Unsigned integer
+    Unsigned(u64),
+    /// Floating point value
+    Float(f64),
+    /// Other bit string, as received
+    BitString(Vec<u8>),
+    /// Structured member (data object with its attributes)
+    Structure(Vec<GooseValue>),
+    /// Member type not used by protection logic
+    Other,
+}
+
+impl GooseValue {
+    /// Convert a decoded dataset member
+    pub fn from_iec_data(data: &IECData) -> Self {
+        match data {
+            IECData::Boolean(value) => GooseValue::Boolean(*value),
+            IECData::Int8(value) => GooseValue::Integer(i64::from(*value)),
+            IECData::Int16(value) => GooseValue::Integer(i64::from(*value)),
+            IECData::Int32(value) => GooseValue::Integer(i64::from(*value)),
+            IECData::Int64(value) => GooseValue::Integer(*value),
+            IECData::Int8u(value) => GooseValue::Unsigned(u64::from(*value)),
+            IECData::Int16u(value) => GooseValue::Unsigned(u64::from(*value)),
+            IECData::Int32u(value) => GooseValue::Unsigned(u64::from(*value)),
+            IECData::Float32(value) => GooseValue::Float(f64::from(*value)),
+            IECData::Float64(value) => GooseValue::Float(*value),
+            IECData::UtcTime(bytes) => GooseValue::Timestamp(iec61850_to_timestamp(bytes)),
+            IECData::BitString { padding, val } => decode_bit_string(*padding, val),
+            IECData::Structure(members) | IECData::Array(members) => {
+                GooseValue::Structure(members.iter().map(GooseValue::from_iec_data).collect())
+            }
+            _ => GooseValue::Other,
+        }
+    }
+
+    /// Get the value as a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            GooseValue::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a double point status
+    pub fn as_dbpos(&self) -> Option<Dbpos> {
+        match self {
+            GooseValue::Dbpos(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a quality
+    pub fn as_quality(&self) -> Option<Quality> {
+        match self {
+            GooseValue::Quality(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Decode a bit string as Dbpos (2 bits), quality (13 bits) or raw bits
+///
+/// Bit 0 of an ASN.1 bit string is the most significant bit of the first octet.
+/// The two validity bits are read as a number with bit 0 as the most
+/// significant bit ("01" = invalid), matching the `Quality` representation.
+fn decode_bit_string(padding: u8, val: &[u8]) -> GooseValue {
+    let bit = |index: usize| val.get(index / 8).is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0);
+    let bits = (val.len() * 8).saturating_sub(usize::from(padding));
+
+    match bits {
+        2 => GooseValue::Dbpos(match (bit(0), bit(1)) {
+            (false, false) => Dbpos::Intermediate,
+            (false, true) => Dbpos::Off,
+            (true, false) => Dbpos::On,
+            (true, true) => Dbpos::Bad,
+        }),
+        13 => {
+            let flags = (2..13).filter(|&i| bit(i)).fold(0, |q, i| q | (1 << i));
+            let validity = (u32::from(bit(0)) << 1) | u32::from(bit(1));
+            GooseValue::Quality(Quality::from_bits(flags | validity))
+        }
+        _ => GooseValue::BitString(val.to_vec()),
+    }
+}
+
+/// Classification of an accepted GOOSE message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GooseEvent {
+    /// First message of a new state (stNum changed), dataset updated
+    StateChange,
+    /// Retransmission of the current state
+    Retransmission,
+    /// Same stNum and sqNum as the previous message
+    Duplicate,
+    /// Older stNum or sqNum than the previous message (ignored)
+    OutOfOrder,
+}
+
+/// Reason a GOOSE message was not accepted by a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GooseRejectReason {
+    /// Destination MAC does not match
+    DstMac,
+    /// APPID does not match
+    AppId,
+    /// GOOSE control block reference does not match
+    GoCbRef,
+    /// GOOSE ID does not match
+    GoId,
+    /// Configuration revision does not match
+    ConfRev,
+    /// Simulated message while simulation is not accepted
+    Simulation,
+}
+
+/// Counters of a GOOSE subscription
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GooseSubscriptionStats {
+    /// Accepted messages
+    pub received: u64,
+    /// State changes (stNum increments)
+    pub state_changes: u64,
+    /// Messages missed according to stNum/sqNum
+    pub missed: u64,
+    /// Duplicated messages
+    pub duplicates: u64,
+    /// Out-of-order messages
+    pub out_of_order: u64,
+    /// Messages rejected on confRev or simulation
+    pub rejected: u64,
+    /// Number of times the time allowed to live expired
+    pub tal_expired: u64,
+}
+
+/// Subscription to one GOOSE control block
+///
+/// Tracks stNum/sqNum of the publisher and holds the dataset of the last
+/// state. The dataset is only valid while the time allowed to live (TAL) of
+/// the last message has not expired and the publisher does not flag
+/// `ndsCom`. Member quality is decoded but left to the protection logic.
+pub struct GooseSubscription {
+    config: GooseSubscriptionConfig,
+    dst_mac: [u8; 6],
+    st_num: Option<u32>,
+    sq_num: u32,
+    values: Vec<GooseValue>,
+    event_time: u64,
+    expiry: Option<u64>,
+    nds_com: bool,
+    simulation: bool,
+    timed_out: bool,
+    stats: GooseSubscriptionStats,
+}
+
+impl GooseSubscription {
+    /// Create a new subscription
+    ///
+    /// # Returns
+    /// Error if the destination MAC address is malformed
+    pub fn new(config: GooseSubscriptionConfig) -> Result<Self, Box<dyn Error>> {
+        let dst_mac = parse_mac_address(&config.dst_mac)?;
+        Ok(Self {
+            config,
+            dst_mac,
+            st_num: None,
+            sq_num: 0,
+            values: Vec::new(),
+            event_time: 0,
+            expiry: None,
+            nds_com: false,
+            simulation: false,
+            timed_out: false,
+            stats: GooseSubscriptionStats::default(),
+        })
+    }
+
+    /// Check if a frame belongs to this subscription
+    ///
+    /// # Arguments
+    /// * `dst_mac` - Destination MAC address of the frame
+    /// * `appid` - APPID of the frame
+    /// * `pdu` - Decoded GOOSE PDU
+    pub fn matches(&self, dst_mac: &[u8; 6], appid: u16, pdu: &IECGoosePdu) -> Result<(), GooseRejectReason> {
+        if &self.dst_mac != dst_mac {
+            return Err(GooseRejectReason::DstMac);
+        }
+        if self.config.appid.is_some_and(|id| id != appid) {
+            return Err(GooseRejectReason::AppId);
+        }
+        if self.config.gocb_ref != pdu.go_cb_ref {
+            return Err(GooseRejectReason::GoCbRef);
+        }
+        if self.config.goid.as_deref().is_some_and(|id| id != pdu.go_id) {
+            return Err(GooseRejectReason::GoId);
+        }
+        Ok(())
+    }
+
+    /// Process a message of the subscribed control block
+    ///
+    /// # Arguments
+    /// * `pdu` - Decoded GOOSE PDU (already matched with `matches`)
+    /// * `now` - Receive time in microseconds
+    pub fn process(&mut self, pdu: &IECGoosePdu, now: u64) -> Result<GooseEvent, GooseRejectReason> {
+        if self.config.conf_rev.is_some_and(|rev| rev != pdu.conf_rev) {
+            self.stats.rejected += 1;
+            return Err(GooseRejectReason::ConfRev);
+        }
+        if pdu.simulation && !self.config.accept_simulation {
+            self.stats.rejected += 1;
+            return Err(GooseRejectReason::Simulation);
+        }
+
+        let event = match self.st_num {
+            None => GooseEvent::StateChange,
+            Some(st_num) if pdu.st_num == st_num => {
+                if pdu.sq_num == self.sq_num {
+                    GooseEvent::Duplicate
+                } else if pdu.sq_num > self.sq_num {
+                    self.stats.missed += u64::from(pdu.sq_num - self.sq_num - 1);
+                    GooseEvent::Retransmission
+                } else {
+                    GooseEvent::OutOfOrder
+                }
+            }
+            Some(st_num) if pdu.st_num > st_num => {
+                self.stats.missed += u64::from(pdu.st_num - st_num - 1);
+                GooseEvent::StateChange
+            }
+            // stNum wraps from u32::MAX to 1 and a restarted publisher starts
+            // again at 1; neither is a gap in the messages
+            Some(_) if pdu.st_num <= 1 => GooseEvent::StateChange,
+            Some(_) => GooseEvent::OutOfOrder,
+        };
+
+        match event {
+            GooseEvent::OutOfOrder => {
+                self.stats.out_of_order += 1;
+                return Ok(event);
+            }
+            GooseEvent::Duplicate => self.stats.duplicates += 1,
+            GooseEvent::StateChange => {
+                self.stats.state_changes += 1;
+                self.values = pdu.all_data.iter().map(GooseValue::from_iec_data).collect();
+                self.event_time = iec61850_to_timestamp(&pdu.t.to_bytes());
+            }
+            GooseEvent::Retransmission => {}
+        }
+
+        self.stats.received += 1;
+        self.st_num = Some(pdu.st_num);
+        self.sq_num = pdu.sq_num;
+        self.nds_com = pdu.nds_com;
+        self.simulation = pdu.simulation;
+        self.expiry = Some(now + u64::from(pdu.time_allowed_to_live) * 1000);
+        self.timed_out = false;
+        Ok(event)
+    }
+
+    /// Supervise the time allowed to live
+    ///
+    /// # Returns
+    /// `true` if the TAL expired at this call
+    pub fn check_timeout(&mut self, now: u64) -> bool {
+        let expired = self.expiry.is_some_and(|expiry| now > expiry);
+        if expired && !self.timed_out {
+            self.timed_out = true;
+            self.stats.tal_expired += 1;
+            log::warn!("GOOSE {} time allowed to live expired", self.config.gocb_ref);
+            return true;
+        }
+        false
+    }
+
+    /// Check if the dataset is valid: received, within TAL and not ndsCom
+    pub fn is_valid(&self, now: u64) -> bool {
+        self.expiry.is_some_and(|expiry| now <= expiry) && !self.nds_com
+    }
+
+    /// Get a dataset member of the last state, if the dataset is valid
+    ///
+    /// # Arguments
+    /// * `index` - Member index in the dataset
+    /// * `now` - Current time in microseconds
+    pub fn value(&self, index: usize, now: u64) -> Option<&GooseValue> {
+        if self.is_valid(now) {
+            self.values.get(index)
+        } else {
+            None
+        }
+    }
+
+    /// Get a boolean dataset member (e.g. a blocking signal), if valid
+    pub fn boolean(&self, index: usize, now: u64) -> Option<bool> {
+        self.value(index, now).and_then(GooseValue::as_bool)
+    }
+
+    /// Get a double point dataset member (e.g. breaker position), if valid
+    pub fn dbpos(&self, index: usize, now: u64) -> Option<Dbpos> {
+        self.value(index, now).and_then(GooseValue::as_dbpos)
+    }
+
+    /// Get all dataset members of the last state, regardless of validity
+    pub fn values(&self) -> &[GooseValue] {
+        &self.values
+    }
+
+    /// Get the last state number
+    pub fn st_num(&self) -> Option<u32> {
+        self.st_num
+    }
+
+    /// Get the last sequence number
+    pub fn sq_num(&self) -> u32 {
+        self.sq_num
+    }
+
+    /// Get the event time (t) of the last state change in microseconds
+    pub fn event_time(&self) -> u64 {
+        self.event_time
+    }
+
+    /// Check if the last message was flagged as simulated
+    pub fn is_simulation(&self) -> bool {
+        self.simulation
+    }
+
+    /// Get the subscription counters
+    pub fn stats(&self) -> &GooseSubscriptionStats {
+        &self.stats
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &GooseSubscriptionConfig {
+        &self.config
+    }
+}
+
+/// GOOSE subscriber that receives messages from the network
+///
+/// One raw socket serves all configured subscriptions.
+pub struct GooseSubscriber {
+    config: GooseSubscriberConfig,
+    subscriptions: Vec<GooseSubscription>,
+    socket: Option<Socket>,
+}
+
+impl GooseSubscriber {
+    /// Create a new GOOSE subscriber with the given configuration
+    ///
+    /// # Returns
+    /// Error if a subscription is misconfigured
+    pub fn new(config: GooseSubscriberConfig) -> Result<Self, Box<dyn Error>> {
+        let subscriptions = config
+            .subscriptions
+            .iter()
+            .cloned()
+            .map(GooseSubscription::new)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            config,
+            subscriptions,
+            socket: None,
+        })
+    }
+
+    /// Initialize the subscriber with actual raw socket
+    ///
+    /// This opens a raw Ethernet socket to receive GOOSE packets
+    /// Requires CAP_NET_RAW capability or root privileges on Linux
+    pub fn init(&mut self) -> Result<(), Box<dyn Error>> {
+        log::info!(
+            "Initializing GOOSE subscriber on interface {} ({} subscriptions)",
+            self.config.interface,
+            self.subscriptions.len()
+        );
+
+        #[cfg(target_os = "linux")]
+        {
+            // Create raw packet socket
+            let socket = Socket::new(
+                Domain::PACKET,
+                Type::RAW,
+                Some(Protocol::from(0x0003)), // ETH_P_ALL
+            )?;
+
+            // Set socket to non-blocking mode
+            socket.set_nonblocking(true)?;
+
+            // Bind to interface
+            let if_index = get_interface_index(&self.config.interface)?;
+            let mut addr_storage = [0u8; 128];
+            bind_to_interface(&socket, if_index, &mut addr_storage)?;
+
+            self.socket = Some(socket);
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Err("Raw socket GOOSE reception is only supported on Linux".into())
+        }
+    }
+
+    /// Receive the next GOOSE message of a subscribed control block
+    ///
+    /// # Arguments
+    /// * `now` - Current time in microseconds
+    ///
+    /// # Returns
+    /// Index of the updated subscription and the message classification
+    pub fn receive(&mut self, now: u64) -> Result<(usize, GooseEvent), Box<dyn Error>> {
+        let socket = self.socket.as_ref()
+            .ok_or("Socket not initialized. Call init() first.")?;
+
+        let mut recv_buf: Vec<std::mem::MaybeUninit<u8>> = vec![std::mem::MaybeUninit::uninit(); MAX_ETHERNET_FRAME_SIZE];
+
+        loop {
+            // Receive packet (non-blocking)
+            let len = match socket.recv_from(&mut recv_buf) {
+                Ok((n, _)) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    return Err("No data available (non-blocking mode)".into());
+                }
+                Err(e) => return Err(Box::new(e)),
+            };
+
+            if len < MIN_ETHERNET_FRAME_SIZE {
+                continue;
+            }
+
+            let buffer: Vec<u8> = recv_buf[..len]
+                .iter()
+                .map(|byte| unsafe { byte.assume_init() })
+                .collect();
+
+            // Decode Ethernet header
+            let mut eth_header = EthernetHeader::default();
+            let pos = decode_ethernet_header(&mut eth_header, &buffer);
+
+            // Check if this is a GOOSE packet (EtherType 0x88B8)
+            if eth_header.ether_type != [0x88, 0xB8] {
+                continue;
+            }
+
+            let pdu = match decode_goose_pdu(&buffer, pos) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::debug!("Failed to decode GOOSE PDU: {:?}", e);
+                    continue;
+                }
+            };
+
+            let appid = u16::from_be_bytes(eth_header.appid);
+            let index = match self
+                .subscriptions
+                .iter()
+                .position(|sub| sub.matches(&eth_header.dst_addr, appid, &pdu).is_ok())
+            {
+                Some(index) => index,
+                None => continue,
+            };
+
+            match self.subscriptions[index].process(&pdu, now) {
+                Ok(event) => return Ok((index, event)),
+                Err(reason) => {
+                    log::debug!("GOOSE {} rejected: {:?}", pdu.go_cb_ref, reason);
+                    continue;
+                }
+            }
+        }
+    }
+
+    /// Supervise the time allowed to live of all subscriptions
+    ///
+    /// # Returns
+    /// Number of subscriptions whose TAL expired at this call
+    pub fn check_timeouts(&mut self, now: u64) -> usize {
+        self.subscriptions
+            .iter_mut()
+            .filter_map(|sub| sub.check_timeout(now).then_some(()))
+            .count()
+    }
+
+    /// Get all subscriptions
+    pub fn subscriptions(&self) -> &[GooseSubscription] {
+        &self.subscriptions
+    }
+
+    /// Get a subscription by index
+    pub fn subscription(&self, index: usize) -> Option<&GooseSubscription> {
+        self.subscriptions.get(index)
+    }
+
+    /// Find a subscription by GOOSE control block reference
+    pub fn find(&self, gocb_ref: &str) -> Option<&GooseSubscription> {
+        self.subscriptions.iter().find(|sub| sub.config.gocb_ref == gocb_ref)
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &GooseSubscriberConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iec_61850_lib::types::Timestamp;
+
+    const GOOSE_MAC: [u8; 6] = [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x10];
+
+    fn subscription() -> GooseSubscription {
+        GooseSubscription::new(GooseSubscriptionConfig {
+            gocb_ref: "BAY1LD0/LLN0$GO$Block".to_string(),
+            dst_mac: "01:0C:CD:01:00:10".to_string(),
+            appid: Some(0x0010),
+            conf_rev: Some(1),
+            ..GooseSubscriptionConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn pdu(st_num: u32, sq_num: u32, block: bool) -> IECGoosePdu {
+        IECGoosePdu {
+            go_cb_ref: "BAY1LD0/LLN0$GO$Block".to_string(),
+            time_allowed_to_live: 2000,
+            dat_set: "BAY1LD0/LLN0$Block".to_string(),
+            go_id: "Block".to_string(),
+            t: Timestamp::from_bytes([0; 8]),
+            st_num,
+            sq_num,
+            simulation: false,
+            conf_rev: 1,
+            nds_com: false,
+            num_dat_set_entries: 1,
+            all_data: vec![IECData::Boolean(block)],
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let sub = subscription();
+        let message = pdu(1, 0, true);
+        assert!(sub.matches(&GOOSE_MAC, 0x0010, &message).is_ok());
+        assert_eq!(sub.matches(&[0x01, 0x0C, 0xCD, 0x01, 0x00, 0x11], 0x0010, &message), Err(GooseRejectReason::DstMac));
+        assert_eq!(sub.matches(&GOOSE_MAC, 0x0011, &message), Err(GooseRejectReason::AppId));
+
+        let mut other = pdu(1, 0, true);
+        other.go_cb_ref = "BAY2LD0/LLN0$GO$Block".to_string();
+        assert_eq!(sub.matches(&GOOSE_MAC, 0x0010, &other), Err(GooseRejectReason::GoCbRef));
+    }
+
+    #[test]
+    fn test_state_tracking() {
+        let mut sub = subscription();
+        assert_eq!(sub.process(&pdu(1, 0, false), 0), Ok(GooseEvent::StateChange));
+        assert_eq!(sub.process(&pdu(1, 1, false), 1_000), Ok(GooseEvent::Retransmission));
+        assert_eq!(sub.process(&pdu(1, 1, false), 2_000), Ok(GooseEvent::Duplicate));
+        assert_eq!(sub.process(&pdu(1, 0, false), 3_000), Ok(GooseEvent::OutOfOrder));
+        assert_eq!(sub.boolean(0, 3_000), Some(false));
+
+        assert_eq!(sub.process(&pdu(2, 0, true), 4_000), Ok(GooseEvent::StateChange));
+        assert_eq!(sub.boolean(0, 4_000), Some(true));
+        assert_eq!(sub.st_num(), Some(2));
+
+        // Missed sqNum 1..3 and stNum 3
+        sub.process(&pdu(2, 4, true), 5_000).unwrap();
+        sub.process(&pdu(4, 0, true), 6_000).unwrap();
+        assert_eq!(sub.stats().missed, 4);
+        assert_eq!(sub.stats().state_changes, 3);
+        assert_eq!(sub.stats().out_of_order, 1);
+    }
+
+    #[test]
+    fn test_st_num_wrap() {
+        let mut sub = subscription();
+        sub.process(&pdu(u32::MAX, 0, false), 0).unwrap();
+        assert_eq!(sub.process(&pdu(1, 0, true), 1_000), Ok(GooseEvent::StateChange));
+        assert_eq!(sub.boolean(0, 1_000), Some(true));
+        assert_eq!(sub.stats().missed, 0);
+    }
+
+    #[test]
+    fn test_publisher_restart() {
+        let mut sub = subscription();
+        sub.process(&pdu(25, 3, true), 0).unwrap();
+
+        // Restarted publisher sends its initial state with stNum 1
+        assert_eq!(sub.process(&pdu(1, 0, false), 1_000), Ok(GooseEvent::StateChange));
+        assert_eq!(sub.boolean(0, 1_000), Some(false));
+        assert_eq!(sub.st_num(), Some(1));
+        assert_eq!(sub.stats().missed, 0);
+    }
+
+    #[test]
+    fn test_tal_expiry() {
+        let mut sub = subscription();
+        assert!(!sub.is_valid(0));
+        assert_eq!(sub.boolean(0, 0), None);
+
+        sub.process(&pdu(1, 0, true), 1_000_000).unwrap();
+        assert_eq!(sub.boolean(0, 2_999_999), Some(true));
+        assert!(!sub.check_timeout(3_000_000));
+
+        // TAL of 2000 ms expired
+        assert!(sub.check_timeout(3_000_001));
+        assert!(!sub.check_timeout(3_500_000));
+        assert_eq!(sub.boolean(0, 3_000_001), None);
+        assert_eq!(sub.stats().tal_expired, 1);
+
+        // Next message restores validity
+        sub.process(&pdu(1, 1, true), 4_000_000).unwrap();
+        assert_eq!(sub.boolean(0, 4_000_000), Some(true));
+    }
+
+    #[test]
+    fn test_conf_rev_and_simulation_rejected() {
+        let mut sub = subscription();
+        let mut message = pdu(1, 0, true);
+        message.conf_rev = 2;
+        assert_eq!(sub.process(&message, 0), Err(GooseRejectReason::ConfRev));
+
+        let mut message = pdu(1, 0, true);
+        message.simulation = true;
+        assert_eq!(sub.process(&message, 0), Err(GooseRejectReason::Simulation));
+        assert_eq!(sub.stats().rejected, 2);
+        assert!(!sub.is_valid(0));
+    }
+
+    #[test]
+    fn test_nds_com_invalidates_dataset() {
+        let mut sub = subscription();
+        let mut message = pdu(1, 0, true);
+        message.nds_com = true;
+        sub.process(&message, 0).unwrap();
+        assert_eq!(sub.boolean(0, 0), None);
+    }
+
+    #[test]
+    fn test_decode_dbpos_and_quality() {
+        let value = |padding, val: Vec<u8>| GooseValue::from_iec_data(&IECData::BitString { padding, val });
+        assert_eq!(value(6, vec![0x40]), GooseValue::Dbpos(Dbpos::Off));
+        assert_eq!(value(6, vec![0x80]), GooseValue::Dbpos(Dbpos::On));
+        assert_eq!(value(6, vec![0x00]), GooseValue::Dbpos(Dbpos::Intermediate));
+
+        // validity invalid (bit 1) and test (bit 11)
+        let quality = value(3, vec![0x40, 0x10]).as_quality().unwrap();
+        assert!(quality.is_invalid());
+        assert!(quality.is_test());
+    }
+
+    #[test]
+    fn test_quality_round_trip() {
+        use super::super::goose_output::quality_to_iec_data;
+
+        for quality in [
+            Quality::GOOD,
+            Quality::INVALID,
+            Quality::from_bits(0b11).with(Quality::OLD_DATA),
+            Quality::GOOD.with(Quality::TEST).with(Quality::OPERATOR_BLOCKED),
+        ] {
+            let decoded = GooseValue::from_iec_data(&quality_to_iec_data(quality));
+            assert_eq!(decoded, GooseValue::Quality(quality));
+        }
+    }
+
+    #[test]
+    fn test_decode_structure() {
+        let data = IECData::Structure(vec![IECData::Boolean(true), IECData::Int32(3)]);
+        assert_eq!(
+            GooseValue::from_iec_data(&data),
+            GooseValue::Structure(vec![GooseValue::Boolean(true), GooseValue::Integer(3)])
+        );
+    }
+}
@@ -1,6 +1,11 @@
 /// GOOSE output handling using iec_61850_lib
 use crate::config::GooseConfig;
+use crate::measurement::Quality;
+use crate::protection::PhaseFlags;
+use super::goose_scheduler::RetransmissionScheduler;
+use super::vlan::{VlanTag, VLAN_TPID};
 use std::error::Error;
+use serde::{Deserialize, Serialize};
 use socket2::{Socket, Domain, Type, Protocol};
 use iec_61850_lib::encode_goose::encode_goose;
 use iec_61850_lib::types::{EthernetHeader, IECGoosePdu, IECData};
@@ -21,26 +26,142 @@ pub struct GooseTripMessage {
     pub timestamp: u64,
 }
 
+/// Protection outputs published in the GOOSE dataset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GooseOutputs {
+    /// Operate (trip) per conductor (Op)
+    pub op: PhaseFlags,
+    /// Start (pickup) per conductor (Str)
+    pub start: PhaseFlags,
+    /// Quality of the protection outputs (q)
+    pub quality: Quality,
+}
+
+impl GooseOutputs {
+    /// Outputs of a three-phase trip (or no trip), with start equal to trip
+    pub fn trip(trip: bool) -> Self {
+        let flags = PhaseFlags {
+            phs_a: trip,
+            phs_b: trip,
+            phs_c: trip,
+            neut: false,
+        };
+        Self {
+            op: flags,
+            start: flags,
+            quality: Quality::GOOD,
+        }
+    }
+}
+
+/// Member of the published GOOSE dataset
+///
+/// Names follow the functional constraint data attributes of the SCD file
+/// (e.g. `PTOC1.Op.general`), so the dataset can be configured in the same
+/// order as the subscribers expect it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GooseDataMember {
+    /// General trip
+    #[serde(rename = "Op.general")]
+    OpGeneral,
+    /// Phase A trip
+    #[serde(rename = "Op.phsA")]
+    OpPhsA,
+    /// Phase B trip
+    #[serde(rename = "Op.phsB")]
+    OpPhsB,
+    /// Phase C trip
+    #[serde(rename = "Op.phsC")]
+    OpPhsC,
+    /// Neutral trip
+    #[serde(rename = "Op.neut")]
+    OpNeut,
+    /// General start (pickup)
+    #[serde(rename = "Str.general")]
+    StrGeneral,
+    /// Phase A start
+    #[serde(rename = "Str.phsA")]
+    StrPhsA,
+    /// Phase B start
+    #[serde(rename = "Str.phsB")]
+    StrPhsB,
+    /// Phase C start
+    #[serde(rename = "Str.phsC")]
+    StrPhsC,
+    /// Neutral start
+    #[serde(rename = "Str.neut")]
+    StrNeut,
+    /// Quality bit string
+    #[serde(rename = "q")]
+    Quality,
+    /// UTC time of the last change of the outputs
+    #[serde(rename = "t")]
+    Timestamp,
+}
+
+impl GooseDataMember {
+    /// Encode the member value
+    ///
+    /// # Arguments
+    /// * `outputs` - Protection outputs
+    /// * `event_time` - Time of the last change of the outputs (microseconds)
+    pub fn value(&self, outputs: &GooseOutputs, event_time: u64) -> IECData {
+        match self {
+            GooseDataMember::OpGeneral => IECData::Boolean(outputs.op.general()),
+            GooseDataMember::OpPhsA => IECData::Boolean(outputs.op.phs_a),
+            GooseDataMember::OpPhsB => IECData::Boolean(outputs.op.phs_b),
+            GooseDataMember::OpPhsC => IECData::Boolean(outputs.op.phs_c),
+            GooseDataMember::OpNeut => IECData::Boolean(outputs.op.neut),
+            GooseDataMember::StrGeneral => IECData::Boolean(outputs.start.general()),
+            GooseDataMember::StrPhsA => IECData::Boolean(outputs.start.phs_a),
+            GooseDataMember::StrPhsB => IECData::Boolean(outputs.start.phs_b),
+            GooseDataMember::StrPhsC => IECData::Boolean(outputs.start.phs_c),
+            GooseDataMember::StrNeut => IECData::Boolean(outputs.start.neut),
+            GooseDataMember::Quality => quality_to_iec_data(outputs.quality),
+            GooseDataMember::Timestamp => IECData::UtcTime(timestamp_to_iec61850(event_time)),
+        }
+    }
+}
+
 /// GOOSE publisher that sends trip messages over the network
 pub struct GoosePublisher {
     config: GooseConfig,
     sq_num: u32,
     st_num: u32,
-    last_trip_state: bool,
+    last_outputs: GooseOutputs,
+    event_time: u64,
     socket: Option<Socket>,
     src_mac: [u8; 6],
+    scheduler: RetransmissionScheduler,
+    last_tal: u32,
+    conf_rev: u32,
+    test_mode: bool,
+    dst_mac: Option<[u8; 6]>,
+    if_index: u32,
+    pdu: IECGoosePdu,
 }
 
 impl GoosePublisher {
     /// Create a new GOOSE publisher with the given configuration
     pub fn new(config: GooseConfig) -> Self {
+        let scheduler = RetransmissionScheduler::from_config(&config);
+        let conf_rev = config.conf_rev;
+        let pdu = initial_pdu(&config);
         Self {
             config,
             sq_num: 0,
             st_num: 0,
-            last_trip_state: false,
+            last_outputs: GooseOutputs::default(),
+            event_time: 0,
             socket: None,
             src_mac: DEFAULT_SRC_MAC,
+            scheduler,
+            last_tal: 0,
+            conf_rev,
+            test_mode: false,
+            dst_mac: None,
+            if_index: 0,
+            pdu,
         }
     }
 
@@ -72,6 +193,10 @@ impl GoosePublisher {
             let mut addr_storage = [0u8; 128];
             bind_to_interface(&socket, if_index, &mut addr_storage)?;
             
+            // Resolved once, so publishing does not look them up per frame
+            self.if_index = if_index;
+            self.dst_mac = Some(parse_mac_address(&self.config.dst_mac)?);
+            
             // Get MAC address of the interface
             self.src_mac = get_interface_mac(&self.config.interface)?;
             
@@ -90,63 +215,116 @@ impl GoosePublisher {
         }
     }
 
-    /// Publish a trip message using iec_61850_lib encoding
+    /// Update the protection outputs and send a frame when required
+    /// 
+    /// Call this periodically (e.g. once per processing cycle). A frame is sent
+    /// immediately when the outputs change and repeated afterwards
+    /// according to the retransmission schedule (T1 burst, backoff, T0 heartbeat).
+    /// 
+    /// # Arguments
+    /// * `outputs` - Protection outputs
+    /// * `now` - Current wall-clock time, drives the retransmission schedule (microseconds)
+    /// * `event_time` - Time of the outputs, sent as `t` when they changed (microseconds)
+    /// 
+    /// # Returns
+    /// `true` if a frame was sent
+    pub fn update(&mut self, outputs: &GooseOutputs, now: u64, event_time: u64) -> Result<bool, Box<dyn Error>> {
+        if self.effective_outputs(outputs) == self.last_outputs && !self.scheduler.is_due(now) {
+            return Ok(false);
+        }
+
+        self.publish(outputs, now, event_time)?;
+        Ok(true)
+    }
+
+    /// Publish a three-phase trip message using iec_61850_lib encoding
     /// 
     /// This encodes and sends an actual GOOSE message over the network
+    /// immediately. See `publish`.
     pub fn publish_trip(&mut self, trip: bool, timestamp: u64) -> Result<(), Box<dyn Error>> {
-        // Increment sequence number
-        self.sq_num = self.sq_num.wrapping_add(1);
-        
-        // Increment state number if trip state changed
-        let state_changed = trip != self.last_trip_state;
+        self.publish(&GooseOutputs::trip(trip), timestamp, timestamp)
+    }
+
+    /// Publish the protection outputs using iec_61850_lib encoding
+    /// 
+    /// This encodes and sends an actual GOOSE message over the network
+    /// immediately, with the dataset members configured in `GooseConfig::dataset`.
+    /// The time allowed to live is derived from the next scheduled retransmission.
+    /// 
+    /// In test mode the simulation bit is set and the outputs are flagged with
+    /// test quality. While the publisher is not fully configured (see
+    /// `needs_commissioning`) ndsCom is set and the dataset is sent empty.
+    /// 
+    /// # Arguments
+    /// * `outputs` - Protection outputs
+    /// * `now` - Current wall-clock time, drives the retransmission schedule (microseconds)
+    /// * `event_time` - Time of the outputs, sent as `t` when they changed (microseconds)
+    pub fn publish(&mut self, outputs: &GooseOutputs, now: u64, event_time: u64) -> Result<(), Box<dyn Error>> {
+        let outputs = &self.effective_outputs(outputs);
+
+        // A state change increments stNum and restarts sqNum at 0, a
+        // retransmission increments sqNum; both wrap to 1 (IEC 61850-8-1)
+        let state_changed = *outputs != self.last_outputs;
         if state_changed {
-            self.st_num += 1;
-            self.last_trip_state = trip;
+            self.st_num = self.st_num.checked_add(1).unwrap_or(1);
+            self.sq_num = 0;
+            self.last_outputs = *outputs;
+            self.event_time = event_time;
+            self.scheduler.state_changed();
             
             log::info!(
                 "GOOSE trip state changed: {} (stNum: {}, sqNum: {})",
-                if trip { "TRIP" } else { "NORMAL" },
+                if outputs.op.general() { "TRIP" } else { "NORMAL" },
                 self.st_num,
                 self.sq_num
             );
+        } else {
+            self.sq_num = self.sq_num.checked_add(1).unwrap_or(1);
         }
+        let trip = outputs.op.general();
 
-        // Parse destination MAC address
-        let dst_mac = parse_mac_address(&self.config.dst_mac)?;
+        // Destination MAC address, parsed in init() when the socket is open
+        let dst_mac = match self.dst_mac {
+            Some(mac) => mac,
+            None => parse_mac_address(&self.config.dst_mac)?,
+        };
         
         // Create Ethernet header
         let eth_header = EthernetHeader {
             dst_addr: dst_mac,
             src_addr: self.src_mac,
-            tpid: None,
-            tci: None,
+            tpid: Some(VLAN_TPID),
+            tci: Some(self.vlan_tag().to_tci()),
             ether_type: [0x88, 0xB8], // GOOSE EtherType
             appid: self.config.appid.to_be_bytes(),
             length: [0x00, 0x00], // Will be set by encode_goose
         };
         
-        // Convert timestamp to IEC 61850 format
-        let timestamp_bytes = timestamp_to_iec61850(timestamp);
-        let t = iec_61850_lib::types::Timestamp::from_bytes(timestamp_bytes);
-        
-        // Create GOOSE PDU
-        let pdu = IECGoosePdu {
-            go_cb_ref: self.config.gocb_ref.clone(),
-            time_allowed_to_live: if state_changed { 2000 } else { 5000 }, // Faster updates on state change
-            dat_set: self.config.dat_set.clone(),
-            go_id: self.config.goid.clone(),
-            t,
-            st_num: self.st_num,
-            sq_num: self.sq_num,
-            simulation: false,
-            conf_rev: 1,
-            nds_com: false,
-            num_dat_set_entries: 1,
-            all_data: vec![IECData::Boolean(trip)],
-        };
+        // TAL covers the interval until the next retransmission
+        let tal = self.scheduler.time_allowed_to_live();
+
+        // Update the PDU built in new(); the references and dataset buffer are reused
+        let nds_com = self.needs_commissioning();
+        let pdu = &mut self.pdu;
+        pdu.time_allowed_to_live = tal;
+        pdu.t = iec_61850_lib::types::Timestamp::from_bytes(timestamp_to_iec61850(self.event_time));
+        pdu.st_num = self.st_num;
+        pdu.sq_num = self.sq_num;
+        pdu.simulation = self.test_mode;
+        pdu.conf_rev = self.conf_rev;
+        pdu.nds_com = nds_com;
 
-        // Encode GOOSE message
-        let frame = encode_goose(&eth_header, &pdu)
+        // Dataset is only sent when the publisher is fully configured
+        pdu.all_data.clear();
+        if !nds_com {
+            let event_time = self.event_time;
+            pdu.all_data
+                .extend(self.config.dataset.iter().map(|member| member.value(outputs, event_time)));
+        }
+        pdu.num_dat_set_entries = pdu.all_data.len() as u32;
+
+        // Encode GOOSE message; iec_61850_lib returns the frame in a newly allocated buffer
+        let frame = encode_goose(&eth_header, &self.pdu)
             .map_err(|e| format!("Failed to encode GOOSE: {:?}", e))?;
 
         // Send frame if socket is initialized
@@ -155,7 +333,7 @@ impl GoosePublisher {
             {
                 use std::os::unix::io::AsRawFd;
                 
-                let if_index = get_interface_index(&self.config.interface)?;
+                let if_index = self.if_index;
                 
                 // Create sockaddr_ll for sending
                 let mut addr_storage = [0u8; 128];
@@ -215,6 +393,8 @@ impl GoosePublisher {
             );
         }
 
+        // Schedule the next retransmission only once the frame was sent
+        self.last_tal = self.scheduler.transmitted(now);
         Ok(())
     }
 
@@ -223,6 +403,67 @@ impl GoosePublisher {
         &self.config
     }
 
+    /// Enable or disable IED test mode
+    /// 
+    /// In test mode published frames carry the simulation bit and test quality,
+    /// so subscribers not in test mode ignore them. A change of test mode is
+    /// published as a state change.
+    pub fn set_test_mode(&mut self, test_mode: bool) {
+        if test_mode != self.test_mode {
+            log::info!("GOOSE publisher test mode {}", if test_mode { "on" } else { "off" });
+            self.test_mode = test_mode;
+        }
+    }
+
+    /// Check if the publisher is in test mode
+    pub fn is_test_mode(&self) -> bool {
+        self.test_mode
+    }
+
+    /// Change the dataset definition
+    /// 
+    /// The configuration revision is incremented when the definition changes,
+    /// and the new dataset is published immediately on the next `update`.
+    pub fn set_dataset(&mut self, dataset: Vec<GooseDataMember>) {
+        if dataset != self.config.dataset {
+            self.pdu.all_data.reserve(dataset.len());
+            self.config.dataset = dataset;
+            self.conf_rev = self.conf_rev.wrapping_add(1);
+            self.scheduler.state_changed();
+            log::info!("GOOSE dataset changed (confRev: {})", self.conf_rev);
+        }
+    }
+
+    /// Get the configuration revision of the dataset
+    pub fn conf_rev(&self) -> u32 {
+        self.conf_rev
+    }
+
+    /// Check if the publisher needs commissioning (ndsCom)
+    /// 
+    /// True when the control block reference, GOOSE ID, dataset reference or
+    /// dataset members are missing.
+    pub fn needs_commissioning(&self) -> bool {
+        self.config.gocb_ref.is_empty()
+            || self.config.goid.is_empty()
+            || self.config.dat_set.is_empty()
+            || self.config.dataset.is_empty()
+    }
+
+    /// Outputs as published, with test quality in test mode
+    fn effective_outputs(&self, outputs: &GooseOutputs) -> GooseOutputs {
+        let mut outputs = *outputs;
+        if self.test_mode {
+            outputs.quality = outputs.quality.with(Quality::TEST);
+        }
+        outputs
+    }
+
+    /// Get the 802.1Q tag of published frames
+    pub fn vlan_tag(&self) -> VlanTag {
+        VlanTag::new(self.config.vlan_id, self.config.vlan_priority)
+    }
+
     /// Get the current sequence number
     pub fn sq_num(&self) -> u32 {
         self.sq_num
@@ -235,19 +476,63 @@ impl GoosePublisher {
 
     /// Get the last trip state
     pub fn last_trip_state(&self) -> bool {
-        self.last_trip_state
+        self.last_outputs.op.general()
+    }
+
+    /// Get the last published protection outputs
+    pub fn last_outputs(&self) -> &GooseOutputs {
+        &self.last_outputs
+    }
+
+    /// Get the time of the last change of the outputs (`t`) in microseconds
+    pub fn event_time(&self) -> u64 {
+        self.event_time
+    }
+
+    /// Get the time allowed to live of the last sent frame in milliseconds
+    pub fn last_tal(&self) -> u32 {
+        self.last_tal
+    }
+
+    /// Get the time of the next scheduled retransmission in microseconds
+    pub fn next_transmission(&self) -> Option<u64> {
+        self.scheduler.next_transmission()
     }
 
     /// Reset the publisher state
     pub fn reset(&mut self) {
         self.sq_num = 0;
         self.st_num = 0;
-        self.last_trip_state = false;
+        self.last_outputs = GooseOutputs::default();
+        self.event_time = 0;
+        self.last_tal = 0;
+        self.scheduler = RetransmissionScheduler::from_config(&self.config);
+    }
+}
+
+/// GOOSE PDU with the control block references of the configuration
+///
+/// The dataset buffer is allocated for all configured members, so publishing
+/// fills it without allocating.
+fn initial_pdu(config: &GooseConfig) -> IECGoosePdu {
+    IECGoosePdu {
+        go_cb_ref: config.gocb_ref.clone(),
+        time_allowed_to_live: 0,
+        dat_set: config.dat_set.clone(),
+        go_id: config.goid.clone(),
+        t: iec_61850_lib::types::Timestamp::from_bytes([0; 8]),
+        st_num: 0,
+        sq_num: 0,
+        simulation: false,
+        conf_rev: config.conf_rev,
+        nds_com: false,
+        num_dat_set_entries: 0,
+        all_data: Vec::with_capacity(config.dataset.len()),
     }
 }
 
 /// Parse MAC address from string format "XX:XX:XX:XX:XX:XX"
-fn parse_mac_address(mac_str: &str) -> Result<[u8; 6], Box<dyn Error>> {
+pub(crate) fn parse_mac_address(mac_str: &str) -> Result<[u8; 6], Box<dyn Error>> {
     let parts: Vec<&str> = mac_str.split(':').collect();
     if parts.len() != 6 {
         return Err(format!("Invalid MAC address format: {}", mac_str).into());
@@ -282,6 +567,36 @@ fn timestamp_to_iec61850(timestamp_us: u64) -> [u8; 8] {
     
     bytes
 }
+
+/// Encode a quality as the IEC 61850-8-1 13-bit bit string
+///
+/// Bit 0 of the bit string is the most significant bit of the first octet;
+/// the validity value is written with bit 0 as its most significant bit.
+pub(crate) fn quality_to_iec_data(quality: Quality) -> IECData {
+    let bits = quality.bits();
+    let validity = bits & 0b11;
+    let ordered = (validity >> 1) | ((validity & 1) << 1) | (bits & 0x1FFC);
+
+    let mut val = [0u8; 2];
+    for i in (0..13).filter(|&i| ordered & (1 << i) != 0) {
+        val[i / 8] |= 0x80 >> (i % 8);
+    }
+
+    IECData::BitString {
+        padding: 3,
+        val: val.to_vec(),
+    }
+}
+
+/// Convert an IEC 61850 8-byte timestamp to microseconds
+pub(crate) fn iec61850_to_timestamp(bytes: &[u8; 8]) -> u64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let fraction = u32::from_be_bytes([0, bytes[4], bytes[5], bytes[6]]) as u64;
+
+    // fraction is in units of 2^-24 seconds
+    seconds * 1_000_000 + (fraction * 1_000_000) / 16_777_216
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,17 +619,32 @@ mod tests {
         // First trip message (without socket, just tests state management)
         publisher.publish_trip(true, 1000).unwrap();
         assert_eq!(publisher.st_num(), 1);
-        assert_eq!(publisher.sq_num(), 1);
+        assert_eq!(publisher.sq_num(), 0);
         
         // Same state - st_num should not increment
         publisher.publish_trip(true, 2000).unwrap();
         assert_eq!(publisher.st_num(), 1);
-        assert_eq!(publisher.sq_num(), 2);
+        assert_eq!(publisher.sq_num(), 1);
         
-        // State change - st_num should increment
+        // State change - st_num should increment and sq_num restart
         publisher.publish_trip(false, 3000).unwrap();
         assert_eq!(publisher.st_num(), 2);
-        assert_eq!(publisher.sq_num(), 3);
+        assert_eq!(publisher.sq_num(), 0);
+    }
+
+    #[test]
+    fn test_goose_counters_wrap_to_one() {
+        let mut publisher = GoosePublisher::new(GooseConfig::default());
+        publisher.publish_trip(true, 1000).unwrap();
+
+        publisher.sq_num = u32::MAX;
+        publisher.publish_trip(true, 2000).unwrap();
+        assert_eq!(publisher.sq_num(), 1);
+
+        publisher.st_num = u32::MAX;
+        publisher.publish_trip(false, 3000).unwrap();
+        assert_eq!(publisher.st_num(), 1);
+        assert_eq!(publisher.sq_num(), 0);
     }
 
     #[test]
@@ -330,6 +660,199 @@ mod tests {
         assert_eq!(publisher.last_trip_state(), false);
     }
     
+    #[test]
+    fn test_goose_retransmission() {
+        let config = GooseConfig::default();
+        let mut publisher = GoosePublisher::new(config);
+
+        // State change is sent immediately with a short TAL
+        assert!(publisher.update(&GooseOutputs::trip(true), 0, 0).unwrap());
+        assert_eq!(publisher.st_num(), 1);
+        assert_eq!(publisher.last_tal(), 4);
+
+        // Nothing is sent before the first repetition (T1 = 2 ms)
+        assert!(!publisher.update(&GooseOutputs::trip(true), 1_000, 1_000).unwrap());
+        assert!(publisher.update(&GooseOutputs::trip(true), 2_000, 2_000).unwrap());
+        assert_eq!(publisher.st_num(), 1);
+        assert_eq!(publisher.sq_num(), 1);
+        assert_eq!(publisher.next_transmission(), Some(6_000));
+        assert_eq!(publisher.last_tal(), 8);
+
+        // A new state change interrupts the repetitions
+        assert!(publisher.update(&GooseOutputs::trip(false), 3_000, 3_000).unwrap());
+        assert_eq!(publisher.st_num(), 2);
+        assert_eq!(publisher.sq_num(), 0);
+        assert_eq!(publisher.next_transmission(), Some(5_000));
+    }
+
+    #[test]
+    fn test_goose_schedule_uses_wall_clock() {
+        let mut publisher = GoosePublisher::new(GooseConfig::default());
+
+        // Sample taken 1.5 ms before the trip was published
+        assert!(publisher.update(&GooseOutputs::trip(true), 10_000, 8_500).unwrap());
+        assert_eq!(publisher.event_time(), 8_500);
+        assert_eq!(publisher.next_transmission(), Some(12_000));
+
+        // Retransmissions keep the event time and follow the wall clock
+        assert!(!publisher.update(&GooseOutputs::trip(true), 11_000, 10_500).unwrap());
+        assert!(publisher.update(&GooseOutputs::trip(true), 12_000, 9_000).unwrap());
+        assert_eq!(publisher.event_time(), 8_500);
+        assert_eq!(publisher.next_transmission(), Some(16_000));
+    }
+
+    #[test]
+    fn test_goose_heartbeat() {
+        let config = GooseConfig::default();
+        let mut publisher = GoosePublisher::new(config);
+
+        let sent = (0..5_000u64)
+            .filter(|ms| publisher.update(&GooseOutputs::trip(false), ms * 1000, ms * 1000).unwrap())
+            .count();
+        // Startup burst followed by T0 = 1 s heartbeats
+        assert_eq!(sent, 13);
+        assert_eq!(publisher.last_tal(), 2000);
+    }
+
+    #[test]
+    fn test_goose_dataset_members() {
+        let config = GooseConfig {
+            dataset: vec![
+                GooseDataMember::OpGeneral,
+                GooseDataMember::OpPhsA,
+                GooseDataMember::OpPhsB,
+                GooseDataMember::StrGeneral,
+                GooseDataMember::Quality,
+                GooseDataMember::Timestamp,
+            ],
+            ..GooseConfig::default()
+        };
+        let mut publisher = GoosePublisher::new(config.clone());
+
+        let outputs = GooseOutputs {
+            op: PhaseFlags { phs_b: true, ..PhaseFlags::default() },
+            start: PhaseFlags { phs_a: true, phs_b: true, ..PhaseFlags::default() },
+            quality: Quality::GOOD,
+        };
+        publisher.publish(&outputs, 5_000_000, 5_000_000).unwrap();
+        assert!(publisher.last_trip_state());
+
+        let values: Vec<IECData> = config
+            .dataset
+            .iter()
+            .map(|member| member.value(&outputs, 5_000_000))
+            .collect();
+        assert_eq!(values[0], IECData::Boolean(true));
+        assert_eq!(values[1], IECData::Boolean(false));
+        assert_eq!(values[2], IECData::Boolean(true));
+        assert_eq!(values[3], IECData::Boolean(true));
+        assert_eq!(values[4], IECData::BitString { padding: 3, val: vec![0x00, 0x00] });
+        assert_eq!(values[5], IECData::UtcTime(timestamp_to_iec61850(5_000_000)));
+    }
+
+    #[test]
+    fn test_goose_pdu_reused() {
+        let mut publisher = GoosePublisher::new(GooseConfig::default());
+        let buffer = publisher.pdu.all_data.as_ptr();
+
+        publisher.publish_trip(true, 1_000).unwrap();
+        publisher.publish_trip(false, 2_000).unwrap();
+        assert_eq!(publisher.pdu.all_data.as_ptr(), buffer);
+        assert_eq!(publisher.pdu.st_num, 2);
+        assert_eq!(publisher.pdu.num_dat_set_entries as usize, publisher.config().dataset.len());
+    }
+
+    #[test]
+    fn test_goose_start_change_increments_st_num() {
+        let mut publisher = GoosePublisher::new(GooseConfig::default());
+        let mut outputs = GooseOutputs::default();
+        outputs.start.phs_a = true;
+
+        publisher.publish(&outputs, 0, 0).unwrap();
+        assert_eq!(publisher.st_num(), 1);
+        assert!(!publisher.last_trip_state());
+    }
+
+    #[test]
+    fn test_quality_encoding() {
+        let invalid = quality_to_iec_data(Quality::INVALID);
+        assert_eq!(invalid, IECData::BitString { padding: 3, val: vec![0x40, 0x00] });
+
+        let test = quality_to_iec_data(Quality::GOOD.with(Quality::TEST));
+        assert_eq!(test, IECData::BitString { padding: 3, val: vec![0x00, 0x10] });
+    }
+
+    #[test]
+    fn test_goose_vlan_tag() {
+        let publisher = GoosePublisher::new(GooseConfig::default());
+        assert_eq!(publisher.vlan_tag(), VlanTag::new(0, 4));
+
+        let config = GooseConfig {
+            vlan_id: 10,
+            vlan_priority: 6,
+            ..GooseConfig::default()
+        };
+        let publisher = GoosePublisher::new(config);
+        assert_eq!(publisher.vlan_tag().to_tci(), [0xC0, 0x0A]);
+    }
+
+    #[test]
+    fn test_goose_test_mode() {
+        let mut publisher = GoosePublisher::new(GooseConfig::default());
+        publisher.update(&GooseOutputs::trip(false), 0, 0).unwrap();
+        assert_eq!(publisher.st_num(), 0);
+
+        // Entering test mode is published as a state change with test quality
+        publisher.set_test_mode(true);
+        assert!(publisher.update(&GooseOutputs::trip(false), 1_000, 1_000).unwrap());
+        assert_eq!(publisher.st_num(), 1);
+        assert!(publisher.last_outputs().quality.is_test());
+        assert!(publisher.is_test_mode());
+
+        publisher.set_test_mode(false);
+        publisher.update(&GooseOutputs::trip(false), 2_000, 2_000).unwrap();
+        assert_eq!(publisher.st_num(), 2);
+        assert!(!publisher.last_outputs().quality.is_test());
+    }
+
+    #[test]
+    fn test_goose_conf_rev() {
+        let config = GooseConfig {
+            conf_rev: 10,
+            ..GooseConfig::default()
+        };
+        let mut publisher = GoosePublisher::new(config);
+        assert_eq!(publisher.conf_rev(), 10);
+
+        // Same definition keeps the revision
+        publisher.set_dataset(vec![GooseDataMember::OpGeneral]);
+        assert_eq!(publisher.conf_rev(), 10);
+
+        publisher.set_dataset(vec![GooseDataMember::OpGeneral, GooseDataMember::Quality]);
+        assert_eq!(publisher.conf_rev(), 11);
+        assert_eq!(publisher.next_transmission(), None);
+    }
+
+    #[test]
+    fn test_goose_needs_commissioning() {
+        let publisher = GoosePublisher::new(GooseConfig::default());
+        assert!(!publisher.needs_commissioning());
+
+        let config = GooseConfig {
+            dataset: Vec::new(),
+            ..GooseConfig::default()
+        };
+        assert!(GoosePublisher::new(config).needs_commissioning());
+
+        let config = GooseConfig {
+            gocb_ref: String::new(),
+            ..GooseConfig::default()
+        };
+        let mut publisher = GoosePublisher::new(config);
+        assert!(publisher.needs_commissioning());
+        publisher.publish_trip(true, 0).unwrap();
+    }
+
     #[test]
     fn test_mac_address_parsing() {
         let mac = parse_mac_address("01:0C:CD:01:00:00").unwrap();
@@ -360,4 +883,11 @@ mod tests {
         // 0.5 seconds = (0.5 * 2^24) / 1 = 8388608
         assert!((fraction as i32 - 8_388_608).abs() < 100); // Allow small tolerance
     }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let timestamp = 1_700_000_000_250_000;
+        let decoded = iec61850_to_timestamp(&timestamp_to_iec61850(timestamp));
+        assert!(timestamp - decoded <= 1);
+    }
 }
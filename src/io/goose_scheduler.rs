@@ -0,0 +1,176 @@
+/// GOOSE retransmission scheduling according to IEC 61850-8-1
+use crate::config::GooseConfig;
+
+/// Time allowed to live as a multiple of the interval to the next transmission
+pub const TAL_FACTOR: u64 = 2;
+
+/// Retransmission scheduler of a GOOSE publisher
+///
+/// After a state change the message is repeated with a short interval T1,
+/// which is multiplied by the backoff factor after every transmission until
+/// it reaches the heartbeat interval T0:
+///
+/// ```text
+/// change  T1   T1·k   T1·k²  ...  T0   T0   T0
+///   |     |     |      |          |    |    |
+/// ```
+///
+/// The time allowed to live (TAL) sent with each message is `TAL_FACTOR`
+/// times the interval to the next scheduled transmission, so subscribers can
+/// detect a lost publisher as soon as one repetition is missed.
+#[derive(Debug, Clone)]
+pub struct RetransmissionScheduler {
+    t0_us: u64,
+    t1_us: u64,
+    backoff_factor: f64,
+    interval_us: u64,
+    next_transmission: Option<u64>,
+}
+
+impl RetransmissionScheduler {
+    /// Create a new scheduler
+    ///
+    /// # Arguments
+    /// * `t0` - Heartbeat interval in milliseconds
+    /// * `t1` - Minimum interval after a state change in milliseconds
+    /// * `backoff_factor` - Interval multiplier after each repetition (> 1.0)
+    pub fn new(t0: u64, t1: u64, backoff_factor: f64) -> Self {
+        let t1_us = t1.max(1) * 1000;
+        Self {
+            t0_us: (t0 * 1000).max(t1_us),
+            t1_us,
+            backoff_factor: backoff_factor.max(1.0),
+            interval_us: t1_us,
+            next_transmission: None,
+        }
+    }
+
+    /// Create a scheduler from the GOOSE configuration
+    pub fn from_config(config: &GooseConfig) -> Self {
+        Self::new(config.t0, config.t1, config.backoff_factor)
+    }
+
+    /// Restart the fast repetition sequence after a state change
+    pub fn state_changed(&mut self) {
+        self.interval_us = self.t1_us;
+        self.next_transmission = None;
+    }
+
+    /// Check if a transmission is due
+    ///
+    /// # Arguments
+    /// * `now` - Current time in microseconds
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_transmission.map(|next| now >= next).unwrap_or(true)
+    }
+
+    /// Record a transmission and schedule the next one
+    ///
+    /// # Arguments
+    /// * `now` - Time of the transmission in microseconds
+    ///
+    /// # Returns
+    /// Time allowed to live for the transmitted message in milliseconds
+    pub fn transmitted(&mut self, now: u64) -> u32 {
+        let tal = self.time_allowed_to_live();
+        let interval = self.interval_us;
+        self.next_transmission = Some(now + interval);
+        self.interval_us = ((interval as f64 * self.backoff_factor) as u64).min(self.t0_us);
+        tal
+    }
+
+    /// Get the time allowed to live for a message transmitted now
+    ///
+    /// # Returns
+    /// Time allowed to live in milliseconds, without recording a transmission
+    pub fn time_allowed_to_live(&self) -> u32 {
+        (self.interval_us * TAL_FACTOR).div_ceil(1000) as u32
+    }
+
+    /// Get the time of the next scheduled transmission in microseconds
+    pub fn next_transmission(&self) -> Option<u64> {
+        self.next_transmission
+    }
+
+    /// Get the interval that will follow the next transmission in microseconds
+    pub fn interval(&self) -> u64 {
+        self.interval_us
+    }
+
+    /// Check if the fast repetition sequence has ended
+    pub fn is_steady_state(&self) -> bool {
+        self.interval_us >= self.t0_us
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transmit whenever due, stepping the clock in 1 ms increments
+    fn transmission_times(scheduler: &mut RetransmissionScheduler, until_ms: u64) -> Vec<u64> {
+        let mut times = Vec::new();
+        for ms in 0..=until_ms {
+            let now = ms * 1000;
+            if scheduler.is_due(now) {
+                scheduler.transmitted(now);
+                times.push(ms);
+            }
+        }
+        times
+    }
+
+    #[test]
+    fn test_backoff_sequence() {
+        let mut scheduler = RetransmissionScheduler::new(1000, 2, 2.0);
+        let times = transmission_times(&mut scheduler, 3100);
+        assert_eq!(
+            times,
+            vec![0, 2, 6, 14, 30, 62, 126, 254, 510, 1022, 2022, 3022]
+        );
+        assert!(scheduler.is_steady_state());
+    }
+
+    #[test]
+    fn test_tal_follows_next_interval() {
+        let mut scheduler = RetransmissionScheduler::new(1000, 4, 2.0);
+        assert_eq!(scheduler.transmitted(0), 8);
+        assert_eq!(scheduler.transmitted(4_000), 16);
+        assert_eq!(scheduler.transmitted(12_000), 32);
+        for _ in 0..10 {
+            scheduler.transmitted(0);
+        }
+        assert_eq!(scheduler.transmitted(0), 2000);
+    }
+
+    #[test]
+    fn test_tal_without_transmission() {
+        let mut scheduler = RetransmissionScheduler::new(1000, 4, 2.0);
+        // A failed send leaves the schedule unchanged
+        assert_eq!(scheduler.time_allowed_to_live(), 8);
+        assert_eq!(scheduler.time_allowed_to_live(), 8);
+        assert!(scheduler.is_due(0));
+        assert_eq!(scheduler.transmitted(0), 8);
+        assert_eq!(scheduler.time_allowed_to_live(), 16);
+    }
+
+    #[test]
+    fn test_state_change_restarts_burst() {
+        let mut scheduler = RetransmissionScheduler::new(1000, 2, 2.0);
+        transmission_times(&mut scheduler, 5000);
+        assert!(!scheduler.is_due(5_001_000));
+
+        scheduler.state_changed();
+        assert!(scheduler.is_due(5_001_000));
+        assert_eq!(scheduler.transmitted(5_001_000), 4);
+        assert_eq!(scheduler.next_transmission(), Some(5_003_000));
+    }
+
+    #[test]
+    fn test_invalid_settings_are_limited() {
+        // T0 below T1 and a shrinking backoff are clamped
+        let mut scheduler = RetransmissionScheduler::new(1, 10, 0.5);
+        assert_eq!(scheduler.transmitted(0), 20);
+        assert_eq!(scheduler.transmitted(10_000), 20);
+    }
+}
@@ -1,9 +1,28 @@
 /// I/O module for Sampled Values input and GOOSE output
 pub mod sv_input;
+pub mod sv_filter;
+pub mod sv_supervision;
+pub mod sample_clock;
 pub mod goose_output;
+pub mod goose_scheduler;
+pub mod goose_input;
+pub mod vlan;
+pub mod timestamping;
 
 #[cfg(target_os = "linux")]
 pub mod network_utils;
+#[cfg(target_os = "linux")]
+pub mod packet_ring;
 
-pub use sv_input::{SampleData, SvSubscriber, SvSampleBuffer};
-pub use goose_output::{GooseTripMessage, GoosePublisher};
+pub use sv_input::{SampleData, SvChannel, SvSubscriber, SvSampleBuffer, SV_CHANNEL_COUNT};
+pub use sv_filter::{SvFilter, SvFilterStats, SvRejectReason};
+pub use sv_supervision::{GapPolicy, SampleCounterSupervisor, SampleEvent, SvLossStats};
+pub use sample_clock::SampleClock;
+pub use timestamping::TimestampSource;
+pub use goose_output::{GooseDataMember, GooseOutputs, GooseTripMessage, GoosePublisher};
+pub use goose_scheduler::RetransmissionScheduler;
+pub use vlan::{VlanTag, DEFAULT_GOOSE_PRIORITY};
+pub use goose_input::{
+    Dbpos, GooseEvent, GooseRejectReason, GooseSubscriber, GooseSubscription, GooseSubscriptionStats,
+    GooseValue,
+};
@@ -0,0 +1,506 @@
+/// Memory-mapped AF_PACKET receive ring (TPACKET_V3) with a classic BPF filter
+use crate::config::PacketRingConfig;
+use super::network_utils::{bind_to_interface, get_interface_index};
+use super::timestamping::{enable_hardware_timestamping, ReceiveTimestamps, TimestampSource, SOF_TIMESTAMPING_RAW_HARDWARE};
+use socket2::{Domain, Socket, Type};
+use std::error::Error;
+use std::marker::PhantomData;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::atomic::{fence, AtomicU32, Ordering};
+
+// Socket options and ring layout from linux/if_packet.h and linux/filter.h
+const SOL_PACKET: libc::c_int = 263;
+const PACKET_RX_RING: libc::c_int = 5;
+const PACKET_VERSION: libc::c_int = 10;
+const PACKET_TIMESTAMP: libc::c_int = 17;
+const TPACKET_V3: libc::c_int = 2;
+const SO_ATTACH_FILTER: libc::c_int = 26;
+
+const TP_STATUS_KERNEL: u32 = 0;
+const TP_STATUS_USER: u32 = 1 << 0;
+const TP_STATUS_VLAN_VALID: u32 = 1 << 4;
+const TP_STATUS_TS_RAW_HARDWARE: u32 = 1 << 31;
+
+/// Frame slot size used to size the ring; frames are packed in blocks in TPACKET_V3
+const RING_FRAME_SIZE: u32 = 2048;
+
+// Offsets in struct tpacket_block_desc (header v1)
+const BLOCK_STATUS: usize = 8;
+const BLOCK_NUM_PKTS: usize = 12;
+const BLOCK_FIRST_PKT: usize = 16;
+
+// Offsets in struct tpacket3_hdr
+const PKT_NEXT_OFFSET: usize = 0;
+const PKT_SEC: usize = 4;
+const PKT_NSEC: usize = 8;
+const PKT_SNAPLEN: usize = 12;
+const PKT_STATUS: usize = 20;
+const PKT_MAC: usize = 24;
+const PKT_VLAN_TCI: usize = 32;
+
+// Classic BPF opcodes
+const BPF_LD_H_ABS: u16 = 0x28;
+const BPF_LD_W_ABS: u16 = 0x20;
+const BPF_JEQ_K: u16 = 0x15;
+const BPF_RET_K: u16 = 0x06;
+
+/// Snap length returned by the filter for accepted frames
+const BPF_ACCEPT: u32 = 0xFFFF;
+
+/// EtherType of IEEE 802.1Q tagged frames
+const ETHERTYPE_VLAN: u32 = 0x8100;
+
+/// One classic BPF instruction (struct sock_filter)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SockFilter {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+impl SockFilter {
+    fn stmt(code: u16, k: u32) -> Self {
+        Self { code, jt: 0, jf: 0, k }
+    }
+
+    fn jump(code: u16, k: u32, jt: u8, jf: u8) -> Self {
+        Self { code, jt, jf, k }
+    }
+}
+
+/// BPF program passed to SO_ATTACH_FILTER (struct sock_fprog)
+#[repr(C)]
+struct SockFprog {
+    len: u16,
+    filter: *const SockFilter,
+}
+
+/// Ring request passed to PACKET_RX_RING (struct tpacket_req3)
+#[repr(C)]
+struct TpacketReq3 {
+    tp_block_size: u32,
+    tp_block_nr: u32,
+    tp_frame_size: u32,
+    tp_frame_nr: u32,
+    tp_retire_blk_tov: u32,
+    tp_sizeof_priv: u32,
+    tp_feature_req_word: u32,
+}
+
+/// Build a BPF program accepting only frames with the given EtherType
+///
+/// Tagged frames are matched on the EtherType following the 802.1Q tag; when
+/// the driver strips the tag the EtherType is found at its usual position.
+///
+/// # Arguments
+/// * `ether_type` - EtherType to accept (0x88BA for SV)
+/// * `dst_mac` - Destination MAC address to accept (None = any)
+pub fn ether_type_filter(ether_type: u16, dst_mac: Option<[u8; 6]>) -> Vec<SockFilter> {
+    let mut program = vec![
+        SockFilter::stmt(BPF_LD_H_ABS, 12),
+        SockFilter::jump(BPF_JEQ_K, ETHERTYPE_VLAN, 0, 1),
+        SockFilter::stmt(BPF_LD_H_ABS, 16),
+        // Jump to drop is patched below
+        SockFilter::jump(BPF_JEQ_K, u32::from(ether_type), 0, 0),
+    ];
+    if let Some(mac) = dst_mac {
+        program.extend([
+            SockFilter::stmt(BPF_LD_W_ABS, 2),
+            SockFilter::jump(BPF_JEQ_K, u32::from_be_bytes([mac[2], mac[3], mac[4], mac[5]]), 0, 0),
+            SockFilter::stmt(BPF_LD_H_ABS, 0),
+            SockFilter::jump(BPF_JEQ_K, u32::from(u16::from_be_bytes([mac[0], mac[1]])), 0, 0),
+        ]);
+    }
+    program.push(SockFilter::stmt(BPF_RET_K, BPF_ACCEPT));
+    program.push(SockFilter::stmt(BPF_RET_K, 0));
+
+    // Mismatches of the EtherType and MAC comparisons go to the final drop
+    let drop = program.len() - 1;
+    for (pc, ins) in program.iter_mut().enumerate().take(drop).skip(3) {
+        if ins.code == BPF_JEQ_K {
+            ins.jf = (drop - pc - 1) as u8;
+        }
+    }
+    program
+}
+
+/// Frame received in the ring, valid until its block is released
+#[derive(Debug, Clone, Copy)]
+pub struct RingFrame<'a> {
+    /// Ethernet frame starting at the destination MAC address
+    pub data: &'a [u8],
+    /// 802.1Q tag stripped by the driver (VLAN offload)
+    pub vlan_tci: Option<[u8; 2]>,
+    /// Receive time in nanoseconds since the Unix epoch
+    pub timestamp_ns: u64,
+    /// Clock that took the receive time
+    pub timestamp_source: TimestampSource,
+}
+
+impl RingFrame<'_> {
+    /// Receive timestamps in microseconds
+    ///
+    /// A ring frame carries a single timestamp, so with hardware timestamps
+    /// enabled there is no kernel software timestamp.
+    pub fn timestamps(&self) -> ReceiveTimestamps {
+        let time = Some(self.timestamp_ns / 1_000);
+        match self.timestamp_source {
+            TimestampSource::Hardware => ReceiveTimestamps {
+                hardware: time,
+                ..ReceiveTimestamps::default()
+            },
+            _ => ReceiveTimestamps {
+                software: time,
+                ..ReceiveTimestamps::default()
+            },
+        }
+    }
+}
+
+/// Block of frames handed to user space; returned to the kernel when dropped
+pub struct RingBlock<'a> {
+    block: *mut u8,
+    _ring: PhantomData<&'a mut PacketRing>,
+}
+
+impl<'a> RingBlock<'a> {
+    /// Wrap a block retired by the kernel
+    ///
+    /// # Safety
+    /// `block` must point to a complete TPACKET_V3 block that stays mapped for `'a`.
+    unsafe fn from_raw(block: *mut u8) -> Self {
+        Self {
+            block,
+            _ring: PhantomData,
+        }
+    }
+
+    fn read_u32(&self, offset: usize) -> u32 {
+        unsafe { std::ptr::read_unaligned(self.block.add(offset) as *const u32) }
+    }
+
+    /// Number of frames in the block
+    pub fn len(&self) -> usize {
+        self.read_u32(BLOCK_NUM_PKTS) as usize
+    }
+
+    /// Check if the block contains no frames
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the frames in the block, in receive order
+    pub fn frames(&self) -> impl Iterator<Item = RingFrame<'_>> + '_ {
+        let mut offset = self.read_u32(BLOCK_FIRST_PKT) as usize;
+        (0..self.len()).map(move |_| {
+            let header = offset;
+            let next = self.read_u32(header + PKT_NEXT_OFFSET) as usize;
+            offset += next;
+
+            let status = self.read_u32(header + PKT_STATUS);
+            let mac = unsafe { std::ptr::read_unaligned(self.block.add(header + PKT_MAC) as *const u16) };
+            let snaplen = self.read_u32(header + PKT_SNAPLEN) as usize;
+            let tci = unsafe { std::ptr::read_unaligned(self.block.add(header + PKT_VLAN_TCI) as *const u16) };
+            let timestamp_ns = u64::from(self.read_u32(header + PKT_SEC)) * 1_000_000_000
+                + u64::from(self.read_u32(header + PKT_NSEC));
+
+            RingFrame {
+                data: unsafe { std::slice::from_raw_parts(self.block.add(header + mac as usize), snaplen) },
+                vlan_tci: (status & TP_STATUS_VLAN_VALID != 0).then(|| tci.to_be_bytes()),
+                timestamp_ns,
+                timestamp_source: if status & TP_STATUS_TS_RAW_HARDWARE != 0 {
+                    TimestampSource::Hardware
+                } else {
+                    TimestampSource::Software
+                },
+            }
+        })
+    }
+
+    fn status(&self) -> &AtomicU32 {
+        unsafe { &*(self.block.add(BLOCK_STATUS) as *const AtomicU32) }
+    }
+}
+
+impl Drop for RingBlock<'_> {
+    fn drop(&mut self) {
+        // Hand the block back to the kernel once all frames have been read
+        self.status().store(TP_STATUS_KERNEL, Ordering::Release);
+    }
+}
+
+/// Memory-mapped TPACKET_V3 receive ring on an AF_PACKET socket
+///
+/// The kernel writes frames directly into blocks shared with user space, so
+/// frames are decoded in place without a receive syscall or copy per frame.
+/// A block is handed to user space when it is full or when the block timeout
+/// expires, so the timeout bounds the added latency at low frame rates.
+pub struct PacketRing {
+    socket: Socket,
+    map: *mut u8,
+    block_size: usize,
+    block_count: usize,
+    current: usize,
+}
+
+// The mapping is owned by the ring and only accessed through `&mut self`
+unsafe impl Send for PacketRing {}
+
+impl PacketRing {
+    /// Open a receive ring on an interface
+    ///
+    /// The filter is attached before the socket is bound, so no unfiltered
+    /// frame ever reaches the ring.
+    /// Requires CAP_NET_RAW capability or root privileges.
+    ///
+    /// # Arguments
+    /// * `interface` - Network interface name
+    /// * `config` - Ring geometry and block timeout
+    /// * `filter` - Classic BPF program selecting the frames to receive
+    /// * `hardware_timestamps` - Use NIC hardware receive timestamps where supported
+    pub fn new(
+        interface: &str,
+        config: &PacketRingConfig,
+        filter: &[SockFilter],
+        hardware_timestamps: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let page_size = 4096;
+        if config.block_size == 0 || config.block_size & (page_size - 1) != 0 || config.block_count == 0 {
+            return Err("Packet ring block size must be a non-zero multiple of 4096 bytes".into());
+        }
+
+        // Protocol 0: nothing is received until the socket is bound
+        let socket = Socket::new(Domain::PACKET, Type::RAW, None)?;
+        let fd = socket.as_raw_fd();
+
+        set_option(fd, SOL_PACKET, PACKET_VERSION, &TPACKET_V3, "PACKET_VERSION")?;
+
+        let program = SockFprog {
+            len: filter.len() as u16,
+            filter: filter.as_ptr(),
+        };
+        set_option(fd, libc::SOL_SOCKET, SO_ATTACH_FILTER, &program, "SO_ATTACH_FILTER")?;
+
+        // Ring frames carry kernel software timestamps unless the NIC provides them
+        if hardware_timestamps {
+            match enable_hardware_timestamping(fd, interface) {
+                Ok(()) => set_option(fd, SOL_PACKET, PACKET_TIMESTAMP, &SOF_TIMESTAMPING_RAW_HARDWARE, "PACKET_TIMESTAMP")?,
+                Err(e) => log::warn!("Hardware timestamps unavailable on {}, using software timestamps: {}", interface, e),
+            }
+        }
+
+        let block_size = config.block_size as u32;
+        let request = TpacketReq3 {
+            tp_block_size: block_size,
+            tp_block_nr: config.block_count as u32,
+            tp_frame_size: RING_FRAME_SIZE,
+            tp_frame_nr: block_size / RING_FRAME_SIZE * config.block_count as u32,
+            tp_retire_blk_tov: config.block_timeout_ms,
+            tp_sizeof_priv: 0,
+            tp_feature_req_word: 0,
+        };
+        set_option(fd, SOL_PACKET, PACKET_RX_RING, &request, "PACKET_RX_RING")?;
+
+        let length = config.block_size * config.block_count;
+        let map = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                length,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd,
+                0,
+            )
+        };
+        if map == libc::MAP_FAILED {
+            return Err(format!("Failed to map packet ring: {}", std::io::Error::last_os_error()).into());
+        }
+
+        let ring = Self {
+            socket,
+            map: map as *mut u8,
+            block_size: config.block_size,
+            block_count: config.block_count,
+            current: 0,
+        };
+
+        let if_index = get_interface_index(interface)?;
+        let mut addr_storage = [0u8; 128];
+        bind_to_interface(&ring.socket, if_index, &mut addr_storage)?;
+
+        log::info!(
+            "Packet ring on interface {}: {} blocks of {} bytes, block timeout {} ms",
+            interface,
+            config.block_count,
+            config.block_size,
+            config.block_timeout_ms
+        );
+
+        Ok(ring)
+    }
+
+    /// Get the next block retired by the kernel, if any
+    pub fn next_block(&mut self) -> Option<RingBlock<'_>> {
+        let block = unsafe { self.map.add(self.current * self.block_size) };
+        let status = unsafe { &*(block.add(BLOCK_STATUS) as *const AtomicU32) };
+        if status.load(Ordering::Relaxed) & TP_STATUS_USER == 0 {
+            return None;
+        }
+        // Frame data written by the kernel is visible after the status
+        fence(Ordering::Acquire);
+
+        self.current = (self.current + 1) % self.block_count;
+        Some(unsafe { RingBlock::from_raw(block) })
+    }
+}
+
+impl AsRawFd for PacketRing {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+impl Drop for PacketRing {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.map as *mut libc::c_void, self.block_size * self.block_count);
+        }
+    }
+}
+
+/// Set a socket option from a value of any type
+fn set_option<T>(fd: RawFd, level: libc::c_int, name: libc::c_int, value: &T, label: &str) -> Result<(), Box<dyn Error>> {
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            value as *const T as *const libc::c_void,
+            std::mem::size_of::<T>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(format!("Failed to set {}: {}", label, std::io::Error::last_os_error()).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SV_MAC: [u8; 6] = [0x01, 0x0C, 0xCD, 0x04, 0x00, 0x01];
+
+    /// Minimal classic BPF interpreter for the instructions used above
+    fn run_filter(program: &[SockFilter], frame: &[u8]) -> u32 {
+        let mut pc = 0;
+        let mut acc = 0u32;
+        loop {
+            let ins = program[pc];
+            match ins.code {
+                BPF_LD_H_ABS => {
+                    let k = ins.k as usize;
+                    acc = u32::from(u16::from_be_bytes([frame[k], frame[k + 1]]));
+                }
+                BPF_LD_W_ABS => {
+                    let k = ins.k as usize;
+                    acc = u32::from_be_bytes([frame[k], frame[k + 1], frame[k + 2], frame[k + 3]]);
+                }
+                BPF_JEQ_K => {
+                    pc += if acc == ins.k { ins.jt } else { ins.jf } as usize;
+                }
+                BPF_RET_K => return ins.k,
+                code => panic!("unexpected opcode {:#x}", code),
+            }
+            pc += 1;
+        }
+    }
+
+    fn frame(dst: [u8; 6], vlan: bool, ether_type: u16) -> Vec<u8> {
+        let mut frame = dst.to_vec();
+        frame.extend([0x00, 0x0A, 0x35, 0x00, 0x00, 0x01]);
+        if vlan {
+            frame.extend([0x81, 0x00, 0x80, 0x14]);
+        }
+        frame.extend(ether_type.to_be_bytes());
+        frame.extend([0u8; 32]);
+        frame
+    }
+
+    #[test]
+    fn test_filter_ether_type() {
+        let program = ether_type_filter(0x88BA, None);
+        assert_eq!(run_filter(&program, &frame(SV_MAC, false, 0x88BA)), BPF_ACCEPT);
+        assert_eq!(run_filter(&program, &frame(SV_MAC, true, 0x88BA)), BPF_ACCEPT);
+        assert_eq!(run_filter(&program, &frame(SV_MAC, false, 0x88B8)), 0);
+        assert_eq!(run_filter(&program, &frame(SV_MAC, true, 0x0800)), 0);
+    }
+
+    #[test]
+    fn test_filter_destination_mac() {
+        let program = ether_type_filter(0x88BA, Some(SV_MAC));
+        assert_eq!(run_filter(&program, &frame(SV_MAC, false, 0x88BA)), BPF_ACCEPT);
+        assert_eq!(run_filter(&program, &frame(SV_MAC, true, 0x88BA)), BPF_ACCEPT);
+
+        let mut other = SV_MAC;
+        other[5] = 0x02;
+        assert_eq!(run_filter(&program, &frame(other, false, 0x88BA)), 0);
+        other = SV_MAC;
+        other[0] = 0x03;
+        assert_eq!(run_filter(&program, &frame(other, true, 0x88BA)), 0);
+    }
+
+    /// Write a TPACKET_V3 packet header and frame at `offset`
+    fn put_packet(block: &mut [u8], offset: usize, next: u32, data: &[u8], tci: Option<u16>, hardware: bool) {
+        let mac = 48;
+        let put = |block: &mut [u8], at: usize, value: u32| block[at..at + 4].copy_from_slice(&value.to_ne_bytes());
+        put(block, offset + PKT_NEXT_OFFSET, next);
+        put(block, offset + PKT_SEC, 2);
+        put(block, offset + PKT_NSEC, 500);
+        put(block, offset + PKT_SNAPLEN, data.len() as u32);
+        let mut status = TP_STATUS_USER;
+        if tci.is_some() {
+            status |= TP_STATUS_VLAN_VALID;
+        }
+        if hardware {
+            status |= TP_STATUS_TS_RAW_HARDWARE;
+        }
+        put(block, offset + PKT_STATUS, status);
+        block[offset + PKT_MAC..offset + PKT_MAC + 2].copy_from_slice(&(mac as u16).to_ne_bytes());
+        block[offset + PKT_VLAN_TCI..offset + PKT_VLAN_TCI + 2].copy_from_slice(&tci.unwrap_or(0).to_ne_bytes());
+        block[offset + mac..offset + mac + data.len()].copy_from_slice(data);
+    }
+
+    #[test]
+    fn test_block_frames() {
+        let mut block = vec![0u8; 4096];
+        block[BLOCK_STATUS..BLOCK_STATUS + 4].copy_from_slice(&TP_STATUS_USER.to_ne_bytes());
+        block[BLOCK_NUM_PKTS..BLOCK_NUM_PKTS + 4].copy_from_slice(&2u32.to_ne_bytes());
+        block[BLOCK_FIRST_PKT..BLOCK_FIRST_PKT + 4].copy_from_slice(&64u32.to_ne_bytes());
+
+        let first = frame(SV_MAC, false, 0x88BA);
+        let second = frame(SV_MAC, true, 0x88BA);
+        put_packet(&mut block, 64, 256, &first, None, false);
+        put_packet(&mut block, 320, 0, &second, Some(0x8014), true);
+
+        {
+            let ring_block = unsafe { RingBlock::from_raw(block.as_mut_ptr()) };
+            let frames: Vec<_> = ring_block.frames().collect();
+            assert_eq!(ring_block.len(), 2);
+            assert_eq!(frames[0].data, &first[..]);
+            assert_eq!(frames[0].vlan_tci, None);
+            assert_eq!(frames[0].timestamp_ns, 2_000_000_500);
+            assert_eq!(frames[0].timestamp_source, TimestampSource::Software);
+            assert_eq!(frames[0].timestamps().software, Some(2_000_000));
+            assert_eq!(frames[1].timestamp_source, TimestampSource::Hardware);
+            assert_eq!(frames[1].timestamps().software, None);
+            assert_eq!(frames[1].data, &second[..]);
+            assert_eq!(frames[1].vlan_tci, Some([0x80, 0x14]));
+        }
+
+        // Dropping the block returns it to the kernel
+        assert_eq!(&block[BLOCK_STATUS..BLOCK_STATUS + 4], &TP_STATUS_KERNEL.to_ne_bytes());
+    }
+}
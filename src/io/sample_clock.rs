@@ -0,0 +1,173 @@
+/// Sample timestamps derived from the SV sample counter
+///
+/// `SampleClock` converts sample counters into sampling instants.
+///
+/// The sample counter (smpCnt) counts samples within one second. When the
+/// merging unit is synchronised (smpSynch != 0) counter value 0 is the top of
+/// the second, so the sampling instant is the start of the second plus
+/// `smpCnt / samples_per_second`. The second is the one that places the
+/// sample nearest to its receive time, within half a second either way, so
+/// network delay and an offset between the relay clock and the merging unit
+/// clock never move a sample into a neighbouring second.
+///
+/// When the merging unit is not synchronised its counter is free running.
+/// The clock is then anchored to the receive time of the first sample and
+/// advanced by the counter, so consecutive timestamps keep the exact sample
+/// spacing. It is re-anchored if it drifts more than half a second from the
+/// receive time (e.g. after the stream restarted).
+#[derive(Debug, Clone)]
+pub struct SampleClock {
+    samples_per_second: u32,
+    /// Time of counter value 0 of the current second (microseconds)
+    origin: Option<u64>,
+    last_timestamp: u64,
+}
+
+/// Microseconds per second
+const SECOND_US: u64 = 1_000_000;
+
+/// Half a second in microseconds, used to resolve which second a sample belongs to
+const HALF_SECOND_US: u64 = SECOND_US / 2;
+
+impl SampleClock {
+    /// Create a new sample clock
+    ///
+    /// # Arguments
+    /// * `samples_per_second` - Sample rate; the counter wraps at this value
+    pub fn new(samples_per_second: u32) -> Self {
+        Self {
+            samples_per_second,
+            origin: None,
+            last_timestamp: 0,
+        }
+    }
+
+    /// Get the sampling instant of a sample
+    ///
+    /// # Arguments
+    /// * `smp_cnt` - Sample counter
+    /// * `synchronized` - Merging unit is synchronised to the top of the second
+    /// * `receive_time` - Time the frame was received (microseconds)
+    ///
+    /// # Returns
+    /// Sampling instant in microseconds
+    pub fn timestamp(&mut self, smp_cnt: u16, synchronized: bool, receive_time: u64) -> u64 {
+        let offset = self.counter_offset(smp_cnt);
+
+        let timestamp = if synchronized {
+            let mut timestamp = receive_time / SECOND_US * SECOND_US + offset;
+            if timestamp > receive_time + HALF_SECOND_US {
+                // Sample taken in the previous second (network delay)
+                timestamp = timestamp.saturating_sub(SECOND_US);
+            } else if timestamp + HALF_SECOND_US < receive_time {
+                // Sample taken in the next second (relay clock behind the merging unit)
+                timestamp += SECOND_US;
+            }
+            timestamp
+        } else {
+            let mut timestamp = self.origin.map(|origin| origin + offset).unwrap_or(receive_time);
+            if timestamp + HALF_SECOND_US < self.last_timestamp {
+                // Counter wrapped to the next second
+                timestamp += SECOND_US;
+            }
+            if timestamp.abs_diff(receive_time) > HALF_SECOND_US {
+                // First sample or lost track of the merging unit: re-anchor
+                timestamp = receive_time;
+            }
+            timestamp
+        };
+
+        self.origin = Some(timestamp.saturating_sub(offset));
+        self.last_timestamp = timestamp;
+        timestamp
+    }
+
+    /// Forget the current time reference
+    pub fn reset(&mut self) {
+        self.origin = None;
+        self.last_timestamp = 0;
+    }
+
+    /// Time of the sample counter within the second (microseconds)
+    fn counter_offset(&self, smp_cnt: u16) -> u64 {
+        if self.samples_per_second == 0 {
+            return 0;
+        }
+        u64::from(smp_cnt) * SECOND_US / u64::from(self.samples_per_second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Some UTC second, in microseconds
+    const T0: u64 = 1_700_000_000 * SECOND_US;
+
+    #[test]
+    fn test_synchronized_timestamp_ignores_receive_jitter() {
+        let mut clock = SampleClock::new(4000);
+        // Sample 400 is taken 100 ms after the top of the second
+        assert_eq!(clock.timestamp(400, true, T0 + 100_250), T0 + 100_000);
+        assert_eq!(clock.timestamp(401, true, T0 + 101_900), T0 + 100_250);
+    }
+
+    #[test]
+    fn test_synchronized_sample_from_previous_second() {
+        let mut clock = SampleClock::new(4000);
+        // Last sample of a second received just after the next second started
+        assert_eq!(clock.timestamp(3999, true, T0 + 300), T0 - 250);
+    }
+
+    #[test]
+    fn test_synchronized_relay_clock_behind() {
+        let mut clock = SampleClock::new(4000);
+        // Relay clock lags the merging unit: the top of the second is received before T0
+        assert_eq!(clock.timestamp(0, true, T0 - 100), T0);
+    }
+
+    #[test]
+    fn test_synchronized_monotonic_across_second() {
+        // Relay clock 400 µs behind the merging unit, then 400 µs ahead
+        for skew in [-400_i64, 400] {
+            let mut clock = SampleClock::new(4000);
+            let mut last = 0;
+            for n in 3990..4010_u64 {
+                let smp_cnt = (n % 4000) as u16;
+                let sampled = T0 - 10 * 250 + (n - 3990) * 250;
+                let receive_time = (sampled as i64 + 150 + skew) as u64;
+                let timestamp = clock.timestamp(smp_cnt, true, receive_time);
+                assert_eq!(timestamp, sampled);
+                assert!(timestamp > last);
+                last = timestamp;
+            }
+        }
+    }
+
+    #[test]
+    fn test_free_running_keeps_sample_spacing() {
+        let mut clock = SampleClock::new(4000);
+        let first = clock.timestamp(1000, false, T0 + 5_000);
+        assert_eq!(first, T0 + 5_000);
+
+        // Receive jitter does not affect the spacing
+        assert_eq!(clock.timestamp(1001, false, T0 + 5_900), first + 250);
+        assert_eq!(clock.timestamp(1003, false, T0 + 5_800), first + 750);
+    }
+
+    #[test]
+    fn test_free_running_wraparound() {
+        let mut clock = SampleClock::new(4000);
+        let first = clock.timestamp(3999, false, T0);
+        assert_eq!(clock.timestamp(0, false, T0 + 300), first + 250);
+        assert_eq!(clock.timestamp(1, false, T0 + 500), first + 500);
+    }
+
+    #[test]
+    fn test_free_running_reanchors_after_restart() {
+        let mut clock = SampleClock::new(4000);
+        clock.timestamp(100, false, T0);
+        // Stream restarted two seconds later with an unrelated counter
+        assert_eq!(clock.timestamp(2000, false, T0 + 2 * SECOND_US), T0 + 2 * SECOND_US);
+    }
+}
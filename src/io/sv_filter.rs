@@ -0,0 +1,239 @@
+/// Sampled Values stream filtering by MAC address, VLAN, APPID, svID and confRev
+use crate::config::SvConfig;
+use super::goose_output::parse_mac_address;
+use super::vlan::VlanTag;
+use std::error::Error;
+
+/// Reason an SV frame or ASDU was rejected by the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvRejectReason {
+    /// Destination MAC does not match the subscribed multicast address
+    DstMac,
+    /// Source MAC does not match the configured merging unit
+    SrcMac,
+    /// APPID does not match
+    AppId,
+    /// svID (MsvID) does not match
+    SvId,
+    /// Configuration revision does not match
+    ConfRev,
+    /// Frame is untagged or tagged with another VLAN ID
+    Vlan,
+}
+
+/// Counters of accepted and rejected SV frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SvFilterStats {
+    /// Frames with at least one accepted ASDU
+    pub accepted: u64,
+    /// Frames rejected on destination MAC
+    pub rejected_dst_mac: u64,
+    /// Frames rejected on source MAC
+    pub rejected_src_mac: u64,
+    /// Frames rejected on APPID
+    pub rejected_appid: u64,
+    /// Frames rejected on svID
+    pub rejected_sv_id: u64,
+    /// Frames rejected on confRev
+    pub rejected_conf_rev: u64,
+    /// Frames rejected on VLAN ID
+    pub rejected_vlan: u64,
+}
+
+impl SvFilterStats {
+    /// Count a rejected frame
+    pub fn record_reject(&mut self, reason: SvRejectReason) {
+        let counter = match reason {
+            SvRejectReason::DstMac => &mut self.rejected_dst_mac,
+            SvRejectReason::SrcMac => &mut self.rejected_src_mac,
+            SvRejectReason::AppId => &mut self.rejected_appid,
+            SvRejectReason::SvId => &mut self.rejected_sv_id,
+            SvRejectReason::ConfRev => &mut self.rejected_conf_rev,
+            SvRejectReason::Vlan => &mut self.rejected_vlan,
+        };
+        *counter += 1;
+    }
+
+    /// Total number of rejected frames
+    pub fn rejected(&self) -> u64 {
+        self.rejected_dst_mac
+            + self.rejected_src_mac
+            + self.rejected_appid
+            + self.rejected_sv_id
+            + self.rejected_conf_rev
+            + self.rejected_vlan
+    }
+}
+
+/// Filter selecting the SV stream of one merging unit
+///
+/// Frame header fields (MAC addresses, VLAN, APPID) are checked once per frame,
+/// stream identity (svID, confRev) once per ASDU. Criteria left unset accept
+/// any value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SvFilter {
+    dst_mac: Option<[u8; 6]>,
+    src_mac: Option<[u8; 6]>,
+    appid: Option<u16>,
+    sv_id: Option<String>,
+    conf_rev: Option<u32>,
+    vlan_id: Option<u16>,
+}
+
+impl SvFilter {
+    /// Create a filter that accepts every SV frame
+    pub fn accept_all() -> Self {
+        Self::default()
+    }
+
+    /// Create a filter from the SV configuration
+    ///
+    /// # Returns
+    /// Error if one of the configured MAC addresses is malformed
+    pub fn from_config(config: &SvConfig) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            dst_mac: Some(parse_mac_address(&config.multicast_mac)?),
+            src_mac: config.src_mac.as_deref().map(parse_mac_address).transpose()?,
+            appid: config.appid,
+            sv_id: config.sv_id.clone(),
+            conf_rev: config.conf_rev,
+            vlan_id: config.vlan_id,
+        })
+    }
+
+    /// Check the Ethernet header fields of a frame
+    ///
+    /// # Arguments
+    /// * `dst_mac` - Destination MAC address
+    /// * `src_mac` - Source MAC address
+    /// * `appid` - APPID following the EtherType
+    pub fn check_header(&self, dst_mac: &[u8; 6], src_mac: &[u8; 6], appid: u16) -> Result<(), SvRejectReason> {
+        if self.dst_mac.is_some_and(|mac| &mac != dst_mac) {
+            return Err(SvRejectReason::DstMac);
+        }
+        if self.src_mac.is_some_and(|mac| &mac != src_mac) {
+            return Err(SvRejectReason::SrcMac);
+        }
+        if self.appid.is_some_and(|id| id != appid) {
+            return Err(SvRejectReason::AppId);
+        }
+        Ok(())
+    }
+
+    /// Check the 802.1Q tag of a frame
+    ///
+    /// # Arguments
+    /// * `tci` - Tag control information, `None` for untagged frames
+    pub fn check_vlan(&self, tci: Option<[u8; 2]>) -> Result<(), SvRejectReason> {
+        match self.vlan_id {
+            None => Ok(()),
+            Some(vlan_id) if tci.is_some_and(|tci| VlanTag::from_tci(tci).vlan_id == vlan_id) => Ok(()),
+            Some(_) => Err(SvRejectReason::Vlan),
+        }
+    }
+
+    /// Check the stream identity of an ASDU
+    ///
+    /// # Arguments
+    /// * `sv_id` - svID (MsvID) of the ASDU
+    /// * `conf_rev` - Configuration revision of the ASDU
+    pub fn check_asdu(&self, sv_id: &str, conf_rev: u32) -> Result<(), SvRejectReason> {
+        if self.sv_id.as_deref().is_some_and(|id| id != sv_id) {
+            return Err(SvRejectReason::SvId);
+        }
+        if self.conf_rev.is_some_and(|rev| rev != conf_rev) {
+            return Err(SvRejectReason::ConfRev);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MU1: [u8; 6] = [0x00, 0x0A, 0x35, 0x00, 0x00, 0x01];
+    const MU2: [u8; 6] = [0x00, 0x0A, 0x35, 0x00, 0x00, 0x02];
+    const SV_MAC: [u8; 6] = [0x01, 0x0C, 0xCD, 0x04, 0x00, 0x00];
+
+    fn mu1_filter() -> SvFilter {
+        let config = SvConfig {
+            appid: Some(0x4000),
+            sv_id: Some("MU01".to_string()),
+            conf_rev: Some(1),
+            src_mac: Some("00:0A:35:00:00:01".to_string()),
+            ..SvConfig::default()
+        };
+        SvFilter::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_accept_all() {
+        let filter = SvFilter::accept_all();
+        assert!(filter.check_header(&[0xFF; 6], &MU2, 0x1234).is_ok());
+        assert!(filter.check_asdu("anything", 42).is_ok());
+    }
+
+    #[test]
+    fn test_header_filter() {
+        let filter = mu1_filter();
+        assert!(filter.check_header(&SV_MAC, &MU1, 0x4000).is_ok());
+        assert_eq!(
+            filter.check_header(&[0x01, 0x0C, 0xCD, 0x04, 0x00, 0x01], &MU1, 0x4000),
+            Err(SvRejectReason::DstMac)
+        );
+        assert_eq!(filter.check_header(&SV_MAC, &MU2, 0x4000), Err(SvRejectReason::SrcMac));
+        assert_eq!(filter.check_header(&SV_MAC, &MU1, 0x4001), Err(SvRejectReason::AppId));
+    }
+
+    #[test]
+    fn test_asdu_filter() {
+        let filter = mu1_filter();
+        assert!(filter.check_asdu("MU01", 1).is_ok());
+        assert_eq!(filter.check_asdu("MU02", 1), Err(SvRejectReason::SvId));
+        assert_eq!(filter.check_asdu("MU01", 2), Err(SvRejectReason::ConfRev));
+    }
+
+    #[test]
+    fn test_default_config_filters_on_destination_only() {
+        let filter = SvFilter::from_config(&SvConfig::default()).unwrap();
+        assert!(filter.check_header(&SV_MAC, &MU2, 0x4005).is_ok());
+        assert!(filter.check_asdu("any", 7).is_ok());
+    }
+
+    #[test]
+    fn test_vlan_filter() {
+        let filter = SvFilter::accept_all();
+        assert!(filter.check_vlan(None).is_ok());
+        assert!(filter.check_vlan(Some(VlanTag::new(20, 4).to_tci())).is_ok());
+
+        let config = SvConfig {
+            vlan_id: Some(20),
+            ..SvConfig::default()
+        };
+        let filter = SvFilter::from_config(&config).unwrap();
+        assert!(filter.check_vlan(Some(VlanTag::new(20, 4).to_tci())).is_ok());
+        assert_eq!(filter.check_vlan(Some(VlanTag::new(21, 4).to_tci())), Err(SvRejectReason::Vlan));
+        assert_eq!(filter.check_vlan(None), Err(SvRejectReason::Vlan));
+    }
+
+    #[test]
+    fn test_invalid_mac_rejected() {
+        let config = SvConfig {
+            src_mac: Some("not-a-mac".to_string()),
+            ..SvConfig::default()
+        };
+        assert!(SvFilter::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = SvFilterStats::default();
+        stats.record_reject(SvRejectReason::SvId);
+        stats.record_reject(SvRejectReason::SvId);
+        stats.record_reject(SvRejectReason::AppId);
+        assert_eq!(stats.rejected_sv_id, 2);
+        assert_eq!(stats.rejected_appid, 1);
+        assert_eq!(stats.rejected(), 3);
+    }
+}
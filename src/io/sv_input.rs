@@ -1,6 +1,12 @@
 /// Sampled Values (SV) input handling using iec_61850_lib
 use crate::config::SvConfig;
+use crate::measurement::{Phase, Quality};
+use super::sv_filter::{SvFilter, SvFilterStats};
+use super::sv_supervision::{SampleCounterSupervisor, SvLossStats};
+use super::sample_clock::SampleClock;
+use super::timestamping::{ReceiveTimestamps, TimestampSource, TIMESTAMP_CONTROL_SIZE};
 use std::error::Error;
+use serde::{Deserialize, Serialize};
 use socket2::{Socket, Domain, Type, Protocol};
 use iec_61850_lib::decode_basics::decode_ethernet_header;
 use iec_61850_lib::decode_smv::decode_smv;
@@ -8,30 +14,207 @@ use iec_61850_lib::types::EthernetHeader;
 
 #[cfg(target_os = "linux")]
 use super::network_utils::{get_interface_index, bind_to_interface, MAX_ETHERNET_FRAME_SIZE, MIN_ETHERNET_FRAME_SIZE};
+#[cfg(target_os = "linux")]
+use super::packet_ring::{ether_type_filter, PacketRing};
+#[cfg(target_os = "linux")]
+use super::goose_output::parse_mac_address;
+#[cfg(target_os = "linux")]
+use super::timestamping::{enable_timestamping, receive_with_timestamp};
+
+/// Number of analogue channels in an IEC 61850-9-2LE dataset
+pub const SV_CHANNEL_COUNT: usize = 8;
+
+/// Most ASDUs in one SV frame (IEC 61850-9-2LE: 1 at 80, 8 at 256 samples per cycle)
+pub const MAX_ASDUS_PER_FRAME: usize = 8;
+
+/// Least space one SV frame takes in a packet ring block, including its packet header
+const MIN_RING_FRAME_SPACE: usize = 128;
+
+/// Analogue channel carried in an SV dataset entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SvChannel {
+    /// Phase A current
+    Ia,
+    /// Phase B current
+    Ib,
+    /// Phase C current
+    Ic,
+    /// Neutral current
+    In,
+    /// Phase A voltage
+    Va,
+    /// Phase B voltage
+    Vb,
+    /// Phase C voltage
+    Vc,
+    /// Neutral voltage
+    Vn,
+    /// Dataset entry is ignored
+    Unused,
+}
+
+impl SvChannel {
+    /// Dataset order defined by IEC 61850-9-2LE (4 currents, then 4 voltages)
+    pub const LE_LAYOUT: [SvChannel; SV_CHANNEL_COUNT] = [
+        SvChannel::Ia,
+        SvChannel::Ib,
+        SvChannel::Ic,
+        SvChannel::In,
+        SvChannel::Va,
+        SvChannel::Vb,
+        SvChannel::Vc,
+        SvChannel::Vn,
+    ];
+
+    /// Index of the channel in `SampleData::values`, `None` for unused entries
+    pub fn index(&self) -> Option<usize> {
+        SvChannel::LE_LAYOUT.iter().position(|channel| channel == self)
+    }
+
+    /// Conductor the channel is measured on
+    pub fn phase(&self) -> Option<Phase> {
+        match self {
+            SvChannel::Ia | SvChannel::Va => Some(Phase::A),
+            SvChannel::Ib | SvChannel::Vb => Some(Phase::B),
+            SvChannel::Ic | SvChannel::Vc => Some(Phase::C),
+            SvChannel::In | SvChannel::Vn => Some(Phase::N),
+            SvChannel::Unused => None,
+        }
+    }
+
+    /// Check if the channel is a current
+    pub fn is_current(&self) -> bool {
+        matches!(self, SvChannel::Ia | SvChannel::Ib | SvChannel::Ic | SvChannel::In)
+    }
+
+    /// Current channel of a conductor
+    pub fn current(phase: Phase) -> SvChannel {
+        SvChannel::LE_LAYOUT[phase.index()]
+    }
 
-/// Sample data structure representing one sample from SV stream
-#[derive(Debug, Clone)]
+    /// Voltage channel of a conductor
+    pub fn voltage(phase: Phase) -> SvChannel {
+        SvChannel::LE_LAYOUT[4 + phase.index()]
+    }
+}
+
+/// Sample data structure representing one ASDU (one sampling instant) from SV stream
+#[derive(Debug, Clone, PartialEq)]
 pub struct SampleData {
-    /// Raw ADC value for current
-    pub current_adc: i32,
-    /// Sample number within the cycle
+    /// Raw values for Ia, Ib, Ic, In, Va, Vb, Vc, Vn (see `SvChannel::index`)
+    pub values: [i32; SV_CHANNEL_COUNT],
+    /// Quality per channel; channels missing from the dataset are invalid
+    pub quality: [Quality; SV_CHANNEL_COUNT],
+    /// Sample counter (smpCnt)
     pub sample_number: u16,
-    /// Timestamp in microseconds
+    /// Sampling instant in microseconds, derived from the sample counter
     pub timestamp: u64,
+    /// Time the frame was received in microseconds (diagnostics only)
+    pub receive_time: u64,
+    /// Clock that took the receive time
+    pub receive_time_source: TimestampSource,
+    /// Receive time on the system clock in microseconds
+    ///
+    /// The kernel software timestamp, also when `receive_time` is a hardware
+    /// timestamp, or the time taken in user space where it is not available.
+    pub software_receive_time: u64,
+    /// Merging unit was synchronised when the sample was taken (smpSynch)
+    pub synchronized: bool,
+}
+
+impl SampleData {
+    /// Build sample data from the dataset entries of one ASDU
+    ///
+    /// # Arguments
+    /// * `layout` - Channel carried by each dataset entry, in dataset order
+    /// * `entries` - (value, quality bits) of each dataset entry
+    /// * `sample_number` - Sample counter (smpCnt)
+    /// * `timestamp` - Sampling instant in microseconds
+    pub fn from_entries<I>(layout: &[SvChannel], entries: I, sample_number: u16, timestamp: u64) -> Self
+    where
+        I: IntoIterator<Item = (i32, u32)>,
+    {
+        let mut sample = Self {
+            values: [0; SV_CHANNEL_COUNT],
+            quality: [Quality::INVALID; SV_CHANNEL_COUNT],
+            sample_number,
+            timestamp,
+            receive_time: timestamp,
+            receive_time_source: TimestampSource::System,
+            software_receive_time: timestamp,
+            synchronized: false,
+        };
+
+        for (channel, (value, quality)) in layout.iter().zip(entries) {
+            if let Some(index) = channel.index() {
+                sample.values[index] = value;
+                sample.quality[index] = Quality::from_bits(quality);
+            }
+        }
+
+        sample
+    }
+
+    /// Get the raw value of a channel (0 for unused)
+    pub fn value(&self, channel: SvChannel) -> i32 {
+        channel.index().map(|i| self.values[i]).unwrap_or(0)
+    }
+
+    /// Get the quality of a channel (invalid for unused)
+    pub fn channel_quality(&self, channel: SvChannel) -> Quality {
+        channel.index().map(|i| self.quality[i]).unwrap_or(Quality::INVALID)
+    }
+
+    /// Get the raw current value of a conductor
+    pub fn current_adc(&self, phase: Phase) -> i32 {
+        self.value(SvChannel::current(phase))
+    }
+
+    /// Get the raw voltage value of a conductor
+    pub fn voltage_adc(&self, phase: Phase) -> i32 {
+        self.value(SvChannel::voltage(phase))
+    }
+
+    /// Time from the sampling instant to reception in microseconds
+    /// 
+    /// Only meaningful when the merging unit is synchronised and the receive
+    /// clock is synchronised to the same time reference (e.g. PTP).
+    pub fn transport_delay(&self) -> i64 {
+        self.receive_time as i64 - self.timestamp as i64
+    }
 }
 
 /// SV subscriber that receives sampled values from the network
 pub struct SvSubscriber {
     config: SvConfig,
     socket: Option<Socket>,
+    filter: SvFilter,
+    stats: SvFilterStats,
+    supervisor: SampleCounterSupervisor,
+    clock: SampleClock,
+    buffer: Vec<u8>,
+    control: Vec<u8>,
+    #[cfg(target_os = "linux")]
+    ring: Option<PacketRing>,
 }
 
 impl SvSubscriber {
     /// Create a new SV subscriber with the given configuration
     pub fn new(config: SvConfig) -> Self {
+        let supervisor = SampleCounterSupervisor::from_config(&config);
+        let clock = SampleClock::new(config.samples_per_second());
         Self { 
             config,
             socket: None,
+            filter: SvFilter::accept_all(),
+            stats: SvFilterStats::default(),
+            supervisor,
+            clock,
+            // Receive buffers are allocated once, not per frame
+            buffer: vec![0u8; MAX_ETHERNET_FRAME_SIZE],
+            control: vec![0u8; TIMESTAMP_CONTROL_SIZE],
+            #[cfg(target_os = "linux")]
+            ring: None,
         }
     }
 
@@ -45,13 +228,26 @@ impl SvSubscriber {
             self.config.interface,
             self.config.multicast_mac
         );
+
+        // Only accept the configured stream
+        self.filter = SvFilter::from_config(&self.config)?;
         
         // Create raw socket for Ethernet (AF_PACKET on Linux)
         #[cfg(target_os = "linux")]
         {
-            
-            
-            
+            if self.config.packet_ring.enabled {
+                // Only SV frames to the subscribed address reach the ring
+                let dst_mac = parse_mac_address(&self.config.multicast_mac)?;
+                let program = ether_type_filter(0x88BA, Some(dst_mac));
+                self.ring = Some(PacketRing::new(
+                    &self.config.interface,
+                    &self.config.packet_ring,
+                    &program,
+                    self.config.hardware_timestamps,
+                )?);
+                return Ok(());
+            }
+
             // Create raw packet socket (ETH_P_ALL = 0x0003 to receive all protocols)
             let socket = Socket::new(
                 Domain::PACKET,
@@ -61,6 +257,11 @@ impl SvSubscriber {
             
             // Set socket to non-blocking mode
             socket.set_nonblocking(true)?;
+
+            // Kernel (and NIC) receive timestamps for every frame
+            use std::os::unix::io::AsRawFd;
+            let source = enable_timestamping(socket.as_raw_fd(), &self.config.interface, self.config.hardware_timestamps)?;
+            log::info!("SV receive timestamps: {:?}", source);
             
             // Bind to specific interface
             let if_index = get_interface_index(&self.config.interface)?;
@@ -82,74 +283,220 @@ impl SvSubscriber {
         }
     }
 
-    /// Receive the next sample from the SV stream
+    /// Receive the next SV frame from the network
     /// 
-    /// This receives and decodes actual IEC 61850-9-2 SV packets from the network
-    /// Returns the first current sample from the first ASDU
-    pub fn receive_sample(&mut self) -> Result<SampleData, Box<dyn Error>> {
-        let socket = self.socket.as_ref()
-            .ok_or("Socket not initialized. Call init() first.")?;
-        
-        // Buffer for receiving Ethernet frame
-        let mut buffer = vec![0u8; MAX_ETHERNET_FRAME_SIZE];
-        let mut recv_buf: Vec<std::mem::MaybeUninit<u8>> = vec![std::mem::MaybeUninit::uninit(); MAX_ETHERNET_FRAME_SIZE];
-        
-        loop {
-            // Receive packet (non-blocking)
-            let (len, _) = match socket.recv_from(&mut recv_buf) {
-                Ok((n, addr)) => (n, addr),
-                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    return Err("No data available (non-blocking mode)".into());
+    /// This receives and decodes actual IEC 61850-9-2 SV packets from the network.
+    /// Every ASDU of the frame is appended to `samples` with all channels mapped
+    /// according to the configured channel layout. The sample counter is
+    /// supervised: duplicated and late samples are dropped and missing samples
+    /// are handled according to the configured gap policy.
+    /// 
+    /// With the packet ring enabled, all frames of the next ring block are
+    /// decoded in place.
+    /// 
+    /// # Returns
+    /// Number of samples appended, 0 if no frame is available
+    pub fn receive_samples(&mut self, samples: &mut Vec<SampleData>) -> Result<usize, Box<dyn Error>> {
+        #[cfg(target_os = "linux")]
+        if let Some(mut ring) = self.ring.take() {
+            let count = self.receive_from_ring(&mut ring, samples);
+            self.ring = Some(ring);
+            return Ok(count);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::io::AsRawFd;
+
+            let fd = self.socket.as_ref()
+                .ok_or("Socket not initialized. Call init() first.")?
+                .as_raw_fd();
+
+            loop {
+                // Receive packet and its receive timestamp (non-blocking)
+                let Some((len, receive)) = receive_with_timestamp(fd, &mut self.buffer, &mut self.control)? else {
+                    return Ok(0);
+                };
+
+                // The buffer is moved out while decoding; taking a Vec does not allocate
+                let buffer = std::mem::take(&mut self.buffer);
+                let count = self.process_frame(&buffer[0..len], None, receive, samples);
+                self.buffer = buffer;
+
+                if count > 0 {
+                    return Ok(count);
                 }
-                Err(e) => return Err(Box::new(e)),
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = samples;
+            Err("Raw socket SV reception is only supported on Linux".into())
+        }
+    }
+
+    /// Decode the frames of the next ready ring block
+    #[cfg(target_os = "linux")]
+    fn receive_from_ring(&mut self, ring: &mut PacketRing, samples: &mut Vec<SampleData>) -> usize {
+        let start = samples.len();
+        while samples.len() == start {
+            let Some(block) = ring.next_block() else {
+                break;
             };
-            
-            // Copy to initialized buffer
-            for i in 0..len {
-                buffer[i] = unsafe { recv_buf[i].assume_init() };
+            for frame in block.frames() {
+                self.process_frame(frame.data, frame.vlan_tci, frame.timestamps(), samples);
             }
-            
-            if len < MIN_ETHERNET_FRAME_SIZE {
-                // Too small to be a valid Ethernet frame
-                continue;
+        }
+        samples.len() - start
+    }
+
+    /// Decode one Ethernet frame and append the samples of the subscribed stream
+    /// 
+    /// # Arguments
+    /// * `frame` - Ethernet frame starting at the destination MAC address
+    /// * `offloaded_tci` - 802.1Q tag removed from the frame by the driver
+    /// * `receive` - Kernel and NIC receive timestamps
+    /// * `samples` - Output buffer
+    /// 
+    /// # Returns
+    /// Number of samples appended
+    fn process_frame(
+        &mut self,
+        frame: &[u8],
+        offloaded_tci: Option<[u8; 2]>,
+        receive: ReceiveTimestamps,
+        samples: &mut Vec<SampleData>,
+    ) -> usize {
+        if frame.len() < MIN_ETHERNET_FRAME_SIZE {
+            // Too small to be a valid Ethernet frame
+            return 0;
+        }
+        
+        // Decode Ethernet header
+        let mut eth_header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut eth_header, frame);
+        
+        // Check if this is an SV packet (EtherType 0x88BA)
+        if eth_header.ether_type != [0x88, 0xBA] {
+            return 0;
+        }
+
+        // Drop frames from other merging units and VLANs (tagged and untagged frames are decoded alike)
+        let appid = u16::from_be_bytes(eth_header.appid);
+        let tci = eth_header.tci.or(offloaded_tci);
+        let accepted = self
+            .filter
+            .check_header(&eth_header.dst_addr, &eth_header.src_addr, appid)
+            .and_then(|_| self.filter.check_vlan(tci));
+        if let Err(reason) = accepted {
+            self.stats.record_reject(reason);
+            return 0;
+        }
+        
+        // Decode SMV PDU; iec_61850_lib returns it in newly allocated buffers
+        let pdu = match decode_smv(frame, pos) {
+            Ok(p) => p,
+            Err(e) => {
+                log::debug!("Failed to decode SMV PDU: {:?}", e);
+                return 0;
             }
-            
-            // Decode Ethernet header
-            let mut eth_header = EthernetHeader::default();
-            let pos = decode_ethernet_header(&mut eth_header, &buffer[0..len]);
-            
-            // Check if this is an SV packet (EtherType 0x88BA)
-            if eth_header.ether_type != [0x88, 0xBA] {
+        };
+        
+        if pdu.sav_asdu.is_empty() {
+            log::debug!("Received SV packet but no ASDUs found");
+            return 0;
+        }
+
+        // Receive time in microseconds, from the kernel or NIC where available
+        let now = || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_micros() as u64
+        };
+        let (receive_time, receive_time_source) = receive.best().unwrap_or_else(|| (now(), TimestampSource::System));
+        let software_receive_time = match (receive.software, receive_time_source) {
+            (Some(time), _) => time,
+            (None, TimestampSource::System) => receive_time,
+            (None, _) => now(),
+        };
+
+        // Extract all channels from every ASDU of the subscribed stream
+        let start = samples.len();
+        let mut accepted = 0;
+        let mut reject = None;
+        for asdu in &pdu.sav_asdu {
+            if let Err(reason) = self.filter.check_asdu(&asdu.msv_id, asdu.conf_rev) {
+                reject.get_or_insert(reason);
                 continue;
             }
-            
-            // Decode SMV PDU
-            let pdu = match decode_smv(&buffer[0..len], pos) {
-                Ok(p) => p,
-                Err(e) => {
-                    log::debug!("Failed to decode SMV PDU: {:?}", e);
-                    continue;
-                }
+            // Sampling instant from the sample counter, not the receive time
+            let synchronized = asdu.smp_synch != 0;
+            let timestamp = self.clock.timestamp(asdu.smp_cnt, synchronized, receive_time);
+            let mut sample = SampleData::from_entries(
+                &self.config.channel_layout,
+                asdu.all_data.iter().map(|entry| (entry.value, u32::from(entry.quality))),
+                asdu.smp_cnt,
+                timestamp,
+            );
+            sample.receive_time = receive_time;
+            sample.receive_time_source = receive_time_source;
+            sample.software_receive_time = software_receive_time;
+            sample.synchronized = synchronized;
+            self.supervisor.process(sample, samples);
+            accepted += 1;
+        }
+
+        if accepted == 0 {
+            if let Some(reason) = reject {
+                self.stats.record_reject(reason);
+            }
+            return 0;
+        }
+
+        self.stats.accepted += 1;
+        samples.len() - start
+    }
+
+    /// Wait until a frame can be received or the timeout expires
+    /// 
+    /// # Arguments
+    /// * `timeout` - Maximum time to wait
+    /// 
+    /// # Returns
+    /// `true` if a frame is available
+    pub fn wait(&self, timeout: std::time::Duration) -> Result<bool, Box<dyn Error>> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::io::AsRawFd;
+
+            let fd = match (&self.ring, &self.socket) {
+                (Some(ring), _) => ring.as_raw_fd(),
+                (None, Some(socket)) => socket.as_raw_fd(),
+                (None, None) => return Err("Socket not initialized. Call init() first.".into()),
             };
-            
-            // Extract sample data from first ASDU
-            if let Some(asdu) = pdu.sav_asdu.first() {
-                if let Some(sample) = asdu.all_data.first() {
-                    // Get current timestamp in microseconds
-                    let timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_micros() as u64;
-                    
-                    return Ok(SampleData {
-                        current_adc: sample.value,
-                        sample_number: asdu.smp_cnt,
-                        timestamp,
-                    });
+            let mut fds = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+            let ret = unsafe { libc::poll(&mut fds, 1, timeout_ms) };
+            if ret < 0 {
+                let err = std::io::Error::last_os_error();
+                if err.kind() == std::io::ErrorKind::Interrupted {
+                    return Ok(false);
                 }
+                return Err(Box::new(err));
             }
-            
-            log::debug!("Received SV packet but no samples found");
+            Ok(ret > 0)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = timeout;
+            Err("Raw socket SV reception is only supported on Linux".into())
         }
     }
 
@@ -158,10 +505,38 @@ impl SvSubscriber {
         &self.config
     }
 
+    /// Get the counters of accepted and rejected frames
+    pub fn filter_stats(&self) -> &SvFilterStats {
+        &self.stats
+    }
+
+    /// Get the sample loss statistics
+    pub fn loss_stats(&self) -> &SvLossStats {
+        self.supervisor.stats()
+    }
+
+    /// Check if protection must be blocked because samples were lost
+    pub fn is_blocked(&self) -> bool {
+        self.supervisor.is_blocked()
+    }
+
     /// Get the expected samples per cycle
     pub fn samples_per_cycle(&self) -> usize {
         self.config.samples_per_cycle
     }
+
+    /// Most samples one `receive_samples` call appends, for sizing the sample buffer
+    ///
+    /// A socket receive decodes one frame, a ring receive a whole block. A gap
+    /// adds at most one cycle of filled samples.
+    pub fn max_samples_per_receive(&self) -> usize {
+        let frames = if self.config.packet_ring.enabled {
+            self.config.packet_ring.block_size / MIN_RING_FRAME_SPACE
+        } else {
+            1
+        };
+        frames * MAX_ASDUS_PER_FRAME + self.config.samples_per_cycle
+    }
 }
 
 /// SV sample buffer that accumulates samples for one cycle
@@ -232,6 +607,75 @@ impl SvSampleBuffer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sample_data_le_layout() {
+        let entries = (0..8).map(|i| (100 * (i + 1), 0));
+        let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, 42, 1000);
+
+        assert_eq!(sample.sample_number, 42);
+        assert_eq!(sample.current_adc(Phase::A), 100);
+        assert_eq!(sample.current_adc(Phase::N), 400);
+        assert_eq!(sample.voltage_adc(Phase::A), 500);
+        assert_eq!(sample.voltage_adc(Phase::N), 800);
+        assert!(sample.quality.iter().all(Quality::is_good));
+    }
+
+    #[test]
+    fn test_sample_data_custom_layout() {
+        // Currents only, with an unused entry in between
+        let layout = [SvChannel::Ic, SvChannel::Unused, SvChannel::Ia, SvChannel::Ib];
+        let entries = vec![(3, 0), (99, 0), (1, 0), (2, Quality::TEST)];
+        let sample = SampleData::from_entries(&layout, entries, 0, 0);
+
+        assert_eq!(sample.current_adc(Phase::A), 1);
+        assert_eq!(sample.current_adc(Phase::B), 2);
+        assert_eq!(sample.current_adc(Phase::C), 3);
+        assert!(sample.channel_quality(SvChannel::Ib).is_test());
+
+        // Channels not present in the dataset are marked invalid
+        assert_eq!(sample.voltage_adc(Phase::A), 0);
+        assert!(sample.channel_quality(SvChannel::Va).is_invalid());
+    }
+
+    #[test]
+    fn test_sample_data_channel_quality() {
+        let entries = (0..8).map(|i| (i, if i == 2 { 0b01 } else { 0 }));
+        let sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, 0, 0);
+
+        assert!(sample.channel_quality(SvChannel::Ic).is_invalid());
+        assert!(sample.channel_quality(SvChannel::Ia).is_good());
+        assert!(sample.channel_quality(SvChannel::Unused).is_invalid());
+    }
+
+    #[test]
+    fn test_transport_delay() {
+        let mut sample = SampleData::from_entries(&SvChannel::LE_LAYOUT, [(0, 0)], 0, 1_000_000);
+        assert_eq!(sample.receive_time_source, TimestampSource::System);
+        assert_eq!(sample.transport_delay(), 0);
+        sample.receive_time = 1_000_350;
+        assert_eq!(sample.transport_delay(), 350);
+    }
+
+    #[test]
+    fn test_max_samples_per_receive() {
+        let mut config = SvConfig::default();
+        assert_eq!(SvSubscriber::new(config.clone()).max_samples_per_receive(), 8 + 80);
+
+        // A 4096 byte ring block holds up to 32 frames
+        config.packet_ring.enabled = true;
+        assert_eq!(SvSubscriber::new(config).max_samples_per_receive(), 32 * 8 + 80);
+    }
+
+    #[test]
+    fn test_sv_channel_mapping() {
+        assert_eq!(SvChannel::current(Phase::B), SvChannel::Ib);
+        assert_eq!(SvChannel::voltage(Phase::N), SvChannel::Vn);
+        assert_eq!(SvChannel::Vc.phase(), Some(Phase::C));
+        assert!(SvChannel::In.is_current());
+        assert!(!SvChannel::Va.is_current());
+        assert_eq!(SvChannel::Unused.index(), None);
+    }
+
     #[test]
     fn test_sample_buffer_creation() {
         let buffer = SvSampleBuffer::new(80);
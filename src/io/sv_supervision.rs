@@ -0,0 +1,434 @@
+/// Sample counter (smpCnt) supervision and missing-sample handling for SV streams
+use super::sv_input::SampleData;
+use crate::config::SvConfig;
+use crate::measurement::Quality;
+use serde::{Deserialize, Serialize};
+
+/// Consecutive out-of-order samples after which the supervisor follows the new counter
+///
+/// More than a late frame of 8 ASDUs can cause, so only a restarted or
+/// re-synchronised merging unit triggers it.
+pub const RESYNC_OUT_OF_ORDER: u32 = 16;
+
+/// Handling of samples lost in the SV stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GapPolicy {
+    /// Repeat the last received sample for each missing sample
+    HoldLast,
+    /// Fill missing samples by linear interpolation to the next received sample
+    Interpolate,
+    /// Do not fill; block protection until a full cycle of new samples is received
+    #[default]
+    Block,
+}
+
+/// Classification of a received sample by its sample counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleEvent {
+    /// First sample after start or reset
+    First,
+    /// Next sample in sequence
+    InSequence,
+    /// Sample received after the given number of missing samples
+    Gap(u32),
+    /// Same counter as the previous sample (dropped)
+    Duplicate,
+    /// Counter older than the previous sample (dropped)
+    OutOfOrder,
+    /// Counter outside the configured range (dropped)
+    OutOfRange,
+    /// Counter jumped back and stayed there; followed after `RESYNC_OUT_OF_ORDER` samples
+    Resynchronised,
+}
+
+/// Sample loss statistics of an SV stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SvLossStats {
+    /// Samples received from the network
+    pub received: u64,
+    /// Samples missing from the sequence
+    pub lost: u64,
+    /// Samples received twice
+    pub duplicates: u64,
+    /// Samples received out of order
+    pub out_of_order: u64,
+    /// Samples with a counter outside the configured range
+    pub out_of_range: u64,
+    /// Number of gaps (one or more consecutive lost samples)
+    pub gaps: u64,
+    /// Samples inserted by the gap policy
+    pub filled: u64,
+    /// Times the supervisor followed a counter that jumped back
+    pub resynchronisations: u64,
+}
+
+impl SvLossStats {
+    /// Fraction of expected samples that were lost (0.0..1.0)
+    pub fn loss_ratio(&self) -> f64 {
+        let expected = self.received + self.lost;
+        if expected == 0 {
+            0.0
+        } else {
+            self.lost as f64 / expected as f64
+        }
+    }
+}
+
+/// Supervisor of the SV sample counter
+///
+/// The sample counter runs from 0 to `samples_per_second - 1` and then wraps
+/// to 0. Each received sample is compared with the previous one: duplicates,
+/// late (out-of-order) and out-of-range samples are dropped, and gaps are
+/// handled according to the `GapPolicy`.
+///
+/// A gap longer than one cycle is never filled; protection is blocked until
+/// a full cycle of new samples has been received, whatever the policy.
+///
+/// When the merging unit restarts or re-synchronises, its counter jumps back
+/// and every following sample would be dropped as out of order for up to half
+/// a second. After `RESYNC_OUT_OF_ORDER` consecutive out-of-order samples the
+/// supervisor therefore accepts the new counter and blocks protection for one
+/// cycle.
+pub struct SampleCounterSupervisor {
+    samples_per_second: u32,
+    samples_per_cycle: u32,
+    policy: GapPolicy,
+    last: Option<SampleData>,
+    block_remaining: u32,
+    out_of_order_run: u32,
+    stats: SvLossStats,
+}
+
+impl SampleCounterSupervisor {
+    /// Create a new supervisor
+    ///
+    /// # Arguments
+    /// * `samples_per_second` - Sample rate; the counter wraps at this value
+    /// * `samples_per_cycle` - Samples per nominal cycle (block length)
+    /// * `policy` - Handling of missing samples
+    pub fn new(samples_per_second: u32, samples_per_cycle: u32, policy: GapPolicy) -> Self {
+        Self {
+            samples_per_second,
+            samples_per_cycle,
+            policy,
+            last: None,
+            block_remaining: 0,
+            out_of_order_run: 0,
+            stats: SvLossStats::default(),
+        }
+    }
+
+    /// Create a supervisor from the SV configuration
+    pub fn from_config(config: &SvConfig) -> Self {
+        Self::new(
+            config.samples_per_second(),
+            config.samples_per_cycle as u32,
+            config.gap_policy,
+        )
+    }
+
+    /// Supervise a received sample
+    ///
+    /// Samples to be processed, including any samples inserted by the gap
+    /// policy, are appended to `out` in sequence order.
+    ///
+    /// # Arguments
+    /// * `sample` - Sample received from the network
+    /// * `out` - Output buffer
+    ///
+    /// # Returns
+    /// Classification of the received sample
+    pub fn process(&mut self, mut sample: SampleData, out: &mut Vec<SampleData>) -> SampleEvent {
+        self.stats.received += 1;
+
+        if u32::from(sample.sample_number) >= self.samples_per_second {
+            self.stats.out_of_range += 1;
+            return SampleEvent::OutOfRange;
+        }
+
+        let event = match &self.last {
+            None => SampleEvent::First,
+            Some(last) => {
+                let diff = (u32::from(sample.sample_number) + self.samples_per_second
+                    - u32::from(last.sample_number))
+                    % self.samples_per_second;
+
+                if diff == 0 {
+                    self.stats.duplicates += 1;
+                    return SampleEvent::Duplicate;
+                } else if diff > self.samples_per_second / 2 {
+                    self.stats.out_of_order += 1;
+                    self.out_of_order_run += 1;
+                    if self.out_of_order_run < RESYNC_OUT_OF_ORDER {
+                        return SampleEvent::OutOfOrder;
+                    }
+                    // Follow the new counter; the samples in between were dropped
+                    self.stats.resynchronisations += 1;
+                    // Require a full cycle of new samples, including the received one
+                    self.block_remaining = self.samples_per_cycle;
+                    SampleEvent::Resynchronised
+                } else if diff == 1 {
+                    SampleEvent::InSequence
+                } else {
+                    SampleEvent::Gap(diff - 1)
+                }
+            }
+        };
+
+        if let SampleEvent::Gap(missing) = event {
+            self.stats.lost += u64::from(missing);
+            self.stats.gaps += 1;
+            self.handle_gap(missing, &sample, out);
+        }
+
+        self.out_of_order_run = 0;
+        self.last = Some(sample.clone());
+        if self.block_remaining > 0 {
+            // Protection must not evaluate samples while blocked
+            self.block_remaining -= 1;
+            for quality in sample.quality.iter_mut() {
+                *quality = quality.merge(Quality::INVALID);
+            }
+        }
+        out.push(sample);
+        event
+    }
+
+    /// Check if protection must be blocked because of missing samples
+    pub fn is_blocked(&self) -> bool {
+        self.block_remaining > 0
+    }
+
+    /// Get the loss statistics
+    pub fn stats(&self) -> &SvLossStats {
+        &self.stats
+    }
+
+    /// Get the gap policy
+    pub fn policy(&self) -> GapPolicy {
+        self.policy
+    }
+
+    /// Forget the last sample (e.g. after the stream was restarted)
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.block_remaining = 0;
+        self.out_of_order_run = 0;
+    }
+
+    /// Apply the gap policy for `missing` samples before `next`
+    fn handle_gap(&mut self, missing: u32, next: &SampleData, out: &mut Vec<SampleData>) {
+        let last = match &self.last {
+            Some(last) => last,
+            None => return,
+        };
+
+        if self.policy == GapPolicy::Block || missing >= self.samples_per_cycle {
+            // Require a full cycle of new samples, including the received one
+            self.block_remaining = self.samples_per_cycle;
+            return;
+        }
+
+        let steps = f64::from(missing + 1);
+        for k in 1..=missing {
+            let fraction = f64::from(k) / steps;
+            let mut filled = last.clone();
+            filled.sample_number = ((u32::from(last.sample_number) + k) % self.samples_per_second) as u16;
+            filled.timestamp = last.timestamp
+                + ((next.timestamp.saturating_sub(last.timestamp)) as f64 * fraction) as u64;
+
+            if self.policy == GapPolicy::Interpolate {
+                for (value, (&from, &to)) in filled
+                    .values
+                    .iter_mut()
+                    .zip(last.values.iter().zip(next.values.iter()))
+                {
+                    *value = (f64::from(from) + (f64::from(to) - f64::from(from)) * fraction).round() as i32;
+                }
+            }
+
+            // Mark inserted samples as substituted
+            for (quality, &next_quality) in filled.quality.iter_mut().zip(next.quality.iter()) {
+                *quality = quality.merge(next_quality).with(Quality::SUBSTITUTED);
+            }
+
+            out.push(filled);
+            self.stats.filled += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::SvChannel;
+    use crate::measurement::Phase;
+
+    fn sample(smp_cnt: u16, value: i32) -> SampleData {
+        let entries = (0..8).map(|_| (value, 0));
+        SampleData::from_entries(&SvChannel::LE_LAYOUT, entries, smp_cnt, u64::from(smp_cnt) * 250)
+    }
+
+    fn feed(supervisor: &mut SampleCounterSupervisor, samples: &[(u16, i32)]) -> Vec<SampleData> {
+        let mut out = Vec::new();
+        for &(smp_cnt, value) in samples {
+            supervisor.process(sample(smp_cnt, value), &mut out);
+        }
+        out
+    }
+
+    #[test]
+    fn test_in_sequence_and_wraparound() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let mut out = Vec::new();
+        assert_eq!(supervisor.process(sample(3998, 0), &mut out), SampleEvent::First);
+        assert_eq!(supervisor.process(sample(3999, 0), &mut out), SampleEvent::InSequence);
+        assert_eq!(supervisor.process(sample(0, 0), &mut out), SampleEvent::InSequence);
+        assert_eq!(out.len(), 3);
+        assert_eq!(supervisor.stats().lost, 0);
+        assert!(!supervisor.is_blocked());
+    }
+
+    #[test]
+    fn test_duplicate_and_out_of_order_dropped() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let mut out = Vec::new();
+        supervisor.process(sample(10, 0), &mut out);
+        assert_eq!(supervisor.process(sample(10, 0), &mut out), SampleEvent::Duplicate);
+        assert_eq!(supervisor.process(sample(9, 0), &mut out), SampleEvent::OutOfOrder);
+        assert_eq!(supervisor.process(sample(4000, 0), &mut out), SampleEvent::OutOfRange);
+        assert_eq!(out.len(), 1);
+        assert_eq!(supervisor.stats().duplicates, 1);
+        assert_eq!(supervisor.stats().out_of_order, 1);
+        assert_eq!(supervisor.stats().out_of_range, 1);
+    }
+
+    #[test]
+    fn test_resynchronise_after_counter_jump() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let mut out = Vec::new();
+        supervisor.process(sample(1000, 0), &mut out);
+
+        // Merging unit restarted at counter 0
+        for n in 0..RESYNC_OUT_OF_ORDER - 1 {
+            assert_eq!(supervisor.process(sample(n as u16, 0), &mut out), SampleEvent::OutOfOrder);
+        }
+        let n = RESYNC_OUT_OF_ORDER as u16 - 1;
+        assert_eq!(supervisor.process(sample(n, 0), &mut out), SampleEvent::Resynchronised);
+        assert_eq!(supervisor.process(sample(n + 1, 0), &mut out), SampleEvent::InSequence);
+        assert_eq!(out.len(), 3);
+        assert_eq!(supervisor.stats().resynchronisations, 1);
+
+        // Blocked for one cycle of samples following the new counter
+        for k in 2..80 {
+            assert!(supervisor.is_blocked());
+            supervisor.process(sample(n + k, 0), &mut out);
+        }
+        assert!(!supervisor.is_blocked());
+    }
+
+    #[test]
+    fn test_late_samples_do_not_resynchronise() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let mut out = Vec::new();
+        supervisor.process(sample(100, 0), &mut out);
+        for next in 101..104 {
+            for n in 80..80 + RESYNC_OUT_OF_ORDER as u16 - 1 {
+                assert_eq!(supervisor.process(sample(n, 0), &mut out), SampleEvent::OutOfOrder);
+            }
+            // An accepted sample ends the run of late samples
+            assert_eq!(supervisor.process(sample(next, 0), &mut out), SampleEvent::InSequence);
+        }
+        assert_eq!(supervisor.stats().resynchronisations, 0);
+        assert!(!supervisor.is_blocked());
+    }
+
+    #[test]
+    fn test_gap_across_wraparound() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let mut out = Vec::new();
+        supervisor.process(sample(3998, 0), &mut out);
+        assert_eq!(supervisor.process(sample(1, 0), &mut out), SampleEvent::Gap(2));
+        assert_eq!(supervisor.stats().lost, 2);
+        assert_eq!(supervisor.stats().gaps, 1);
+    }
+
+    #[test]
+    fn test_block_policy() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Block);
+        let out = feed(&mut supervisor, &[(0, 0), (3, 0)]);
+        assert_eq!(out.len(), 2);
+        assert!(out[0].quality[0].is_good());
+        assert!(out[1].quality[0].is_invalid());
+        assert!(supervisor.is_blocked());
+
+        // A full cycle of new samples releases the block
+        let mut out = Vec::new();
+        for n in 4..82 {
+            supervisor.process(sample(n, 0), &mut out);
+            assert!(supervisor.is_blocked());
+        }
+        supervisor.process(sample(82, 0), &mut out);
+        assert!(!supervisor.is_blocked());
+        assert!(out.iter().all(|s| s.quality[0].is_invalid()));
+
+        supervisor.process(sample(83, 0), &mut out);
+        assert!(out[79].quality[0].is_good());
+    }
+
+    #[test]
+    fn test_hold_last_policy() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::HoldLast);
+        let out = feed(&mut supervisor, &[(0, 100), (3, 400)]);
+        let values: Vec<i32> = out.iter().map(|s| s.current_adc(Phase::A)).collect();
+        let counters: Vec<u16> = out.iter().map(|s| s.sample_number).collect();
+        assert_eq!(values, vec![100, 100, 100, 400]);
+        assert_eq!(counters, vec![0, 1, 2, 3]);
+        assert!(out[1].quality[0].contains(Quality::SUBSTITUTED));
+        assert!(out[1].quality[0].is_good());
+        assert!(!supervisor.is_blocked());
+        assert_eq!(supervisor.stats().filled, 2);
+    }
+
+    #[test]
+    fn test_filled_quality_keeps_worse_validity() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::HoldLast);
+        let mut out = Vec::new();
+        let mut last = sample(0, 0);
+        last.quality[0] = Quality::INVALID;
+        let mut next = sample(2, 0);
+        next.quality[0] = Quality::from_bits(0b11).with(Quality::OLD_DATA);
+        supervisor.process(last, &mut out);
+        supervisor.process(next, &mut out);
+
+        let filled = out[1].quality[0];
+        assert!(filled.is_invalid());
+        assert!(filled.contains(Quality::OLD_DATA));
+        assert!(filled.contains(Quality::SUBSTITUTED));
+    }
+
+    #[test]
+    fn test_interpolate_policy() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Interpolate);
+        let out = feed(&mut supervisor, &[(3999, 100), (2, 400)]);
+        let values: Vec<i32> = out.iter().map(|s| s.current_adc(Phase::A)).collect();
+        let counters: Vec<u16> = out.iter().map(|s| s.sample_number).collect();
+        assert_eq!(values, vec![100, 200, 300, 400]);
+        assert_eq!(counters, vec![3999, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_long_gap_always_blocks() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::Interpolate);
+        let out = feed(&mut supervisor, &[(0, 0), (200, 0)]);
+        assert_eq!(out.len(), 2);
+        assert!(supervisor.is_blocked());
+    }
+
+    #[test]
+    fn test_loss_ratio() {
+        let mut supervisor = SampleCounterSupervisor::new(4000, 80, GapPolicy::HoldLast);
+        feed(&mut supervisor, &[(0, 0), (1, 0), (4, 0)]);
+        assert!((supervisor.stats().loss_ratio() - 0.4).abs() < 1e-12);
+    }
+}
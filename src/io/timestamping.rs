@@ -0,0 +1,298 @@
+/// Kernel and NIC receive timestamps (SO_TIMESTAMPING) for SV frames
+#[cfg(target_os = "linux")]
+use std::error::Error;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::RawFd;
+
+// Flags and control message types from linux/net_tstamp.h and asm/socket.h
+pub const SO_TIMESTAMPING: i32 = 37;
+pub const SOF_TIMESTAMPING_RX_HARDWARE: u32 = 1 << 2;
+pub const SOF_TIMESTAMPING_RX_SOFTWARE: u32 = 1 << 3;
+pub const SOF_TIMESTAMPING_SOFTWARE: u32 = 1 << 4;
+pub const SOF_TIMESTAMPING_RAW_HARDWARE: u32 = 1 << 6;
+
+/// Size of the control buffer needed for one SCM_TIMESTAMPING message
+pub const TIMESTAMP_CONTROL_SIZE: usize = 128;
+
+#[cfg(target_os = "linux")]
+const SIOCSHWTSTAMP: libc::c_ulong = 0x89B0;
+#[cfg(target_os = "linux")]
+const SIOCGHWTSTAMP: libc::c_ulong = 0x89B1;
+#[cfg(target_os = "linux")]
+const HWTSTAMP_FILTER_ALL: i32 = 1;
+
+/// Clock that produced a receive timestamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampSource {
+    /// Taken by the NIC when the frame arrived (PTP hardware clock)
+    Hardware,
+    /// Taken by the kernel when the frame entered the network stack
+    Software,
+    /// Taken in user space after the frame was received
+    #[default]
+    System,
+}
+
+/// Receive time in microseconds and the clock that took it
+pub type ReceiveTime = (u64, TimestampSource);
+
+/// Receive timestamps of one frame in microseconds, `None` where not provided
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiveTimestamps {
+    /// Kernel software timestamp (system clock)
+    pub software: Option<u64>,
+    /// NIC hardware timestamp (PTP hardware clock)
+    pub hardware: Option<u64>,
+}
+
+impl ReceiveTimestamps {
+    /// Best available receive time, preferring the hardware timestamp
+    pub fn best(&self) -> Option<ReceiveTime> {
+        self.hardware
+            .map(|time| (time, TimestampSource::Hardware))
+            .or_else(|| self.software.map(|time| (time, TimestampSource::Software)))
+    }
+}
+
+/// Hardware timestamping configuration (struct hwtstamp_config)
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct HwtstampConfig {
+    flags: i32,
+    tx_type: i32,
+    rx_filter: i32,
+}
+
+/// Request receive timestamps on a socket
+///
+/// Kernel software timestamps are always requested. With `hardware` the NIC
+/// is switched to timestamp all received frames as well; when the driver does
+/// not support this the socket falls back to software timestamps.
+///
+/// # Arguments
+/// * `fd` - Socket receiving the frames
+/// * `interface` - Network interface the socket is bound to
+/// * `hardware` - Request NIC hardware timestamps
+///
+/// # Returns
+/// Best timestamp source that was enabled
+#[cfg(target_os = "linux")]
+pub fn enable_timestamping(fd: RawFd, interface: &str, hardware: bool) -> Result<TimestampSource, Box<dyn Error>> {
+    let hardware = hardware
+        && match enable_hardware_timestamping(fd, interface) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Hardware timestamps unavailable on {}, using software timestamps: {}", interface, e);
+                false
+            }
+        };
+
+    let mut flags = SOF_TIMESTAMPING_RX_SOFTWARE | SOF_TIMESTAMPING_SOFTWARE;
+    if hardware {
+        flags |= SOF_TIMESTAMPING_RX_HARDWARE | SOF_TIMESTAMPING_RAW_HARDWARE;
+    }
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            SO_TIMESTAMPING,
+            &flags as *const u32 as *const libc::c_void,
+            std::mem::size_of::<u32>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(format!("Failed to set SO_TIMESTAMPING: {}", std::io::Error::last_os_error()).into());
+    }
+
+    Ok(if hardware { TimestampSource::Hardware } else { TimestampSource::Software })
+}
+
+/// Switch the NIC to timestamp all received frames (SIOCSHWTSTAMP)
+///
+/// The current configuration is read first (SIOCGHWTSTAMP) and only the
+/// receive filter is changed, so transmit timestamps of a PTP daemon running
+/// on the same interface are kept. Drivers that cannot report their
+/// configuration are left unchanged and an error is returned.
+/// Requires CAP_NET_ADMIN.
+#[cfg(target_os = "linux")]
+pub fn enable_hardware_timestamping(fd: RawFd, interface: &str) -> Result<(), Box<dyn Error>> {
+    let mut config = HwtstampConfig {
+        flags: 0,
+        tx_type: 0,
+        rx_filter: 0,
+    };
+    hwtstamp_ioctl(fd, interface, SIOCGHWTSTAMP, &mut config)
+        .map_err(|e| format!("SIOCGHWTSTAMP failed: {}", e))?;
+    if config.rx_filter == HWTSTAMP_FILTER_ALL {
+        return Ok(());
+    }
+
+    config.rx_filter = HWTSTAMP_FILTER_ALL;
+    hwtstamp_ioctl(fd, interface, SIOCSHWTSTAMP, &mut config)
+        .map_err(|e| format!("SIOCSHWTSTAMP failed: {}", e))?;
+    Ok(())
+}
+
+/// Read or write the hardware timestamping configuration of an interface
+///
+/// # Arguments
+/// * `request` - SIOCGHWTSTAMP or SIOCSHWTSTAMP
+/// * `config` - Configuration to write, updated with the driver's configuration
+#[cfg(target_os = "linux")]
+fn hwtstamp_ioctl(
+    fd: RawFd,
+    interface: &str,
+    request: libc::c_ulong,
+    config: &mut HwtstampConfig,
+) -> Result<(), Box<dyn Error>> {
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    let name = interface.as_bytes();
+    if name.len() >= libc::IFNAMSIZ {
+        return Err(format!("Interface name '{}' too long", interface).into());
+    }
+    for (dst, &src) in ifr.ifr_name.iter_mut().zip(name) {
+        *dst = src as libc::c_char;
+    }
+    ifr.ifr_ifru.ifru_data = config as *mut HwtstampConfig as *mut libc::c_char;
+
+    let ret = unsafe { libc::ioctl(fd, request as _, &mut ifr) };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+/// Receive one frame together with its receive timestamp
+///
+/// # Arguments
+/// * `fd` - Non-blocking socket with timestamping enabled
+/// * `buffer` - Frame buffer
+/// * `control` - Control message buffer of `TIMESTAMP_CONTROL_SIZE` bytes
+///
+/// # Returns
+/// Frame length and receive timestamps, `None` if no frame is available
+#[cfg(target_os = "linux")]
+pub fn receive_with_timestamp(
+    fd: RawFd,
+    buffer: &mut [u8],
+    control: &mut [u8],
+) -> std::io::Result<Option<(usize, ReceiveTimestamps)>> {
+    let mut iov = libc::iovec {
+        iov_base: buffer.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buffer.len(),
+    };
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = control.len() as _;
+
+    let len = unsafe { libc::recvmsg(fd, &mut msg, libc::MSG_DONTWAIT) };
+    if len < 0 {
+        let err = std::io::Error::last_os_error();
+        if err.kind() == std::io::ErrorKind::WouldBlock {
+            return Ok(None);
+        }
+        return Err(err);
+    }
+
+    let control_len = (msg.msg_controllen as usize).min(control.len());
+    Ok(Some((len as usize, parse_timestamp_control(&control[..control_len]))))
+}
+
+/// Extract the receive timestamps from the control messages of a frame
+///
+/// Both the software and the hardware timestamp are kept; a zero timestamp
+/// means the clock did not provide one.
+///
+/// # Arguments
+/// * `control` - Control messages returned by recvmsg
+///
+/// # Returns
+/// Software and hardware timestamps in microseconds
+#[cfg(target_os = "linux")]
+pub fn parse_timestamp_control(control: &[u8]) -> ReceiveTimestamps {
+    let align = |len: usize| (len + std::mem::size_of::<usize>() - 1) & !(std::mem::size_of::<usize>() - 1);
+    let header_len = align(std::mem::size_of::<libc::cmsghdr>());
+    let timespec_len = std::mem::size_of::<libc::timespec>();
+
+    let mut offset = 0;
+    while offset + header_len <= control.len() {
+        let header: libc::cmsghdr =
+            unsafe { std::ptr::read_unaligned(control.as_ptr().add(offset) as *const libc::cmsghdr) };
+        let len = header.cmsg_len as usize;
+        if len < header_len || offset + len > control.len() {
+            break;
+        }
+
+        // struct scm_timestamping: software, legacy, raw hardware
+        if header.cmsg_level == libc::SOL_SOCKET && header.cmsg_type == SO_TIMESTAMPING && len >= header_len + 3 * timespec_len {
+            let data = offset + header_len;
+            let read = |index: usize| -> u64 {
+                let ts: libc::timespec = unsafe {
+                    std::ptr::read_unaligned(control.as_ptr().add(data + index * timespec_len) as *const libc::timespec)
+                };
+                ts.tv_sec as u64 * 1_000_000 + ts.tv_nsec as u64 / 1_000
+            };
+            let (software, hardware) = (read(0), read(2));
+            return ReceiveTimestamps {
+                software: (software != 0).then_some(software),
+                hardware: (hardware != 0).then_some(hardware),
+            };
+        }
+        offset += align(len);
+    }
+    ReceiveTimestamps::default()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    /// Build one SCM_TIMESTAMPING control message with (software, hardware) times in µs
+    fn control_message(software: u64, hardware: u64) -> Vec<u8> {
+        let header_len = std::mem::size_of::<libc::cmsghdr>();
+        let timespec_len = std::mem::size_of::<libc::timespec>();
+        let len = header_len + 3 * timespec_len;
+
+        let mut header: libc::cmsghdr = unsafe { std::mem::zeroed() };
+        header.cmsg_len = len as _;
+        header.cmsg_level = libc::SOL_SOCKET;
+        header.cmsg_type = SO_TIMESTAMPING;
+
+        let mut control = vec![0u8; TIMESTAMP_CONTROL_SIZE];
+        unsafe {
+            std::ptr::write_unaligned(control.as_mut_ptr() as *mut libc::cmsghdr, header);
+            for (index, micros) in [software, 0, hardware].into_iter().enumerate() {
+                let mut ts: libc::timespec = std::mem::zeroed();
+                ts.tv_sec = (micros / 1_000_000) as _;
+                ts.tv_nsec = ((micros % 1_000_000) * 1_000) as _;
+                let at = header_len + index * timespec_len;
+                std::ptr::write_unaligned(control.as_mut_ptr().add(at) as *mut libc::timespec, ts);
+            }
+        }
+        control.truncate(len);
+        control
+    }
+
+    #[test]
+    fn test_hardware_timestamp_preferred() {
+        let timestamps = parse_timestamp_control(&control_message(1_000_000_100, 1_000_000_050));
+        assert_eq!(timestamps.best(), Some((1_000_000_050, TimestampSource::Hardware)));
+        // The software timestamp is kept alongside
+        assert_eq!(timestamps.software, Some(1_000_000_100));
+    }
+
+    #[test]
+    fn test_software_fallback() {
+        let timestamps = parse_timestamp_control(&control_message(1_000_000_100, 0));
+        assert_eq!(timestamps.best(), Some((1_000_000_100, TimestampSource::Software)));
+        assert_eq!(timestamps.hardware, None);
+    }
+
+    #[test]
+    fn test_no_timestamp() {
+        assert_eq!(parse_timestamp_control(&[]), ReceiveTimestamps::default());
+        assert_eq!(parse_timestamp_control(&control_message(0, 0)).best(), None);
+    }
+}
@@ -0,0 +1,119 @@
+/// IEEE 802.1Q VLAN tag handling for GOOSE and SV frames
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Tag protocol identifier of an IEEE 802.1Q tag
+pub const VLAN_TPID: [u8; 2] = [0x81, 0x00];
+
+/// Default 802.1p priority for GOOSE trips (IEC 61850-8-1)
+pub const DEFAULT_GOOSE_PRIORITY: u8 = 4;
+
+/// Largest valid VLAN ID (4095 is reserved)
+pub const MAX_VLAN_ID: u16 = 4094;
+
+/// Largest 802.1p priority code point
+pub const MAX_PRIORITY: u8 = 7;
+
+/// IEEE 802.1Q tag: VLAN ID and 802.1p priority
+///
+/// VLAN ID 0 is a priority tag: the frame carries a priority but belongs to
+/// the native VLAN of the port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VlanTag {
+    /// VLAN identifier (0..4094)
+    pub vlan_id: u16,
+    /// 802.1p priority code point (0..7)
+    pub priority: u8,
+}
+
+impl VlanTag {
+    /// Create a tag, limiting the VLAN ID to 12 bits and the priority to 3 bits
+    ///
+    /// Configured values are checked with `try_new` when the configuration is
+    /// loaded, so the limits only apply to values set in code.
+    pub fn new(vlan_id: u16, priority: u8) -> Self {
+        Self {
+            vlan_id: vlan_id.min(MAX_VLAN_ID),
+            priority: priority.min(MAX_PRIORITY),
+        }
+    }
+
+    /// Create a tag from configured values
+    ///
+    /// # Returns
+    /// Error if the VLAN ID is above 4094 or the priority above 7
+    pub fn try_new(vlan_id: u16, priority: u8) -> Result<Self, Box<dyn Error>> {
+        if vlan_id > MAX_VLAN_ID {
+            return Err(format!("VLAN ID {} out of range (0..{})", vlan_id, MAX_VLAN_ID).into());
+        }
+        if priority > MAX_PRIORITY {
+            return Err(format!("VLAN priority {} out of range (0..{})", priority, MAX_PRIORITY).into());
+        }
+        Ok(Self { vlan_id, priority })
+    }
+
+    /// Encode the tag control information (PCP, DEI = 0, VID)
+    pub fn to_tci(&self) -> [u8; 2] {
+        ((u16::from(self.priority) << 13) | (self.vlan_id & 0x0FFF)).to_be_bytes()
+    }
+
+    /// Decode the tag control information
+    pub fn from_tci(tci: [u8; 2]) -> Self {
+        let tci = u16::from_be_bytes(tci);
+        Self {
+            vlan_id: tci & 0x0FFF,
+            priority: (tci >> 13) as u8,
+        }
+    }
+}
+
+impl Default for VlanTag {
+    fn default() -> Self {
+        Self {
+            vlan_id: 0,
+            priority: DEFAULT_GOOSE_PRIORITY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tci_encoding() {
+        let tag = VlanTag::new(100, 4);
+        assert_eq!(tag.to_tci(), [0x80, 0x64]);
+        assert_eq!(VlanTag::from_tci([0x80, 0x64]), tag);
+    }
+
+    #[test]
+    fn test_tci_round_trip() {
+        for (vlan_id, priority) in [(0, 0), (1, 7), (4094, 6), (2000, 4)] {
+            let tag = VlanTag::new(vlan_id, priority);
+            assert_eq!(VlanTag::from_tci(tag.to_tci()), tag);
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let tag = VlanTag::new(5000, 9);
+        assert_eq!(tag.vlan_id, MAX_VLAN_ID);
+        assert_eq!(tag.priority, 7);
+    }
+
+    #[test]
+    fn test_out_of_range_rejected() {
+        assert_eq!(VlanTag::try_new(4094, 7).unwrap(), VlanTag::new(4094, 7));
+        assert!(VlanTag::try_new(5000, 4).is_err());
+        assert!(VlanTag::try_new(4095, 4).is_err());
+        assert!(VlanTag::try_new(100, 8).is_err());
+    }
+
+    #[test]
+    fn test_default_is_priority_tag() {
+        let tag = VlanTag::default();
+        assert_eq!(tag.vlan_id, 0);
+        assert_eq!(tag.priority, DEFAULT_GOOSE_PRIORITY);
+    }
+}
@@ -1,32 +1,51 @@
 /// POC Protection Functions - IEC 61850 compliant protection functions in Rust
 /// 
 /// This library implements protection functions according to IEC 61850 standard,
-/// including PTOC (Time Overcurrent Protection) with support for Sampled Values (SV)
-/// input and GOOSE trip output.
+/// including PTOC (Time Overcurrent Protection) for phase and earth faults, PIOC
+/// (Instantaneous Overcurrent Protection), negative-sequence and broken-conductor
+/// protection and sensitive and intermittent earth-fault protection with support
+/// for Sampled Values (SV) input and GOOSE trip output.
 
 pub mod config;
 pub mod measurement;
 pub mod protection;
 pub mod io;
+pub mod engine;
+pub mod realtime;
+pub mod metrics;
 
 pub use config::{
-    SystemConfig, PtocConfig, CtConfig, AdcConfig, GooseConfig, SvConfig,
+    SystemConfig, PtocConfig, PiocConfig, RdirConfig, EarthFaultConfig, PsdeConfig, PtefConfig,
+    BrokenConductorConfig, CtConfig, VtConfig, AdcConfig, GooseConfig, SvConfig, GooseSubscriberConfig,
+    GooseSubscriptionConfig, RealtimeConfig, PacketRingConfig,
 };
 
 pub use measurement::{
     calculate_rms, calculate_rms_i32, RmsCalculator,
-    adc_to_primary, adc_to_secondary, secondary_to_primary, CurrentScaler,
+    adc_to_primary, adc_to_secondary, secondary_to_primary, CurrentScaler, VoltageScaler,
+    Phasor, SequenceComponents, ChannelMeasurement, MeasurementFrame, MeasuringMode, Phase, Quality,
+    dft_phasor, DftWindow, FourierFilter, MimicFilter, PeakDetector,
 };
 
 pub use protection::{
-    ProtectionFunction, ProtectionResult, TripState, Ptoc,
+    ProtectionFunction, ProtectionResult, TripState, PhaseFlags, Ptoc, CurveType, ResetMode,
+    MultiStagePtoc, OvercurrentElement, Pioc, PiocMeasuringMode,
+    Rdir, Direction, DirectionMode, Directions, EarthPolarisation, ResidualPtoc, ResidualSource,
+    Psde, Ptef, SefCriterion, NegativeSequencePtoc, BrokenConductor,
 };
 
 pub use io::{
-    SampleData, SvSubscriber, SvSampleBuffer,
-    GooseTripMessage, GoosePublisher,
+    SampleData, SvChannel, SvSubscriber, SvSampleBuffer,
+    SvFilter, SvFilterStats, SvRejectReason,
+    GapPolicy, SampleCounterSupervisor, SampleEvent, SvLossStats, SampleClock, TimestampSource,
+    GooseTripMessage, GoosePublisher, GooseOutputs, GooseDataMember, RetransmissionScheduler,
+    GooseSubscriber, GooseSubscription, GooseValue, GooseEvent, Dbpos,
+    VlanTag,
 };
 
+pub use engine::ProtectionEngine;
+pub use metrics::{EngineMetrics, LatencyHistogram, TT6_TRANSFER_TIME_US};
+
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const NAME: &str = env!("CARGO_PKG_NAME");
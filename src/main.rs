@@ -1,10 +1,13 @@
-/// Example application demonstrating PTOC protection function
-use poc_protection_functions::{
-    SystemConfig, Ptoc, ProtectionFunction, ProtectionResult,
-    CurrentScaler, SvSampleBuffer,
-};
+/// Real-time protection application: SV input, protection functions and GOOSE output
+use poc_protection_functions::{realtime, GooseOutputs, GoosePublisher, ProtectionEngine, SvSubscriber, SystemConfig};
 use std::error::Error;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Interval between statistics log messages in microseconds
+const STATS_INTERVAL_US: u64 = 10_000_000;
+
+/// Maximum time to wait for an SV frame before servicing the GOOSE heartbeat
+const IDLE_WAIT: Duration = Duration::from_millis(1);
 
 fn get_timestamp_micros() -> u64 {
     SystemTime::now()
@@ -13,99 +16,239 @@ fn get_timestamp_micros() -> u64 {
         .as_micros() as u64
 }
 
+/// Update the GOOSE publisher without ending the process on a send error
+///
+/// # Arguments
+/// * `now` - Wall-clock time driving the retransmission schedule (microseconds)
+/// * `event_time` - Time of the outputs (microseconds)
+/// * `errors` - Counter of failed transmissions
+///
+/// # Returns
+/// `true` if a frame was sent
+fn publish(
+    publisher: &mut GoosePublisher,
+    outputs: &GooseOutputs,
+    now: u64,
+    event_time: u64,
+    errors: &mut u64,
+) -> bool {
+    match publisher.update(outputs, now, event_time) {
+        Ok(sent) => sent,
+        Err(e) => {
+            *errors += 1;
+            log::error!("GOOSE transmission failed: {}", e);
+            false
+        }
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: poc_ptoc <config.json>");
+    eprintln!("       poc_ptoc --write-config <config.json>   write the default configuration");
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Initialize logging
     env_logger::Builder::from_default_env()
         .filter_level(log::LevelFilter::Info)
         .init();
 
-    log::info!("POC Protection Functions - PTOC Example");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config_path = match args.as_slice() {
+        [flag, path] if flag == "--write-config" => {
+            SystemConfig::default().to_json_file(path)?;
+            log::info!("Saved default configuration to {}", path);
+            return Ok(());
+        }
+        [path] if !path.starts_with('-') => path.clone(),
+        _ => {
+            print_usage();
+            std::process::exit(2);
+        }
+    };
+
+    log::info!("POC Protection Functions - real-time engine");
     log::info!("Version: {}", poc_protection_functions::VERSION);
 
-    // Load or create configuration
-    let config = SystemConfig::default();
-    
-    log::info!("Configuration:");
-    log::info!("  PTOC Iset: {} A", config.ptoc.iset);
-    log::info!("  PTOC Tset: {} ms", config.ptoc.tset);
+    let config = SystemConfig::from_json_file(&config_path)
+        .map_err(|e| format!("Failed to load configuration {}: {}", config_path, e))?;
+
+    log::info!("Configuration: {}", config_path);
+    for (index, stage) in config.ptoc.iter().enumerate() {
+        log::info!(
+            "  PTOC{} Iset: {} A, Tset: {} ms, Curve: {:?}, Direction: {:?}",
+            index + 1,
+            stage.iset,
+            stage.tset,
+            stage.curve,
+            stage.direction
+        );
+    }
+    for (index, stage) in config.pioc.iter().enumerate() {
+        log::info!(
+            "  PIOC{} Iset: {} A, Tset: {} ms, Mode: {:?}, Direction: {:?}",
+            index + 1,
+            stage.iset,
+            stage.tset,
+            stage.measuring_mode,
+            stage.direction
+        );
+    }
+    for (index, stage) in config.earth_fault.stages.iter().enumerate() {
+        log::info!(
+            "  PTOC-EF{} IEset: {} A, Tset: {} ms, Curve: {:?}, Source: {:?}, Direction: {:?}",
+            index + 1,
+            stage.iset,
+            stage.tset,
+            stage.curve,
+            config.earth_fault.source,
+            stage.direction
+        );
+    }
+    for (index, stage) in config.psde.iter().enumerate() {
+        log::info!(
+            "  PSDE{} Iset: {} A, Tset: {} ms, Criterion: {:?}, Direction: {:?}, 3V0 min: {} V",
+            index + 1,
+            stage.iset,
+            stage.tset,
+            stage.criterion,
+            stage.direction,
+            stage.min_residual_voltage
+        );
+    }
+    if let Some(ptef) = &config.ptef {
+        log::info!(
+            "  PTEF Threshold: {} A, Pulses: {} in {} ms",
+            ptef.pulse_threshold,
+            ptef.pulse_count,
+            ptef.window
+        );
+    }
+    for (index, stage) in config.negative_sequence.iter().enumerate() {
+        log::info!(
+            "  PTOC-I2{} Iset: {} A, Tset: {} ms, Curve: {:?}, Direction: {:?}",
+            index + 1,
+            stage.iset,
+            stage.tset,
+            stage.curve,
+            stage.direction
+        );
+    }
+    if let Some(detector) = &config.broken_conductor {
+        log::info!(
+            "  Broken conductor I2/I1: {}, Tset: {} ms, I1 min: {} A",
+            detector.ratio_set,
+            detector.tset,
+            detector.min_current
+        );
+    }
     log::info!("  CT Ratio: {}/{}", config.ct.primary, config.ct.secondary);
+    if let Some(neutral_ct) = &config.neutral_ct {
+        log::info!("  Neutral CT Ratio: {}/{}", neutral_ct.primary, neutral_ct.secondary);
+    }
+    log::info!("  VT Ratio: {}/{}", config.vt.primary, config.vt.secondary);
     log::info!("  ADC Scale: {}", config.adc.scale_factor);
     log::info!("  Samples/cycle: {}", config.sv.samples_per_cycle);
+    if config.test_mode {
+        log::warn!("  Test mode: GOOSE published with simulation bit");
+    }
 
-    // Save example configuration
-    config.to_json_file("ptoc_config.json")?;
-    log::info!("Saved example configuration to ptoc_config.json");
-
-    // Initialize components
-    let mut ptoc = Ptoc::new(config.ptoc.clone());
-    let scaler = CurrentScaler::new(config.adc.clone(), config.ct.clone());
-    let mut sample_buffer = SvSampleBuffer::new(config.sv.samples_per_cycle);
-
-    log::info!("\nSimulating overcurrent condition...");
-
-    // Simulate receiving samples over time
-    // Generate one cycle of samples with overcurrent (150A primary)
-    let base_time = get_timestamp_micros();
-    let sample_period_us = 250; // 4000 samples/sec = 250 microseconds per sample
-
-    for cycle in 0..3 {
-        log::info!("\n--- Cycle {} ---", cycle + 1);
-        
-        sample_buffer.clear();
-
-        // Simulate 80 samples per cycle
-        for sample_num in 0..config.sv.samples_per_cycle {
-            // Simulate ADC value for sine wave with overcurrent
-            // 150A primary = 0.375A secondary (with 400/1 CT)
-            // 0.375A secondary = 375 ADC counts (with 0.001 scale factor)
-            // Peak = 375 * sqrt(2) ≈ 530 counts
-            let angle = 2.0 * std::f64::consts::PI * sample_num as f64 / config.sv.samples_per_cycle as f64;
-            let peak_adc = 530.0;
-            let adc_value = (peak_adc * angle.sin()) as i32;
-            
-            sample_buffer.add_sample(adc_value);
-        }
+    let mut engine = ProtectionEngine::new(&config);
 
-        // Calculate RMS from accumulated samples
-        if sample_buffer.is_full() {
-            // Convert ADC samples to primary current and calculate RMS
-            let primary_samples = scaler.scale_samples_to_primary(sample_buffer.samples());
-            let rms_current = poc_protection_functions::calculate_rms(&primary_samples);
-            
-            log::info!("RMS Current: {:.2} A (primary)", rms_current);
-
-            // Process through PTOC
-            let timestamp = base_time + ((cycle + 1) * config.sv.samples_per_cycle) as u64 * sample_period_us;
-            let result = ptoc.process(rms_current, timestamp);
-
-            match result {
-                ProtectionResult::NoTrip => {
-                    log::info!("Status: No Trip");
-                }
-                ProtectionResult::TripPending(delay) => {
-                    log::info!("Status: Trip Pending (remaining: {} ms)", delay.as_millis());
-                }
-                ProtectionResult::Trip => {
-                    log::warn!("Status: TRIP!");
-                    // In a real system, this would trigger GOOSE message
-                }
-                ProtectionResult::Disabled => {
-                    log::info!("Status: Disabled");
+    let mut subscriber = SvSubscriber::new(config.sv.clone());
+    subscriber.init()?;
+
+    let mut publisher = GoosePublisher::new(config.goose.clone());
+    publisher.init()?;
+    publisher.set_test_mode(config.test_mode);
+
+    // Send the initial state, then only on change and retransmission schedule
+    let mut outputs = engine.outputs();
+    let mut send_errors = 0;
+    let now = get_timestamp_micros();
+    publish(&mut publisher, &outputs, now, now, &mut send_errors);
+
+    // Room for all ASDUs of a receive call plus samples filled in for a gap
+    let mut samples = Vec::with_capacity(subscriber.max_samples_per_receive());
+    let mut next_stats = get_timestamp_micros() + STATS_INTERVAL_US;
+
+    // All buffers are allocated at this point; lock them in memory before going real-time
+    if config.realtime.enabled {
+        realtime::configure_current_thread(&config.realtime)?;
+    }
+
+    log::info!("Protection engine running");
+
+    loop {
+        samples.clear();
+        let count = match subscriber.receive_samples(&mut samples) {
+            Ok(count) => count,
+            Err(e) => {
+                log::error!("SV reception failed: {}", e);
+                std::thread::sleep(Duration::from_millis(100));
+                0
+            }
+        };
+
+        for sample in &samples {
+            let previous = outputs;
+            outputs = engine.process_sample(sample);
+            let st_num = publisher.st_num();
+
+            // Scheduled on the wall clock; the sampling instant is the event time t
+            let sent = publish(
+                &mut publisher,
+                &outputs,
+                get_timestamp_micros(),
+                sample.timestamp,
+                &mut send_errors,
+            );
+
+            // Transfer time from reception of the sample to completion of the send
+            if sent && publisher.st_num() != st_num {
+                engine.record_transfer_time(sample.software_receive_time, get_timestamp_micros());
+            }
+
+            if outputs.op.general() != previous.op.general() {
+                if outputs.op.general() {
+                    log::warn!("TRIP (phases: {:?})", outputs.op);
+                } else {
+                    log::info!("Trip reset");
                 }
             }
+        }
 
-            log::info!("PTOC State: {:?}", ptoc.state());
+        let now = get_timestamp_micros();
+        if count == 0 {
+            // Keep the GOOSE heartbeat running while no samples arrive
+            publish(&mut publisher, &outputs, now, now, &mut send_errors);
+            subscriber.wait(IDLE_WAIT)?;
         }
 
-        // Simulate time delay between cycles (20ms per cycle at 50Hz)
-        std::thread::sleep(std::time::Duration::from_millis(20));
+        if now >= next_stats {
+            let filter = subscriber.filter_stats();
+            let loss = subscriber.loss_stats();
+            log::info!(
+                "SV frames accepted: {}, rejected: {}, samples lost: {} ({:.4}%), GOOSE stNum: {}, sqNum: {}, send errors: {}",
+                filter.accepted,
+                filter.rejected(),
+                loss.lost,
+                loss.loss_ratio() * 100.0,
+                publisher.st_num(),
+                publisher.sq_num(),
+                send_errors
+            );
+            let metrics = engine.metrics();
+            log::info!(
+                "Transfer time: {} ({} not measurable)",
+                metrics.transfer_time,
+                metrics.skipped_transfer_times()
+            );
+            log::info!("Processing time: {}", metrics.processing_time);
+            if metrics.tt6_violations() > 0 {
+                log::warn!("Transfer time above TT6 (3 ms): {} state changes", metrics.tt6_violations());
+            }
+            next_stats = now + STATS_INTERVAL_US;
+        }
     }
-
-    log::info!("\nSimulation complete!");
-    log::info!("In a real deployment:");
-    log::info!("  - SV subscriber would receive samples from network");
-    log::info!("  - GOOSE publisher would send trip messages");
-    log::info!("  - Integration with Omicron test equipment");
-
-    Ok(())
 }
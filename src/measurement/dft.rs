@@ -0,0 +1,265 @@
+/// Fourier filter (DFT) estimation of the fundamental frequency phasor
+use super::phasor::Phasor;
+use std::f64::consts::{PI, SQRT_2};
+
+/// Data window of the Fourier filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DftWindow {
+    /// One full cycle: rejects DC and all integer harmonics
+    FullCycle,
+    /// Half a cycle: responds twice as fast, but does not reject DC offset
+    /// or even harmonics
+    HalfCycle,
+}
+
+impl DftWindow {
+    /// Number of samples in the window for the given sampling rate
+    pub fn window_len(&self, samples_per_cycle: usize) -> usize {
+        match self {
+            DftWindow::FullCycle => samples_per_cycle,
+            DftWindow::HalfCycle => samples_per_cycle / 2,
+        }
+    }
+}
+
+/// Calculate the fundamental phasor of a block of samples
+///
+/// # Arguments
+/// * `samples` - Samples of the window, oldest first (one or half a cycle)
+/// * `samples_per_cycle` - Number of samples per fundamental cycle
+///
+/// # Returns
+/// Phasor with RMS magnitude, angle referenced to the first sample
+pub fn dft_phasor(samples: &[f64], samples_per_cycle: usize) -> Phasor {
+    if samples.is_empty() || samples_per_cycle == 0 {
+        return Phasor::ZERO;
+    }
+
+    let step = 2.0 * PI / samples_per_cycle as f64;
+    let mut sum = Phasor::ZERO;
+    for (n, &x) in samples.iter().enumerate() {
+        let angle = step * n as f64;
+        sum.re += x * angle.cos();
+        sum.im -= x * angle.sin();
+    }
+    sum.scale(SQRT_2 / samples.len() as f64)
+}
+
+/// Recursive (sliding) Fourier filter for one channel
+///
+/// Each new sample updates the running sums in O(1): the newest sample's
+/// contribution is added and the oldest one's is removed. The correlation
+/// uses a fixed time reference (sample index modulo the cycle length), so
+/// a steady-state sinusoid gives a constant phasor. Phasors of channels fed
+/// in lockstep are therefore directly comparable.
+///
+/// The running sums are recalculated from the window once per cycle to keep
+/// floating point error from accumulating.
+pub struct FourierFilter {
+    window: DftWindow,
+    samples_per_cycle: usize,
+    cos_table: Vec<f64>,
+    sin_table: Vec<f64>,
+    samples: Vec<f64>,
+    window_len: usize,
+    current_index: usize,
+    phase_index: usize,
+    sample_count: usize,
+    sum: Phasor,
+}
+
+impl FourierFilter {
+    /// Create a new Fourier filter
+    ///
+    /// # Arguments
+    /// * `samples_per_cycle` - Number of samples per cycle (e.g., 80)
+    /// * `window` - Full-cycle or half-cycle data window
+    pub fn new(samples_per_cycle: usize, window: DftWindow) -> Self {
+        let step = 2.0 * PI / samples_per_cycle as f64;
+        let window_len = window.window_len(samples_per_cycle);
+        Self {
+            window,
+            samples_per_cycle,
+            cos_table: (0..samples_per_cycle).map(|n| (step * n as f64).cos()).collect(),
+            sin_table: (0..samples_per_cycle).map(|n| (step * n as f64).sin()).collect(),
+            samples: vec![0.0; window_len],
+            window_len,
+            current_index: 0,
+            phase_index: 0,
+            sample_count: 0,
+            sum: Phasor::ZERO,
+        }
+    }
+
+    /// Create a full-cycle Fourier filter
+    pub fn full_cycle(samples_per_cycle: usize) -> Self {
+        Self::new(samples_per_cycle, DftWindow::FullCycle)
+    }
+
+    /// Create a half-cycle Fourier filter
+    pub fn half_cycle(samples_per_cycle: usize) -> Self {
+        Self::new(samples_per_cycle, DftWindow::HalfCycle)
+    }
+
+    /// Add a new sample and return the updated phasor
+    ///
+    /// # Arguments
+    /// * `sample` - New sample value
+    pub fn add_sample(&mut self, sample: f64) -> Phasor {
+        // Reference index of the sample leaving the window
+        let oldest_phase =
+            (self.phase_index + self.samples_per_cycle - self.window_len) % self.samples_per_cycle;
+        let oldest = self.samples[self.current_index];
+
+        self.sum.re += sample * self.cos_table[self.phase_index]
+            - oldest * self.cos_table[oldest_phase];
+        self.sum.im -= sample * self.sin_table[self.phase_index]
+            - oldest * self.sin_table[oldest_phase];
+
+        self.samples[self.current_index] = sample;
+        self.current_index = (self.current_index + 1) % self.window_len;
+        self.phase_index = (self.phase_index + 1) % self.samples_per_cycle;
+        self.sample_count += 1;
+
+        if self.phase_index == 0 {
+            self.resynchronize();
+        }
+
+        self.phasor()
+    }
+
+    /// Get the current fundamental phasor (RMS magnitude)
+    pub fn phasor(&self) -> Phasor {
+        self.sum.scale(SQRT_2 / self.window_len as f64)
+    }
+
+    /// Get the current fundamental magnitude (RMS)
+    pub fn magnitude(&self) -> f64 {
+        self.phasor().magnitude()
+    }
+
+    /// Get the current fundamental angle in radians
+    pub fn angle(&self) -> f64 {
+        self.phasor().angle()
+    }
+
+    /// Check if the filter has received a full window of samples
+    pub fn is_full(&self) -> bool {
+        self.sample_count >= self.window_len
+    }
+
+    /// Get the data window
+    pub fn window(&self) -> DftWindow {
+        self.window
+    }
+
+    /// Get the number of samples in the data window
+    pub fn window_len(&self) -> usize {
+        self.window_len
+    }
+
+    /// Reset the filter
+    pub fn reset(&mut self) {
+        self.samples.fill(0.0);
+        self.current_index = 0;
+        self.phase_index = 0;
+        self.sample_count = 0;
+        self.sum = Phasor::ZERO;
+    }
+
+    /// Recalculate the running sums from the samples in the window
+    fn resynchronize(&mut self) {
+        let mut sum = Phasor::ZERO;
+        for offset in 0..self.window_len {
+            // Walk the window from oldest to newest sample
+            let index = (self.current_index + offset) % self.window_len;
+            let phase = (self.phase_index + self.samples_per_cycle - self.window_len + offset)
+                % self.samples_per_cycle;
+            sum.re += self.samples[index] * self.cos_table[phase];
+            sum.im -= self.samples[index] * self.sin_table[phase];
+        }
+        self.sum = sum;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: usize = 80;
+
+    /// Sample n of x(t) = sqrt(2) * rms * cos(wt + phi) + dc
+    fn sample(n: usize, rms: f64, phi_deg: f64, dc: f64) -> f64 {
+        SQRT_2 * rms * (2.0 * PI * n as f64 / N as f64 + phi_deg.to_radians()).cos() + dc
+    }
+
+    #[test]
+    fn test_dft_phasor_block() {
+        let samples: Vec<f64> = (0..N).map(|n| sample(n, 100.0, 30.0, 0.0)).collect();
+        let phasor = dft_phasor(&samples, N);
+        assert!((phasor.magnitude() - 100.0).abs() < 1e-9);
+        assert!((phasor.angle_deg() - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_full_cycle_steady_state() {
+        let mut filter = FourierFilter::full_cycle(N);
+        for n in 0..3 * N + 17 {
+            filter.add_sample(sample(n, 100.0, -45.0, 0.0));
+        }
+        assert!(filter.is_full());
+        assert!((filter.magnitude() - 100.0).abs() < 1e-9);
+        assert!((filter.phasor().angle_deg() + 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_full_cycle_rejects_dc_and_harmonics() {
+        let mut filter = FourierFilter::full_cycle(N);
+        for n in 0..2 * N {
+            let third = 30.0 * (3.0 * 2.0 * PI * n as f64 / N as f64).sin();
+            filter.add_sample(sample(n, 100.0, 0.0, 50.0) + third);
+        }
+        assert!((filter.magnitude() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_half_cycle_magnitude() {
+        let mut filter = FourierFilter::half_cycle(N);
+        assert_eq!(filter.window_len(), 40);
+        for n in 0..N + 5 {
+            filter.add_sample(sample(n, 10.0, 60.0, 0.0));
+        }
+        assert!((filter.magnitude() - 10.0).abs() < 1e-9);
+        assert!((filter.phasor().angle_deg() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_half_cycle_settles_faster() {
+        let mut full = FourierFilter::full_cycle(N);
+        let mut half = FourierFilter::half_cycle(N);
+        for n in 0..N / 2 {
+            full.add_sample(sample(n, 100.0, 0.0, 0.0));
+            half.add_sample(sample(n, 100.0, 0.0, 0.0));
+        }
+        assert!((half.magnitude() - 100.0).abs() < 1e-9);
+        assert!(full.magnitude() < 90.0);
+    }
+
+    #[test]
+    fn test_no_drift_over_long_run() {
+        let mut filter = FourierFilter::full_cycle(N);
+        for n in 0..200_000 {
+            filter.add_sample(sample(n, 1000.0, 10.0, 0.0));
+        }
+        assert!((filter.magnitude() - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filter = FourierFilter::full_cycle(N);
+        filter.add_sample(1.0);
+        filter.reset();
+        assert!(!filter.is_full());
+        assert_eq!(filter.phasor(), Phasor::ZERO);
+    }
+}
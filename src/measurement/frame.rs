@@ -0,0 +1,396 @@
+/// Three-phase and neutral measurement frame passed to protection functions
+use super::phasor::Phasor;
+use super::sequence::SequenceComponents;
+use serde::{Deserialize, Serialize};
+
+/// Operating quantity used by a protection function
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeasuringMode {
+    /// True RMS value, including harmonics and DC offset
+    #[default]
+    Rms,
+    /// Magnitude of the fundamental frequency phasor (DFT)
+    Fundamental,
+}
+
+/// Phase (or neutral) conductor of a measurement channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    A,
+    B,
+    C,
+    /// Neutral / residual
+    N,
+}
+
+impl Phase {
+    /// The three phase conductors, without neutral
+    pub const PHASES: [Phase; 3] = [Phase::A, Phase::B, Phase::C];
+
+    /// All conductors including neutral
+    pub const ALL: [Phase; 4] = [Phase::A, Phase::B, Phase::C, Phase::N];
+
+    /// Index of the conductor in a `[_; 4]` channel array
+    pub fn index(&self) -> usize {
+        match self {
+            Phase::A => 0,
+            Phase::B => 1,
+            Phase::C => 2,
+            Phase::N => 3,
+        }
+    }
+
+    /// IEC 61850 data attribute name (phsA, phsB, phsC, neut)
+    pub fn iec61850_name(&self) -> &'static str {
+        match self {
+            Phase::A => "phsA",
+            Phase::B => "phsB",
+            Phase::C => "phsC",
+            Phase::N => "neut",
+        }
+    }
+}
+
+/// IEC 61850-7-3 quality of a measured value
+///
+/// Stored as the 32-bit quality word used by IEC 61850-9-2LE, with bit 0 as
+/// the least significant bit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quality(u32);
+
+impl Quality {
+    /// Good quality, no flags set
+    pub const GOOD: Quality = Quality(0);
+    /// Invalid quality
+    pub const INVALID: Quality = Quality(Self::VALIDITY_INVALID);
+
+    const VALIDITY_MASK: u32 = 0b11;
+    const VALIDITY_INVALID: u32 = 0b01;
+    const VALIDITY_QUESTIONABLE: u32 = 0b11;
+
+    /// Detail quality: overflow
+    pub const OVERFLOW: u32 = 1 << 2;
+    /// Detail quality: out of range
+    pub const OUT_OF_RANGE: u32 = 1 << 3;
+    /// Detail quality: bad reference
+    pub const BAD_REFERENCE: u32 = 1 << 4;
+    /// Detail quality: oscillatory
+    pub const OSCILLATORY: u32 = 1 << 5;
+    /// Detail quality: failure
+    pub const FAILURE: u32 = 1 << 6;
+    /// Detail quality: old data
+    pub const OLD_DATA: u32 = 1 << 7;
+    /// Detail quality: inconsistent
+    pub const INCONSISTENT: u32 = 1 << 8;
+    /// Detail quality: inaccurate
+    pub const INACCURATE: u32 = 1 << 9;
+    /// Source: substituted (0 = process)
+    pub const SUBSTITUTED: u32 = 1 << 10;
+    /// Test flag
+    pub const TEST: u32 = 1 << 11;
+    /// Operator blocked
+    pub const OPERATOR_BLOCKED: u32 = 1 << 12;
+    /// Derived value (IEC 61850-9-2LE)
+    pub const DERIVED: u32 = 1 << 13;
+
+    /// Create a quality from its raw bit representation
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Get the raw bit representation
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Check if the validity is good
+    pub fn is_good(&self) -> bool {
+        self.0 & Self::VALIDITY_MASK == 0
+    }
+
+    /// Check if the validity is invalid
+    pub fn is_invalid(&self) -> bool {
+        self.0 & Self::VALIDITY_MASK == Self::VALIDITY_INVALID
+    }
+
+    /// Check if the validity is questionable
+    pub fn is_questionable(&self) -> bool {
+        self.0 & Self::VALIDITY_MASK == Self::VALIDITY_QUESTIONABLE
+    }
+
+    /// Check if the test flag is set
+    pub fn is_test(&self) -> bool {
+        self.0 & Self::TEST != 0
+    }
+
+    /// Check if a detail quality or flag bit is set
+    pub fn contains(&self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    /// Return a copy with the given flag bits set
+    pub fn with(&self, flag: u32) -> Self {
+        Self(self.0 | flag)
+    }
+
+    /// Combine two qualities, keeping all detail flags and the worse validity
+    pub fn merge(&self, other: Quality) -> Self {
+        let flags = (self.0 | other.0) & !Self::VALIDITY_MASK;
+        let validity = if self.is_invalid() || other.is_invalid() {
+            Self::VALIDITY_INVALID
+        } else {
+            (self.0 | other.0) & Self::VALIDITY_MASK
+        };
+        Self(flags | validity)
+    }
+}
+
+/// Measured quantities of one analogue channel
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChannelMeasurement {
+    /// True RMS value in primary units
+    pub rms: f64,
+    /// Fundamental frequency phasor in primary units (RMS magnitude)
+    pub phasor: Phasor,
+    /// Quality of the channel
+    pub quality: Quality,
+    /// Sampled value at the frame timestamp in primary units
+    pub instantaneous: f64,
+}
+
+impl ChannelMeasurement {
+    /// Create a channel measurement with good quality
+    pub fn new(rms: f64, phasor: Phasor) -> Self {
+        Self {
+            rms,
+            phasor,
+            quality: Quality::GOOD,
+            instantaneous: 0.0,
+        }
+    }
+
+    /// Get the magnitude according to the measuring mode
+    pub fn magnitude(&self, mode: MeasuringMode) -> f64 {
+        match mode {
+            MeasuringMode::Rms => self.rms,
+            MeasuringMode::Fundamental => self.phasor.magnitude(),
+        }
+    }
+
+    /// Create a channel measurement from an RMS value only
+    pub fn from_rms(rms: f64) -> Self {
+        Self::new(rms, Phasor::from_polar(rms, 0.0))
+    }
+}
+
+/// Snapshot of all three-phase and neutral measurements at one instant
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeasurementFrame {
+    /// Timestamp of the measurement (microseconds)
+    pub timestamp: u64,
+    /// Phase A, B, C and neutral currents in primary amperes
+    pub currents: [ChannelMeasurement; 4],
+    /// Phase A, B, C and neutral voltages in primary volts
+    pub voltages: [ChannelMeasurement; 4],
+}
+
+impl MeasurementFrame {
+    /// Create an empty frame (all channels zero, good quality)
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            ..Self::default()
+        }
+    }
+
+    /// Create a frame from phase A, B and C RMS currents
+    ///
+    /// Phasor angles are set to a balanced 0°, -120°, +120° system.
+    pub fn from_phase_currents(currents: [f64; 3], timestamp: u64) -> Self {
+        let mut frame = Self::new(timestamp);
+        for (i, (&rms, angle)) in currents.iter().zip([0.0, -120.0, 120.0]).enumerate() {
+            frame.currents[i] = ChannelMeasurement::new(rms, Phasor::from_polar_deg(rms, angle));
+        }
+        frame
+    }
+
+    /// Create a frame with the same RMS current in all three phases
+    pub fn balanced(current: f64, timestamp: u64) -> Self {
+        Self::from_phase_currents([current; 3], timestamp)
+    }
+
+    /// Get the current measurement of a conductor
+    pub fn current(&self, phase: Phase) -> &ChannelMeasurement {
+        &self.currents[phase.index()]
+    }
+
+    /// Get the voltage measurement of a conductor
+    pub fn voltage(&self, phase: Phase) -> &ChannelMeasurement {
+        &self.voltages[phase.index()]
+    }
+
+    /// Get a mutable current measurement of a conductor
+    pub fn current_mut(&mut self, phase: Phase) -> &mut ChannelMeasurement {
+        &mut self.currents[phase.index()]
+    }
+
+    /// Get a mutable voltage measurement of a conductor
+    pub fn voltage_mut(&mut self, phase: Phase) -> &mut ChannelMeasurement {
+        &mut self.voltages[phase.index()]
+    }
+
+    /// Get the highest phase current RMS value (A, B, C)
+    pub fn max_phase_current(&self) -> f64 {
+        Phase::PHASES
+            .iter()
+            .map(|&phase| self.current(phase).rms)
+            .fold(0.0, f64::max)
+    }
+
+    /// Calculate the residual current 3I0 = Ia + Ib + Ic
+    ///
+    /// RMS and phasor are the magnitude of the fundamental phasor sum, since
+    /// the true RMS of the sum cannot be derived from the phase RMS values.
+    /// The quality is that of the worst phase.
+    pub fn residual_current(&self) -> ChannelMeasurement {
+        residual(&self.currents)
+    }
+
+    /// Calculate the residual voltage 3V0 = Va + Vb + Vc
+    ///
+    /// Calculated like `residual_current` from the phase voltages.
+    pub fn residual_voltage(&self) -> ChannelMeasurement {
+        residual(&self.voltages)
+    }
+
+    /// Calculate the symmetrical components of the phase currents
+    ///
+    /// Calculated from the fundamental phasors; the quality is that of the worst phase.
+    pub fn current_sequence(&self) -> SequenceComponents {
+        sequence(&self.currents)
+    }
+
+    /// Calculate the symmetrical components of the phase voltages
+    ///
+    /// Calculated from the fundamental phasors; the quality is that of the worst phase.
+    pub fn voltage_sequence(&self) -> SequenceComponents {
+        sequence(&self.voltages)
+    }
+}
+
+/// Sum of the phase channels A, B and C with the quality of the worst phase
+fn residual(channels: &[ChannelMeasurement; 4]) -> ChannelMeasurement {
+    let phases = Phase::PHASES.map(|phase| channels[phase.index()]);
+    let phasor = phases.iter().fold(Phasor::default(), |sum, channel| sum + channel.phasor);
+    ChannelMeasurement {
+        rms: phasor.magnitude(),
+        phasor,
+        quality: worst_quality(&phases),
+        instantaneous: phases.iter().map(|channel| channel.instantaneous).sum(),
+    }
+}
+
+/// Symmetrical components of the phase channels A, B and C
+fn sequence(channels: &[ChannelMeasurement; 4]) -> SequenceComponents {
+    let phases = Phase::PHASES.map(|phase| channels[phase.index()]);
+    SequenceComponents {
+        quality: worst_quality(&phases),
+        ..SequenceComponents::from_phasors(phases.map(|channel| channel.phasor))
+    }
+}
+
+/// Quality of the worst channel: invalid, then questionable, then good
+fn worst_quality(channels: &[ChannelMeasurement]) -> Quality {
+    let qualities = || channels.iter().map(|channel| channel.quality);
+    qualities()
+        .find(Quality::is_invalid)
+        .or_else(|| qualities().find(|quality| !quality.is_good()))
+        .unwrap_or(Quality::GOOD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_validity() {
+        assert!(Quality::GOOD.is_good());
+        assert!(Quality::INVALID.is_invalid());
+        assert!(Quality::from_bits(0b11).is_questionable());
+        assert!(Quality::GOOD.with(Quality::TEST).is_test());
+        assert!(Quality::GOOD.with(Quality::TEST).is_good());
+        assert!(Quality::from_bits(Quality::FAILURE).contains(Quality::FAILURE));
+    }
+
+    #[test]
+    fn test_quality_merge() {
+        let questionable = Quality::from_bits(0b11).with(Quality::OLD_DATA);
+        let merged = Quality::INVALID.merge(questionable);
+        assert!(merged.is_invalid());
+        assert!(merged.contains(Quality::OLD_DATA));
+        assert!(questionable.merge(Quality::GOOD).is_questionable());
+        assert!(Quality::GOOD.merge(Quality::GOOD.with(Quality::TEST)).is_good());
+    }
+
+    #[test]
+    fn test_frame_from_phase_currents() {
+        let frame = MeasurementFrame::from_phase_currents([100.0, 200.0, 300.0], 42);
+        assert_eq!(frame.timestamp, 42);
+        assert_eq!(frame.current(Phase::B).rms, 200.0);
+        assert!((frame.current(Phase::B).phasor.angle_deg() + 120.0).abs() < 1e-9);
+        assert_eq!(frame.current(Phase::N).rms, 0.0);
+        assert_eq!(frame.max_phase_current(), 300.0);
+    }
+
+    #[test]
+    fn test_residual_current() {
+        let frame = MeasurementFrame::balanced(500.0, 0);
+        assert!(frame.residual_current().rms < 1e-9);
+
+        let mut frame = MeasurementFrame::from_phase_currents([300.0, 100.0, 100.0], 0);
+        let residual = frame.residual_current();
+        assert!((residual.rms - 200.0).abs() < 1e-9);
+        assert!(residual.phasor.angle_deg().abs() < 1e-9);
+        assert!(residual.quality.is_good());
+
+        frame.current_mut(Phase::C).quality = Quality::INVALID;
+        assert!(frame.residual_current().quality.is_invalid());
+
+        // Phase A voltage collapsed: 3V0 = −Va of the healthy system
+        for (phase, angle) in Phase::PHASES.into_iter().zip([0.0, -120.0, 120.0]) {
+            let magnitude = if phase == Phase::A { 0.0 } else { 11547.0 };
+            *frame.voltage_mut(phase) = ChannelMeasurement::new(magnitude, Phasor::from_polar_deg(magnitude, angle));
+        }
+        let residual = frame.residual_voltage();
+        assert!((residual.rms - 11547.0).abs() < 1e-6);
+        assert!((residual.phasor.angle_deg().abs() - 180.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_current_sequence() {
+        let mut frame = MeasurementFrame::from_phase_currents([400.0, 100.0, 100.0], 0);
+        let sequence = frame.current_sequence();
+        assert!((sequence.positive.magnitude() - 200.0).abs() < 1e-9);
+        assert!((sequence.negative.magnitude() - 100.0).abs() < 1e-9);
+        assert!((sequence.zero.magnitude() - 100.0).abs() < 1e-9);
+        assert!(sequence.quality.is_good());
+
+        frame.current_mut(Phase::B).quality = Quality::INVALID;
+        assert!(frame.current_sequence().quality.is_invalid());
+    }
+
+    #[test]
+    fn test_measuring_mode() {
+        let channel = ChannelMeasurement::new(110.0, Phasor::from_polar(100.0, 0.3));
+        assert_eq!(channel.magnitude(MeasuringMode::Rms), 110.0);
+        assert!((channel.magnitude(MeasuringMode::Fundamental) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_phase_index() {
+        for (i, phase) in Phase::ALL.iter().enumerate() {
+            assert_eq!(phase.index(), i);
+        }
+        assert_eq!(Phase::N.iec61850_name(), "neut");
+    }
+}
@@ -0,0 +1,156 @@
+/// Sample-based measurement for instantaneous protection: DC offset removal and peak detection
+use std::f64::consts::PI;
+
+/// Mimic (replica impedance) filter removing a decaying DC offset
+///
+/// The filter `y[n] = k · (x[n] − a · x[n−1])` with `a = e^(−Ts/τ)` exactly
+/// cancels an exponential with time constant τ, the DC offset of a fault
+/// current on a line with X/R = ω·τ. The gain `k` is normalised to unity at
+/// the fundamental frequency. Higher frequencies are amplified, so the
+/// output is noisier than the input.
+///
+/// The first sample after a reset only primes the filter, so the start of a
+/// signal is not seen as a step.
+#[derive(Debug, Clone)]
+pub struct MimicFilter {
+    decay: f64,
+    gain: f64,
+    previous: Option<f64>,
+}
+
+impl MimicFilter {
+    /// Create a mimic filter
+    ///
+    /// # Arguments
+    /// * `time_constant` - Time constant of the DC offset in milliseconds (0 = pass through)
+    /// * `samples_per_cycle` - Number of samples per cycle (e.g., 80)
+    /// * `nominal_frequency` - Nominal system frequency in Hz
+    pub fn new(time_constant: f64, samples_per_cycle: usize, nominal_frequency: f64) -> Self {
+        if time_constant <= 0.0 || samples_per_cycle == 0 || nominal_frequency <= 0.0 {
+            return Self { decay: 0.0, gain: 1.0, previous: None };
+        }
+
+        let sample_period_ms = 1000.0 / (samples_per_cycle as f64 * nominal_frequency);
+        let decay = (-sample_period_ms / time_constant).exp();
+
+        // |1 − a·e^(−jωTs)| at the fundamental
+        let step = 2.0 * PI / samples_per_cycle as f64;
+        let re = 1.0 - decay * step.cos();
+        let im = decay * step.sin();
+        Self {
+            decay,
+            gain: 1.0 / (re * re + im * im).sqrt(),
+            previous: None,
+        }
+    }
+
+    /// Filter one sample
+    pub fn add_sample(&mut self, sample: f64) -> f64 {
+        let previous = self.previous.unwrap_or(sample);
+        self.previous = Some(sample);
+        self.gain * (sample - self.decay * previous)
+    }
+
+    /// Reset the filter
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// Highest absolute value over a sliding window of samples
+///
+/// With a half-cycle window every sinusoid reaches its peak once per window,
+/// so the output follows the amplitude with at most half a cycle of delay
+/// after the current drops.
+#[derive(Debug, Clone)]
+pub struct PeakDetector {
+    samples: Vec<f64>,
+    current_index: usize,
+}
+
+impl PeakDetector {
+    /// Create a peak detector
+    ///
+    /// # Arguments
+    /// * `window_size` - Number of samples in the window (e.g., 40 for half a cycle at 80 samples/cycle)
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            samples: vec![0.0; window_size.max(1)],
+            current_index: 0,
+        }
+    }
+
+    /// Create a peak detector over half a cycle
+    pub fn half_cycle(samples_per_cycle: usize) -> Self {
+        Self::new(samples_per_cycle / 2)
+    }
+
+    /// Add a new sample and return the peak over the window
+    pub fn add_sample(&mut self, sample: f64) -> f64 {
+        self.samples[self.current_index] = sample.abs();
+        self.current_index = (self.current_index + 1) % self.samples.len();
+        self.peak()
+    }
+
+    /// Get the peak over the window
+    pub fn peak(&self) -> f64 {
+        self.samples.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// Reset the detector
+    pub fn reset(&mut self) {
+        self.samples.fill(0.0);
+        self.current_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fully offset fault current: −cos(ωt) + e^(−t/τ), peak amplitude 1
+    fn offset_current(n: usize, time_constant_ms: f64) -> f64 {
+        let t_ms = n as f64 * 0.25;
+        -(2.0 * PI * n as f64 / 80.0).cos() + (-t_ms / time_constant_ms).exp()
+    }
+
+    #[test]
+    fn test_mimic_filter_removes_dc_offset() {
+        let mut filter = MimicFilter::new(40.0, 80, 50.0);
+        let mut peak: f64 = 0.0;
+        for n in 0..400 {
+            let y = filter.add_sample(offset_current(n, 40.0));
+            peak = peak.max(y.abs());
+        }
+        // Unity gain at the fundamental, DC offset cancelled
+        assert!((peak - 1.0).abs() < 0.01, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_mimic_filter_primed_by_first_sample() {
+        let mut filter = MimicFilter::new(40.0, 80, 50.0);
+        // A signal starting at its peak is not amplified as a step
+        assert!(filter.add_sample(1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_mimic_filter_pass_through() {
+        let mut filter = MimicFilter::new(0.0, 80, 50.0);
+        assert_eq!(filter.add_sample(3.0), 3.0);
+        assert_eq!(filter.add_sample(-2.0), -2.0);
+    }
+
+    #[test]
+    fn test_peak_detector() {
+        let mut detector = PeakDetector::half_cycle(8);
+        assert_eq!(detector.add_sample(-3.0), 3.0);
+        for _ in 0..3 {
+            assert_eq!(detector.add_sample(0.5), 3.0);
+        }
+        // -3.0 has left the four-sample window
+        assert_eq!(detector.add_sample(0.5), 0.5);
+
+        detector.reset();
+        assert_eq!(detector.peak(), 0.0);
+    }
+}
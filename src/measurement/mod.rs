@@ -1,9 +1,19 @@
-/// Measurement module for RMS calculation and scaling
+/// Measurement module for RMS calculation, phasor estimation, symmetrical components and scaling
 pub mod rms;
 pub mod scaling;
+pub mod phasor;
+pub mod frame;
+pub mod dft;
+pub mod instantaneous;
+pub mod sequence;
 
 pub use rms::{calculate_rms, calculate_rms_i32, RmsCalculator};
 pub use scaling::{
     adc_to_primary, adc_to_secondary, secondary_to_primary, 
-    adc_samples_to_primary, adc_samples_to_secondary, CurrentScaler
+    adc_samples_to_primary, adc_samples_to_secondary, CurrentScaler, VoltageScaler
 };
+pub use phasor::Phasor;
+pub use frame::{ChannelMeasurement, MeasurementFrame, MeasuringMode, Phase, Quality};
+pub use dft::{dft_phasor, DftWindow, FourierFilter};
+pub use instantaneous::{MimicFilter, PeakDetector};
+pub use sequence::SequenceComponents;
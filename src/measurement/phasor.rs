@@ -0,0 +1,170 @@
+/// Phasor representation of fundamental frequency quantities
+use std::f64::consts::PI;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// Complex phasor in rectangular form
+///
+/// The magnitude is the RMS value of the sinusoid it represents and the
+/// angle is in radians.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Phasor {
+    /// Real part
+    pub re: f64,
+    /// Imaginary part
+    pub im: f64,
+}
+
+impl Phasor {
+    /// Zero phasor
+    pub const ZERO: Phasor = Phasor { re: 0.0, im: 0.0 };
+
+    /// Create a phasor from rectangular components
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Create a phasor from magnitude and angle (radians)
+    pub fn from_polar(magnitude: f64, angle: f64) -> Self {
+        Self {
+            re: magnitude * angle.cos(),
+            im: magnitude * angle.sin(),
+        }
+    }
+
+    /// Create a phasor from magnitude and angle in degrees
+    pub fn from_polar_deg(magnitude: f64, angle_deg: f64) -> Self {
+        Self::from_polar(magnitude, angle_deg.to_radians())
+    }
+
+    /// Get the magnitude (RMS)
+    pub fn magnitude(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Get the angle in radians (-π..π)
+    pub fn angle(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Get the angle in degrees (-180..180)
+    pub fn angle_deg(&self) -> f64 {
+        self.angle().to_degrees()
+    }
+
+    /// Get the complex conjugate
+    pub fn conj(&self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    /// Rotate the phasor by the given angle in radians
+    pub fn rotate(&self, angle: f64) -> Self {
+        *self * Phasor::from_polar(1.0, angle)
+    }
+
+    /// Scale the phasor by a real factor
+    pub fn scale(&self, factor: f64) -> Self {
+        Self {
+            re: self.re * factor,
+            im: self.im * factor,
+        }
+    }
+}
+
+/// Normalise an angle in radians to the range -π..π
+pub fn normalize_angle(angle: f64) -> f64 {
+    let mut angle = angle % (2.0 * PI);
+    if angle > PI {
+        angle -= 2.0 * PI;
+    } else if angle < -PI {
+        angle += 2.0 * PI;
+    }
+    angle
+}
+
+impl Add for Phasor {
+    type Output = Phasor;
+
+    fn add(self, other: Phasor) -> Phasor {
+        Phasor::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl AddAssign for Phasor {
+    fn add_assign(&mut self, other: Phasor) {
+        self.re += other.re;
+        self.im += other.im;
+    }
+}
+
+impl Sub for Phasor {
+    type Output = Phasor;
+
+    fn sub(self, other: Phasor) -> Phasor {
+        Phasor::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Phasor {
+    type Output = Phasor;
+
+    fn mul(self, other: Phasor) -> Phasor {
+        Phasor::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Mul<f64> for Phasor {
+    type Output = Phasor;
+
+    fn mul(self, factor: f64) -> Phasor {
+        self.scale(factor)
+    }
+}
+
+impl Neg for Phasor {
+    type Output = Phasor;
+
+    fn neg(self) -> Phasor {
+        Phasor::new(-self.re, -self.im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_conversion() {
+        let p = Phasor::from_polar_deg(10.0, 30.0);
+        assert!((p.magnitude() - 10.0).abs() < 1e-9);
+        assert!((p.angle_deg() - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_multiplication_adds_angles() {
+        let a = Phasor::from_polar_deg(2.0, 30.0);
+        let b = Phasor::from_polar_deg(3.0, 60.0);
+        let c = a * b;
+        assert!((c.magnitude() - 6.0).abs() < 1e-9);
+        assert!((c.angle_deg() - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rotate_and_conj() {
+        let p = Phasor::new(1.0, 0.0).rotate(PI / 2.0);
+        assert!(p.re.abs() < 1e-12);
+        assert!((p.im - 1.0).abs() < 1e-12);
+        assert_eq!(Phasor::new(1.0, 2.0).conj(), Phasor::new(1.0, -2.0));
+    }
+
+    #[test]
+    fn test_normalize_angle() {
+        assert!((normalize_angle(3.0 * PI / 2.0) + PI / 2.0).abs() < 1e-12);
+        assert!((normalize_angle(-3.0 * PI / 2.0) - PI / 2.0).abs() < 1e-12);
+    }
+}
@@ -34,12 +34,17 @@ pub fn calculate_rms_i32(samples: &[i32]) -> f64 {
     mean_square.sqrt()
 }
 
-/// RMS calculator that accumulates samples over a window
+/// RMS calculator that accumulates samples over a sliding window
+///
+/// A running sum of squares is kept so that each new sample costs O(1),
+/// independent of the window size. The sum is recalculated from the window
+/// once per window length to remove accumulated floating point error.
 pub struct RmsCalculator {
     samples: Vec<f64>,
     window_size: usize,
     current_index: usize,
     sample_count: usize,
+    sum_of_squares: f64,
 }
 
 impl RmsCalculator {
@@ -53,6 +58,7 @@ impl RmsCalculator {
             window_size,
             current_index: 0,
             sample_count: 0,
+            sum_of_squares: 0.0,
         }
     }
 
@@ -60,15 +66,28 @@ impl RmsCalculator {
     /// 
     /// # Arguments
     /// * `sample` - New sample value
-    pub fn add_sample(&mut self, sample: f64) {
+    /// 
+    /// # Returns
+    /// RMS value over the window including the new sample
+    pub fn add_sample(&mut self, sample: f64) -> f64 {
+        let oldest = self.samples[self.current_index];
+        self.sum_of_squares += sample * sample - oldest * oldest;
+
         self.samples[self.current_index] = sample;
         self.current_index = (self.current_index + 1) % self.window_size;
         self.sample_count += 1;
+
+        if self.current_index == 0 {
+            self.resynchronize();
+        }
+
+        self.calculate()
     }
 
     /// Calculate the current RMS value from accumulated samples
     pub fn calculate(&self) -> f64 {
-        calculate_rms(&self.samples)
+        // Rounding can leave a tiny negative sum after large samples leave the window
+        (self.sum_of_squares.max(0.0) / self.window_size as f64).sqrt()
     }
 
     /// Check if the calculator has received a full window of samples
@@ -86,6 +105,12 @@ impl RmsCalculator {
         self.samples.fill(0.0);
         self.current_index = 0;
         self.sample_count = 0;
+        self.sum_of_squares = 0.0;
+    }
+
+    /// Recalculate the running sum of squares from the window
+    fn resynchronize(&mut self) {
+        self.sum_of_squares = self.samples.iter().map(|&x| x * x).sum();
     }
 }
 
@@ -146,4 +171,57 @@ mod tests {
         let rms = calc.calculate();
         assert!((rms - 1.0 / SQRT_2).abs() < 0.01);
     }
+
+    #[test]
+    fn test_rms_calculator_sliding_window() {
+        let mut calc = RmsCalculator::new(4);
+
+        for _ in 0..4 {
+            calc.add_sample(2.0);
+        }
+        // Replace two of the four samples: sqrt((4 + 4 + 16 + 16) / 4)
+        calc.add_sample(4.0);
+        let rms = calc.add_sample(4.0);
+        assert!((rms - 10.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(rms, calc.calculate());
+    }
+
+    #[test]
+    fn test_rms_calculator_matches_block_calculation() {
+        let mut calc = RmsCalculator::new(80);
+        let samples: Vec<f64> = (0..1000)
+            .map(|i| 100.0 * (2.0 * PI * i as f64 / 80.0).sin() + (i % 7) as f64)
+            .collect();
+
+        for (i, &sample) in samples.iter().enumerate() {
+            let rms = calc.add_sample(sample);
+            if i >= 79 {
+                let expected = calculate_rms(&samples[i - 79..=i]);
+                assert!((rms - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rms_calculator_no_drift_after_large_transient() {
+        let mut calc = RmsCalculator::new(80);
+
+        // A large fault current followed by a small load current
+        for _ in 0..80 {
+            calc.add_sample(1.0e6);
+        }
+        for _ in 0..80 * 100 {
+            calc.add_sample(1.0e-3);
+        }
+        assert!((calc.calculate() - 1.0e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rms_calculator_reset() {
+        let mut calc = RmsCalculator::new(80);
+        calc.add_sample(10.0);
+        calc.reset();
+        assert_eq!(calc.calculate(), 0.0);
+        assert!(!calc.is_full());
+    }
 }
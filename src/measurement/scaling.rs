@@ -1,5 +1,5 @@
-/// Scaling functions for CT ratio and ADC conversion
-use crate::config::{AdcConfig, CtConfig};
+/// Scaling functions for CT/VT ratio and ADC conversion
+use crate::config::{AdcConfig, CtConfig, VtConfig};
 
 /// Convert raw ADC value to secondary current (Amperes)
 /// 
@@ -110,6 +110,37 @@ impl CurrentScaler {
     }
 }
 
+/// Scaler for voltage channels: ADC counts to primary volts
+pub struct VoltageScaler {
+    adc_config: AdcConfig,
+    vt_config: VtConfig,
+}
+
+impl VoltageScaler {
+    /// Create a new voltage scaler
+    pub fn new(adc_config: AdcConfig, vt_config: VtConfig) -> Self {
+        Self {
+            adc_config,
+            vt_config,
+        }
+    }
+
+    /// Scale raw ADC value to primary voltage
+    pub fn scale_to_primary(&self, adc_value: i32) -> f64 {
+        adc_to_secondary(adc_value, &self.adc_config) * self.vt_config.ratio()
+    }
+
+    /// Get the ADC configuration
+    pub fn adc_config(&self) -> &AdcConfig {
+        &self.adc_config
+    }
+
+    /// Get the VT configuration
+    pub fn vt_config(&self) -> &VtConfig {
+        &self.vt_config
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +227,12 @@ mod tests {
         
         assert_eq!(primaries, vec![400.0, 800.0, 1200.0]);
     }
+
+    #[test]
+    fn test_voltage_scaler() {
+        let scaler = VoltageScaler::new(AdcConfig::voltage(), VtConfig::default());
+        // 5774 counts = 57.74 V secondary = 11547 V primary on a 20000/100 V VT
+        let primary = scaler.scale_to_primary(5774);
+        assert!((primary - 11548.0).abs() < 1e-6);
+    }
 }
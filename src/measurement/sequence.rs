@@ -0,0 +1,88 @@
+/// Symmetrical components (zero, positive and negative sequence) of three-phase phasors
+use super::frame::Quality;
+use super::phasor::Phasor;
+use std::f64::consts::PI;
+
+/// Zero, positive and negative sequence phasors of a three-phase quantity
+///
+/// The components are the per-phase values referred to phase A, so a
+/// balanced system has `positive` equal to the phase A phasor and the other
+/// two components zero. Multiply by 3 for the residual quantities 3I0/3V0
+/// and 3I2/3V2 used by directional and earth-fault elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SequenceComponents {
+    /// Zero sequence phasor X0
+    pub zero: Phasor,
+    /// Positive sequence phasor X1
+    pub positive: Phasor,
+    /// Negative sequence phasor X2
+    pub negative: Phasor,
+    /// Quality of the worst phase channel
+    pub quality: Quality,
+}
+
+impl SequenceComponents {
+    /// Calculate the symmetrical components of phase A, B and C phasors
+    ///
+    /// X0 = (A + B + C) / 3, X1 = (A + a·B + a²·C) / 3 and
+    /// X2 = (A + a²·B + a·C) / 3 with a = 1∠120°. The quality is good.
+    pub fn from_phasors(phases: [Phasor; 3]) -> Self {
+        let a = 2.0 * PI / 3.0;
+        let [pa, pb, pc] = phases;
+        Self {
+            zero: (pa + pb + pc).scale(1.0 / 3.0),
+            positive: (pa + pb.rotate(a) + pc.rotate(2.0 * a)).scale(1.0 / 3.0),
+            negative: (pa + pb.rotate(2.0 * a) + pc.rotate(a)).scale(1.0 / 3.0),
+            quality: Quality::GOOD,
+        }
+    }
+
+    /// Get the ratio of negative to positive sequence magnitude (X2/X1)
+    ///
+    /// # Returns
+    /// The unbalance ratio, or 0.0 without positive sequence
+    pub fn unbalance(&self) -> f64 {
+        let positive = self.positive.magnitude();
+        if positive > 0.0 {
+            self.negative.magnitude() / positive
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phasors(magnitudes: [f64; 3], angles: [f64; 3]) -> [Phasor; 3] {
+        std::array::from_fn(|i| Phasor::from_polar_deg(magnitudes[i], angles[i]))
+    }
+
+    #[test]
+    fn test_balanced_positive_sequence() {
+        let components = SequenceComponents::from_phasors(phasors([100.0; 3], [30.0, -90.0, 150.0]));
+        assert!((components.positive.magnitude() - 100.0).abs() < 1e-9);
+        assert!((components.positive.angle_deg() - 30.0).abs() < 1e-9);
+        assert!(components.negative.magnitude() < 1e-9);
+        assert!(components.zero.magnitude() < 1e-9);
+        assert!(components.unbalance() < 1e-12);
+    }
+
+    #[test]
+    fn test_negative_and_zero_sequence() {
+        // Reversed phase sequence is pure negative sequence
+        let components = SequenceComponents::from_phasors(phasors([100.0; 3], [0.0, 120.0, -120.0]));
+        assert!((components.negative.magnitude() - 100.0).abs() < 1e-9);
+        assert!(components.positive.magnitude() < 1e-9);
+
+        // Single-phase current: I0 = I1 = I2 = Ia / 3
+        let components = SequenceComponents::from_phasors(phasors([300.0, 0.0, 0.0], [0.0; 3]));
+        for phasor in [components.zero, components.positive, components.negative] {
+            assert!((phasor.magnitude() - 100.0).abs() < 1e-9);
+            assert!(phasor.angle_deg().abs() < 1e-9);
+        }
+        assert!((components.unbalance() - 1.0).abs() < 1e-9);
+        assert_eq!(SequenceComponents::default().unbalance(), 0.0);
+    }
+}
@@ -0,0 +1,272 @@
+/// Latency histograms for transfer time and processing time measurements
+use std::fmt;
+
+/// Transfer time limit of IEC 61850-5 performance class TT6 (trips) in microseconds
+pub const TT6_TRANSFER_TIME_US: u64 = 3_000;
+
+/// Histogram of latencies with fixed-width buckets
+///
+/// Buckets are allocated once, so recording never allocates. Values beyond
+/// the last bucket are counted in an overflow bucket; minimum, maximum and
+/// mean are exact, percentiles are resolved to the bucket width.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    bucket_width: u64,
+    buckets: Vec<u64>,
+    overflow: u64,
+    count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram
+    ///
+    /// # Arguments
+    /// * `bucket_width` - Width of one bucket in microseconds
+    /// * `bucket_count` - Number of buckets; the range is `bucket_width × bucket_count`
+    pub fn new(bucket_width: u64, bucket_count: usize) -> Self {
+        Self {
+            bucket_width: bucket_width.max(1),
+            buckets: vec![0; bucket_count.max(1)],
+            overflow: 0,
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+
+    /// Record one latency
+    ///
+    /// # Arguments
+    /// * `value` - Latency in microseconds
+    pub fn record(&mut self, value: u64) {
+        match self.buckets.get_mut((value / self.bucket_width) as usize) {
+            Some(bucket) => *bucket += 1,
+            None => self.overflow += 1,
+        }
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Number of recorded values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest recorded value, `None` if empty
+    pub fn min(&self) -> Option<u64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Largest recorded value, `None` if empty
+    pub fn max(&self) -> Option<u64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Mean of the recorded values, `None` if empty
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// Value below which the given fraction of recorded values fall
+    ///
+    /// Returns the upper bound of the bucket holding the percentile, limited
+    /// to the maximum, so the result never underestimates the latency.
+    ///
+    /// # Arguments
+    /// * `fraction` - Percentile as a fraction (e.g. 0.99)
+    pub fn percentile(&self, fraction: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((fraction.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= rank {
+                return Some(((index as u64 + 1) * self.bucket_width - 1).min(self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// Number of recorded values above a limit
+    ///
+    /// # Arguments
+    /// * `limit` - Limit in microseconds; values in the bucket holding the limit are not counted
+    pub fn count_above(&self, limit: u64) -> u64 {
+        let first = (limit / self.bucket_width + 1) as usize;
+        self.buckets.iter().skip(first).sum::<u64>() + self.overflow
+    }
+
+    /// Clear all recorded values
+    pub fn reset(&mut self) {
+        self.buckets.fill(0);
+        self.overflow = 0;
+        self.count = 0;
+        self.sum = 0;
+        self.min = u64::MAX;
+        self.max = 0;
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.count == 0 {
+            return write!(f, "n=0");
+        }
+        write!(
+            f,
+            "n={} min={} p50={} p99={} p99.9={} max={} mean={:.1} µs",
+            self.count,
+            self.min,
+            self.percentile(0.5).unwrap_or(0),
+            self.percentile(0.99).unwrap_or(0),
+            self.percentile(0.999).unwrap_or(0),
+            self.max,
+            self.mean().unwrap_or(0.0)
+        )
+    }
+}
+
+/// Timing measurements of the protection engine
+#[derive(Debug, Clone)]
+pub struct EngineMetrics {
+    /// Receive time of the SV sample to completion of the GOOSE send, per state change
+    pub transfer_time: LatencyHistogram,
+    /// Processing time of one sample (measurement and protection functions)
+    pub processing_time: LatencyHistogram,
+    tt6_violations: u64,
+    skipped_transfer_times: u64,
+}
+
+impl EngineMetrics {
+    /// Create empty histograms (10 µs buckets up to 20 ms, 1 µs buckets up to 1 ms)
+    pub fn new() -> Self {
+        Self {
+            transfer_time: LatencyHistogram::new(10, 2_000),
+            processing_time: LatencyHistogram::new(1, 1_000),
+            tt6_violations: 0,
+            skipped_transfer_times: 0,
+        }
+    }
+
+    /// Record the transfer time of one state change
+    ///
+    /// # Arguments
+    /// * `value` - Transfer time in microseconds
+    pub fn record_transfer_time(&mut self, value: u64) {
+        self.transfer_time.record(value);
+        if value > TT6_TRANSFER_TIME_US {
+            self.tt6_violations += 1;
+        }
+    }
+
+    /// Count a state change whose transfer time could not be measured
+    pub fn skip_transfer_time(&mut self) {
+        self.skipped_transfer_times += 1;
+    }
+
+    /// Number of state changes whose transfer time could not be measured
+    pub fn skipped_transfer_times(&self) -> u64 {
+        self.skipped_transfer_times
+    }
+
+    /// Number of state changes that exceeded the TT6 transfer time (3 ms)
+    pub fn tt6_violations(&self) -> u64 {
+        self.tt6_violations
+    }
+
+    /// Clear all histograms and counters
+    pub fn reset(&mut self) {
+        self.transfer_time.reset();
+        self.processing_time.reset();
+        self.tt6_violations = 0;
+        self.skipped_transfer_times = 0;
+    }
+}
+
+impl Default for EngineMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_histogram() {
+        let histogram = LatencyHistogram::new(10, 100);
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.min(), None);
+        assert_eq!(histogram.percentile(0.5), None);
+        assert_eq!(histogram.to_string(), "n=0");
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut histogram = LatencyHistogram::new(1, 1_000);
+        for value in 1..=100 {
+            histogram.record(value);
+        }
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.min(), Some(1));
+        assert_eq!(histogram.max(), Some(100));
+        assert_eq!(histogram.mean(), Some(50.5));
+        assert_eq!(histogram.percentile(0.5), Some(50));
+        assert_eq!(histogram.percentile(0.99), Some(99));
+        assert_eq!(histogram.percentile(1.0), Some(100));
+    }
+
+    #[test]
+    fn test_percentile_resolved_to_bucket() {
+        let mut histogram = LatencyHistogram::new(100, 10);
+        histogram.record(120);
+        histogram.record(130);
+        histogram.record(850);
+        // Upper bound of the bucket, never below the actual value
+        assert_eq!(histogram.percentile(0.5), Some(199));
+        assert_eq!(histogram.percentile(1.0), Some(850));
+    }
+
+    #[test]
+    fn test_overflow_and_count_above() {
+        let mut histogram = LatencyHistogram::new(10, 1_000);
+        histogram.record(500);
+        histogram.record(2_999);
+        histogram.record(3_010);
+        histogram.record(50_000);
+        assert_eq!(histogram.max(), Some(50_000));
+        assert_eq!(histogram.percentile(1.0), Some(50_000));
+        assert_eq!(histogram.count_above(TT6_TRANSFER_TIME_US), 2);
+
+        histogram.reset();
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.count_above(0), 0);
+    }
+
+    #[test]
+    fn test_tt6_violations() {
+        let mut metrics = EngineMetrics::new();
+        metrics.record_transfer_time(1_200);
+        metrics.record_transfer_time(3_000);
+        metrics.record_transfer_time(3_001);
+        metrics.record_transfer_time(3_500);
+        // Violations are counted exactly: 3 001 µs and 3 500 µs exceed the limit
+        assert_eq!(metrics.tt6_violations(), 2);
+        assert_eq!(metrics.transfer_time.count(), 4);
+
+        // The histogram resolves 10 µs buckets: 3 001 µs falls in the limit's bucket
+        assert_eq!(metrics.transfer_time.count_above(TT6_TRANSFER_TIME_US), 1);
+
+        metrics.reset();
+        assert_eq!(metrics.tt6_violations(), 0);
+    }
+}
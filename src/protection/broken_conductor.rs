@@ -0,0 +1,168 @@
+/// Broken-conductor detection on the negative to positive sequence current ratio I2/I1
+use super::element::{self, OvercurrentElement};
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::{BrokenConductorConfig, PtocConfig};
+use crate::measurement::MeasurementFrame;
+
+/// Broken-conductor function operating on the unbalance ratio I2/I1
+///
+/// An open conductor on a lightly loaded feeder causes no overcurrent and,
+/// in high-impedance earthed networks, little residual current. The ratio of
+/// negative to positive sequence current of a single open phase lies between
+/// about 0.5 and 1.0 depending on the load impedances, independent of the
+/// load, whereas it stays small under normal load unbalance. The ratio is
+/// compared with `ratio_set` like a definite time stage.
+///
+/// Below `min_current` positive sequence current the ratio is dominated by
+/// measurement errors and the function is blocked. The function is not phase
+/// selective: pickup and trip are reported on all three phases.
+///
+/// Invalid phase currents block the function like a low I1.
+pub struct BrokenConductor {
+    config: BrokenConductorConfig,
+    stage: PtocConfig,
+    element: OvercurrentElement,
+}
+
+impl BrokenConductor {
+    /// Create a new broken-conductor function
+    ///
+    /// # Arguments
+    /// * `config` - Ratio, delay and minimum current settings
+    pub fn new(config: BrokenConductorConfig) -> Self {
+        Self {
+            stage: element::definite_time_stage(config.ratio_set, config.tset, config.enabled, config.dropout_ratio),
+            config,
+            element: OvercurrentElement::new(),
+        }
+    }
+
+    /// Get the trip state
+    pub fn state(&self) -> TripState {
+        self.element.state()
+    }
+
+    /// Get the start flags (Str.phsA/phsB/phsC while picked up or tripped)
+    pub fn start_phases(&self) -> PhaseFlags {
+        let state = self.state();
+        PhaseFlags::all(state.is_pickup() || state.is_tripped())
+    }
+
+    /// Get the trip flags (Op.phsA/phsB/phsC)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        PhaseFlags::all(self.state().is_tripped())
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &BrokenConductorConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    pub fn set_config(&mut self, config: BrokenConductorConfig) {
+        self.stage = element::definite_time_stage(config.ratio_set, config.tset, config.enabled, config.dropout_ratio);
+        self.config = config;
+        if !self.config.enabled {
+            self.reset();
+        }
+    }
+
+    /// Calculate the unbalance ratio of a frame
+    ///
+    /// # Returns
+    /// I2/I1, or `None` if a phase current is invalid or I1 is below `min_current`
+    pub fn ratio(&self, frame: &MeasurementFrame) -> Option<f64> {
+        let sequence = frame.current_sequence();
+        if sequence.quality.is_invalid() || sequence.positive.magnitude() < self.config.min_current {
+            return None;
+        }
+        Some(sequence.unbalance())
+    }
+}
+
+impl ProtectionFunction for BrokenConductor {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let ratio = self.ratio(frame).unwrap_or(0.0);
+        self.element.process(&self.stage, ratio, frame.timestamp)
+    }
+
+    fn reset(&mut self) {
+        self.element.reset();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        self.stage.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Broken conductor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::{ChannelMeasurement, Phase, Phasor, Quality};
+
+    fn config() -> BrokenConductorConfig {
+        BrokenConductorConfig {
+            ratio_set: 0.2,
+            tset: 1000,
+            min_current: 20.0,
+            ..BrokenConductorConfig::default()
+        }
+    }
+
+    /// Load current with phase C open: the load redistributes over A and B
+    fn open_phase(load: f64, timestamp: u64) -> MeasurementFrame {
+        let mut frame = MeasurementFrame::new(timestamp);
+        let current = load * 3.0_f64.sqrt() / 2.0;
+        *frame.current_mut(Phase::A) = ChannelMeasurement::new(current, Phasor::from_polar_deg(current, -30.0));
+        *frame.current_mut(Phase::B) = ChannelMeasurement::new(current, Phasor::from_polar_deg(current, 150.0));
+        frame
+    }
+
+    #[test]
+    fn test_open_phase_detected_at_light_load() {
+        let mut detector = BrokenConductor::new(config());
+        let ratio = detector.ratio(&open_phase(60.0, 0)).unwrap();
+        assert!((ratio - 1.0).abs() < 1e-9);
+
+        assert!(matches!(detector.process(&open_phase(60.0, 0)), ProtectionResult::TripPending(_)));
+        assert_eq!(detector.process(&open_phase(60.0, 1_000_000)), ProtectionResult::Trip);
+        assert!(detector.trip_phases().phs_a && detector.trip_phases().phs_c);
+    }
+
+    #[test]
+    fn test_normal_unbalance_no_trip() {
+        let mut detector = BrokenConductor::new(config());
+        // 10 % load unbalance on phase A
+        let frame = MeasurementFrame::from_phase_currents([330.0, 300.0, 300.0], 0);
+        assert!(detector.ratio(&frame).unwrap() < 0.05);
+        assert_eq!(detector.process(&frame), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_minimum_current_supervision() {
+        let mut detector = BrokenConductor::new(config());
+        // Open phase below minimum load
+        assert_eq!(detector.ratio(&open_phase(10.0, 0)), None);
+        assert_eq!(detector.process(&open_phase(10.0, 0)), ProtectionResult::NoTrip);
+
+        let mut frame = open_phase(60.0, 0);
+        frame.current_mut(Phase::C).quality = Quality::INVALID;
+        assert_eq!(detector.process(&frame), ProtectionResult::NoTrip);
+    }
+}
@@ -0,0 +1,207 @@
+/// Time-current characteristics for overcurrent protection (IEC 60255-151, IEEE C37.112)
+use serde::{Deserialize, Serialize};
+
+/// Upper limit of the current multiple used for inverse-time curves.
+///
+/// IEC 60255-151 only requires the characteristic to be accurate up to 20 × Is;
+/// above that the operate time is held at the 20 × Is value (definite time).
+pub const MAX_CURVE_MULTIPLE: f64 = 20.0;
+
+/// Operating characteristic of a time overcurrent stage
+///
+/// Inverse curves follow the common IEC 60255-151 / IEEE C37.112 form:
+///
+/// `t(G) = TMS * (A / ((G / Gs)^p - 1) + B)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum CurveType {
+    /// Definite time characteristic, delay given by `PtocConfig::tset`
+    #[default]
+    DefiniteTime,
+    /// IEC Standard Inverse (A = 0.14, p = 0.02)
+    IecStandardInverse,
+    /// IEC Very Inverse (A = 13.5, p = 1)
+    IecVeryInverse,
+    /// IEC Extremely Inverse (A = 80, p = 2)
+    IecExtremelyInverse,
+    /// IEC Long-Time Inverse (A = 120, p = 1)
+    IecLongTimeInverse,
+    /// IEEE Moderately Inverse (A = 0.0515, B = 0.114, p = 0.02)
+    IeeeModeratelyInverse,
+    /// IEEE Very Inverse (A = 19.61, B = 0.491, p = 2)
+    IeeeVeryInverse,
+    /// IEEE Extremely Inverse (A = 28.2, B = 0.1217, p = 2)
+    IeeeExtremelyInverse,
+    /// User-defined inverse curve constants
+    UserDefined {
+        /// Constant A in seconds
+        a: f64,
+        /// Constant B in seconds
+        b: f64,
+        /// Exponent p
+        p: f64,
+    },
+}
+
+/// Reset behaviour of the time delay after the current drops out
+///
+/// With a timer memory (definite time or inverse reset) intermittent faults
+/// keep accumulating operate time like an electromechanical disk relay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ResetMode {
+    /// Operate timer is cleared as soon as the current drops out
+    #[default]
+    Instantaneous,
+    /// Operate timer is held for the reset time, then cleared
+    DefiniteTime,
+    /// Operate timer runs down according to the IEC 60255-151 reset curve
+    /// `tr(G) = TMS * tr / (1 - (G / Gs)^2)` (disk emulation)
+    Inverse,
+}
+
+impl CurveType {
+    /// Get the (A, B, p) constants of an inverse curve
+    ///
+    /// Returns `None` for the definite time characteristic.
+    pub fn constants(&self) -> Option<(f64, f64, f64)> {
+        match *self {
+            CurveType::DefiniteTime => None,
+            CurveType::IecStandardInverse => Some((0.14, 0.0, 0.02)),
+            CurveType::IecVeryInverse => Some((13.5, 0.0, 1.0)),
+            CurveType::IecExtremelyInverse => Some((80.0, 0.0, 2.0)),
+            CurveType::IecLongTimeInverse => Some((120.0, 0.0, 1.0)),
+            CurveType::IeeeModeratelyInverse => Some((0.0515, 0.114, 0.02)),
+            CurveType::IeeeVeryInverse => Some((19.61, 0.491, 2.0)),
+            CurveType::IeeeExtremelyInverse => Some((28.2, 0.1217, 2.0)),
+            CurveType::UserDefined { a, b, p } => Some((a, b, p)),
+        }
+    }
+
+    /// Get the reset time constant tr in seconds, if the curve defines one
+    ///
+    /// IEC 60255-151 only specifies tr for the IEEE curves.
+    pub fn reset_constant(&self) -> Option<f64> {
+        match *self {
+            CurveType::IeeeModeratelyInverse => Some(4.85),
+            CurveType::IeeeVeryInverse => Some(21.6),
+            CurveType::IeeeExtremelyInverse => Some(29.1),
+            _ => None,
+        }
+    }
+
+    /// Check if this is an inverse-time characteristic
+    pub fn is_inverse(&self) -> bool {
+        !matches!(self, CurveType::DefiniteTime)
+    }
+
+    /// Calculate the operate time in seconds for an inverse curve
+    ///
+    /// # Arguments
+    /// * `multiple` - Measured current divided by the pickup setting (G / Gs)
+    /// * `tms` - Time multiplier setting
+    ///
+    /// # Returns
+    /// Operate time in seconds, or `None` if the current is at or below pickup
+    /// or the curve is definite time
+    pub fn operate_time(&self, multiple: f64, tms: f64) -> Option<f64> {
+        let (a, b, p) = self.constants()?;
+        if multiple <= 1.0 {
+            return None;
+        }
+
+        let multiple = multiple.min(MAX_CURVE_MULTIPLE);
+        let denominator = multiple.powf(p) - 1.0;
+        if denominator <= 0.0 {
+            return None;
+        }
+
+        Some(tms * (a / denominator + b))
+    }
+}
+
+/// Calculate the time for a full inverse reset in seconds
+///
+/// # Arguments
+/// * `multiple` - Measured current divided by the pickup setting (G / Gs)
+/// * `tms` - Time multiplier setting
+/// * `tr` - Reset time constant in seconds (reset time at zero current with TMS = 1)
+///
+/// # Returns
+/// Time to reset from full operate progress to zero, or `None` if the
+/// current is at or above pickup
+pub fn inverse_reset_time(multiple: f64, tms: f64, tr: f64) -> Option<f64> {
+    if multiple >= 1.0 {
+        return None;
+    }
+
+    Some(tms * tr / (1.0 - multiple.max(0.0).powi(2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_iec_curves_at_10x() {
+        assert_close(CurveType::IecStandardInverse.operate_time(10.0, 1.0).unwrap(), 2.971);
+        assert_close(CurveType::IecVeryInverse.operate_time(10.0, 1.0).unwrap(), 1.5);
+        assert_close(CurveType::IecExtremelyInverse.operate_time(10.0, 1.0).unwrap(), 0.808);
+        assert_close(CurveType::IecLongTimeInverse.operate_time(10.0, 1.0).unwrap(), 13.333);
+    }
+
+    #[test]
+    fn test_ieee_curves_at_10x() {
+        assert_close(CurveType::IeeeModeratelyInverse.operate_time(10.0, 1.0).unwrap(), 1.207);
+        assert_close(CurveType::IeeeVeryInverse.operate_time(10.0, 1.0).unwrap(), 0.689);
+        assert_close(CurveType::IeeeExtremelyInverse.operate_time(10.0, 1.0).unwrap(), 0.407);
+    }
+
+    #[test]
+    fn test_time_multiplier_scales_linearly() {
+        let t1 = CurveType::IecVeryInverse.operate_time(5.0, 1.0).unwrap();
+        let t02 = CurveType::IecVeryInverse.operate_time(5.0, 0.2).unwrap();
+        assert_close(t02, t1 * 0.2);
+    }
+
+    #[test]
+    fn test_user_defined_curve() {
+        let curve = CurveType::UserDefined { a: 13.5, b: 0.0, p: 1.0 };
+        assert_eq!(
+            curve.operate_time(4.0, 0.5),
+            CurveType::IecVeryInverse.operate_time(4.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_no_operation_at_or_below_pickup() {
+        assert_eq!(CurveType::IecStandardInverse.operate_time(1.0, 1.0), None);
+        assert_eq!(CurveType::IecStandardInverse.operate_time(0.5, 1.0), None);
+        assert_eq!(CurveType::DefiniteTime.operate_time(10.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_inverse_reset_time() {
+        // At zero current the reset time is TMS * tr
+        assert_close(inverse_reset_time(0.0, 0.5, 21.6).unwrap(), 10.8);
+        // At half pickup: tr / (1 - 0.25)
+        assert_close(inverse_reset_time(0.5, 1.0, 21.6).unwrap(), 28.8);
+        assert_eq!(inverse_reset_time(1.0, 1.0, 21.6), None);
+        assert_eq!(CurveType::IeeeVeryInverse.reset_constant(), Some(21.6));
+        assert_eq!(CurveType::IecVeryInverse.reset_constant(), None);
+    }
+
+    #[test]
+    fn test_curve_limited_above_20x() {
+        let t20 = CurveType::IecExtremelyInverse.operate_time(20.0, 1.0).unwrap();
+        let t50 = CurveType::IecExtremelyInverse.operate_time(50.0, 1.0).unwrap();
+        assert_eq!(t20, t50);
+    }
+}
@@ -0,0 +1,257 @@
+/// Time overcurrent measuring element shared by overcurrent protection functions
+use super::curves::{inverse_reset_time, ResetMode};
+use super::traits::{ProtectionResult, TripState};
+use crate::config::PtocConfig;
+use std::time::Duration;
+
+/// Operate progress at which the time delay is considered expired.
+///
+/// Slightly below 1.0 to absorb floating point error when integrating.
+const OPERATE_THRESHOLD: f64 = 1.0 - 1e-9;
+
+/// Time overcurrent measuring element for one scalar quantity
+///
+/// The time delay is implemented as an integrator: on every call the elapsed
+/// time is divided by the operate time for the present current and added to
+/// the operate progress. The element trips when the progress reaches 1.0, so
+/// inverse curves follow a varying fault current correctly.
+///
+/// After the current drops below `dropout_ratio * iset` the progress is
+/// cleared, held or run down according to the configured `ResetMode`. A trip
+/// is held until the current falls below the same threshold.
+///
+/// The settings are passed in on every call so that one `PtocConfig` can
+/// drive several elements (e.g. one per phase).
+#[derive(Debug, Clone, Default)]
+pub struct OvercurrentElement {
+    state: TripState,
+    last_timestamp: Option<u64>,
+    dropout_time: Option<u64>,
+    progress: f64,
+    /// Operate time at the last current above pickup (seconds)
+    last_operate_time: f64,
+}
+
+impl OvercurrentElement {
+    /// Create a new element in the idle state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current trip state
+    pub fn state(&self) -> TripState {
+        self.state
+    }
+
+    /// Get the operate progress of the time delay (0.0 = just picked up, 1.0 = trip)
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// Evaluate the element for a new current value
+    ///
+    /// # Arguments
+    /// * `config` - Pickup, delay, curve and reset settings
+    /// * `current` - Operating quantity in primary amperes
+    /// * `timestamp` - Timestamp of the measurement (microseconds)
+    pub fn process(&mut self, config: &PtocConfig, current: f64, timestamp: u64) -> ProtectionResult {
+        match self.state {
+            TripState::Idle => {
+                if is_overcurrent(config, current) {
+                    // Current exceeded pickup, start timing
+                    self.state = TripState::Pickup;
+                    self.progress = 0.0;
+                    self.last_timestamp = Some(timestamp);
+                    self.evaluate_pickup(config, current, timestamp)
+                } else {
+                    ProtectionResult::NoTrip
+                }
+            }
+            TripState::Pickup => {
+                if is_dropout(config, current) {
+                    // Current dropped below dropout threshold
+                    if config.reset_mode == ResetMode::Instantaneous {
+                        self.reset();
+                    } else {
+                        self.state = TripState::Resetting;
+                        self.dropout_time = Some(timestamp);
+                        self.last_timestamp = Some(timestamp);
+                    }
+                    ProtectionResult::NoTrip
+                } else {
+                    self.evaluate_pickup(config, current, timestamp)
+                }
+            }
+            TripState::Resetting => {
+                if is_overcurrent(config, current) {
+                    // Fault re-struck, continue timing from the held progress
+                    self.state = TripState::Pickup;
+                    self.dropout_time = None;
+                    self.last_timestamp = Some(timestamp);
+                    self.evaluate_pickup(config, current, timestamp)
+                } else {
+                    if self.integrate_reset(config, current, timestamp) {
+                        self.reset();
+                    }
+                    ProtectionResult::NoTrip
+                }
+            }
+            TripState::Trip => {
+                if is_dropout(config, current) {
+                    // Fault cleared, drop the trip out
+                    self.reset();
+                    ProtectionResult::NoTrip
+                } else {
+                    ProtectionResult::Trip
+                }
+            }
+        }
+    }
+
+    /// Reset the element to idle and clear the timer memory
+    pub fn reset(&mut self) {
+        self.state = TripState::Idle;
+        self.last_timestamp = None;
+        self.dropout_time = None;
+        self.progress = 0.0;
+        self.last_operate_time = 0.0;
+    }
+
+    /// Time elapsed since the last evaluation in seconds
+    fn elapsed_secs(&mut self, timestamp: u64) -> f64 {
+        // Convert microseconds to seconds
+        let elapsed = self
+            .last_timestamp
+            .map(|last| timestamp.saturating_sub(last) as f64 / 1_000_000.0)
+            .unwrap_or(0.0);
+        self.last_timestamp = Some(timestamp);
+        elapsed
+    }
+
+    /// Advance the operate integrator to `timestamp` using the present current
+    fn integrate(&mut self, config: &PtocConfig, current: f64, timestamp: u64) {
+        let elapsed = self.elapsed_secs(timestamp);
+
+        if let Some(operate_time) = operate_time_secs(config, current) {
+            self.last_operate_time = operate_time;
+            if operate_time > 0.0 {
+                self.progress += elapsed / operate_time;
+            } else {
+                self.progress = 1.0;
+            }
+        }
+    }
+
+    /// Run down the operate integrator after dropout
+    ///
+    /// Returns `true` once the timer memory is fully reset.
+    fn integrate_reset(&mut self, config: &PtocConfig, current: f64, timestamp: u64) -> bool {
+        match config.reset_mode {
+            ResetMode::Instantaneous => true,
+            ResetMode::DefiniteTime => {
+                self.last_timestamp = Some(timestamp);
+                let dropout = self.dropout_time.unwrap_or(timestamp);
+                timestamp.saturating_sub(dropout) / 1000 >= config.reset_time
+            }
+            ResetMode::Inverse => {
+                let elapsed = self.elapsed_secs(timestamp);
+                match inverse_reset_time(current / config.iset, config.tms, reset_constant(config)) {
+                    Some(reset_time) if reset_time > 0.0 => {
+                        self.progress -= elapsed / reset_time;
+                    }
+                    _ => self.progress = 0.0,
+                }
+                self.progress <= 0.0
+            }
+        }
+    }
+
+    /// Advance the time delay while picked up and trip once it has expired
+    fn evaluate_pickup(&mut self, config: &PtocConfig, current: f64, timestamp: u64) -> ProtectionResult {
+        self.integrate(config, current, timestamp);
+        if self.progress >= OPERATE_THRESHOLD {
+            // Time delay expired, issue trip
+            self.state = TripState::Trip;
+            ProtectionResult::Trip
+        } else {
+            // Still waiting for time delay
+            ProtectionResult::TripPending(self.remaining_time(config, current))
+        }
+    }
+
+    /// Estimate the remaining time to trip if the current stays constant
+    ///
+    /// While an inverse curve holds between the dropout and pickup thresholds
+    /// the estimate for the last current above pickup is kept.
+    fn remaining_time(&self, config: &PtocConfig, current: f64) -> Duration {
+        let operate_time = operate_time_secs(config, current).unwrap_or(self.last_operate_time);
+        Duration::from_secs_f64(operate_time * (1.0 - self.progress).max(0.0))
+    }
+}
+
+/// Calculate the operate time for a constant current
+///
+/// # Arguments
+/// * `config` - Pickup, delay and curve settings
+/// * `current` - Operating quantity in primary amperes
+///
+/// # Returns
+/// Operate time according to the configured characteristic, or `None`
+/// if the current does not exceed the pickup setting
+pub fn operate_time(config: &PtocConfig, current: f64) -> Option<Duration> {
+    if !is_overcurrent(config, current) {
+        return None;
+    }
+
+    operate_time_secs(config, current).map(Duration::from_secs_f64)
+}
+
+/// Definite time setting driving a measuring element
+///
+/// Functions with their own settings (PIOC, PSDE, broken conductor) run the
+/// element with this setting; all other `PtocConfig` fields keep their defaults.
+///
+/// # Arguments
+/// * `iset` - Pickup setting of the operating quantity
+/// * `tset` - Time delay in milliseconds
+/// * `enabled` - Enable or disable the stage
+/// * `dropout_ratio` - Dropout to pickup ratio
+pub fn definite_time_stage(iset: f64, tset: u64, enabled: bool, dropout_ratio: f64) -> PtocConfig {
+    PtocConfig {
+        iset,
+        tset,
+        enabled,
+        dropout_ratio,
+        ..PtocConfig::default()
+    }
+}
+
+/// Check if current exceeds pickup setting
+fn is_overcurrent(config: &PtocConfig, current: f64) -> bool {
+    current > config.iset
+}
+
+/// Check if current has fallen to or below the dropout threshold
+fn is_dropout(config: &PtocConfig, current: f64) -> bool {
+    current <= config.iset * config.dropout_ratio
+}
+
+/// Operate time in seconds while picked up
+///
+/// Between the dropout and pickup thresholds a definite time delay keeps
+/// running, while an inverse curve holds (`None`).
+fn operate_time_secs(config: &PtocConfig, current: f64) -> Option<f64> {
+    if config.curve.is_inverse() {
+        config.curve.operate_time(current / config.iset, config.tms)
+    } else {
+        Some(config.tset as f64 / 1000.0)
+    }
+}
+
+/// Reset constant tr in seconds for the inverse reset characteristic
+fn reset_constant(config: &PtocConfig) -> f64 {
+    config
+        .curve
+        .reset_constant()
+        .unwrap_or(config.reset_time as f64 / 1000.0)
+}
@@ -1,6 +1,26 @@
 /// Protection functions module
 pub mod traits;
 pub mod ptoc;
+pub mod element;
+pub mod curves;
+pub mod multi_stage;
+pub mod pioc;
+pub mod rdir;
+pub mod residual;
+pub mod psde;
+pub mod ptef;
+pub mod negative_sequence;
+pub mod broken_conductor;
 
-pub use traits::{ProtectionFunction, ProtectionResult, TripState};
+pub use traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
 pub use ptoc::Ptoc;
+pub use element::OvercurrentElement;
+pub use curves::{CurveType, ResetMode};
+pub use multi_stage::MultiStagePtoc;
+pub use pioc::{Pioc, PiocMeasuringMode};
+pub use rdir::{Direction, DirectionMode, Directions, EarthPolarisation, Rdir};
+pub use residual::{ResidualPtoc, ResidualSource};
+pub use psde::{Psde, SefCriterion};
+pub use ptef::Ptef;
+pub use negative_sequence::NegativeSequencePtoc;
+pub use broken_conductor::BrokenConductor;
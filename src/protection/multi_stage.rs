@@ -0,0 +1,262 @@
+/// Multi-stage time overcurrent protection (I>, I>>, I>>>)
+use super::ptoc::Ptoc;
+use super::rdir::Directions;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult};
+use crate::config::PtocConfig;
+use crate::measurement::MeasurementFrame;
+
+/// Staged overcurrent element sharing one measurement input
+///
+/// Each stage is an independent `Ptoc` with its own pickup, delay, curve,
+/// reset behaviour and enable flag. Stages are named after their IEC 61850
+/// logical node instance (PTOC1, PTOC2, ...) in configuration order.
+pub struct MultiStagePtoc {
+    stages: Vec<Ptoc>,
+    names: Vec<String>,
+    results: Vec<ProtectionResult>,
+    enabled: bool,
+}
+
+impl MultiStagePtoc {
+    /// Create a new staged overcurrent element with one stage per configuration
+    pub fn new(configs: Vec<PtocConfig>) -> Self {
+        let names = (1..=configs.len()).map(|i| format!("PTOC{}", i)).collect();
+        let results = vec![ProtectionResult::NoTrip; configs.len()];
+        let stages = configs.into_iter().map(Ptoc::new).collect();
+
+        Self {
+            stages,
+            names,
+            results,
+            enabled: true,
+        }
+    }
+
+    /// Get all stages
+    pub fn stages(&self) -> &[Ptoc] {
+        &self.stages
+    }
+
+    /// Get a stage by index
+    pub fn stage(&self, index: usize) -> Option<&Ptoc> {
+        self.stages.get(index)
+    }
+
+    /// Get a mutable stage by index (e.g. to change its settings)
+    pub fn stage_mut(&mut self, index: usize) -> Option<&mut Ptoc> {
+        self.stages.get_mut(index)
+    }
+
+    /// Get the logical node name of a stage (e.g. "PTOC2")
+    pub fn stage_name(&self, index: usize) -> Option<&str> {
+        self.names.get(index).map(String::as_str)
+    }
+
+    /// Get the number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Check if there are no stages configured
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Get the per-stage results of the last evaluation
+    pub fn results(&self) -> &[ProtectionResult] {
+        &self.results
+    }
+
+    /// Get the indices of the stages that are currently tripped
+    pub fn tripped_stages(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stages
+            .iter()
+            .enumerate()
+            .filter(|(_, stage)| stage.state().is_tripped())
+            .map(|(index, _)| index)
+    }
+
+    /// Get the phases started by any stage (Str.phsA/phsB/phsC)
+    pub fn start_phases(&self) -> PhaseFlags {
+        self.stages
+            .iter()
+            .fold(PhaseFlags::default(), |flags, stage| flags.union(stage.start_phases()))
+    }
+
+    /// Get the phases tripped by any stage (Op.phsA/phsB/phsC)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        self.stages
+            .iter()
+            .fold(PhaseFlags::default(), |flags, stage| flags.union(stage.trip_phases()))
+    }
+
+    /// Set the fault directions used by directional stages for the next evaluations
+    pub fn set_directions(&mut self, directions: Directions) {
+        for stage in &mut self.stages {
+            stage.set_directions(directions);
+        }
+    }
+
+    /// Evaluate all stages and return the per-stage results
+    ///
+    /// # Arguments
+    /// * `frame` - Measurement frame shared by all stages
+    pub fn process_stages(&mut self, frame: &MeasurementFrame) -> &[ProtectionResult] {
+        for (stage, result) in self.stages.iter_mut().zip(self.results.iter_mut()) {
+            *result = if self.enabled {
+                stage.process(frame)
+            } else {
+                ProtectionResult::Disabled
+            };
+        }
+        &self.results
+    }
+
+    /// Combine the per-stage results into one result
+    ///
+    /// Any tripped stage trips the element; otherwise the shortest pending
+    /// delay is reported.
+    fn combined_result(&self) -> ProtectionResult {
+        if !self.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        self.results
+            .iter()
+            .fold(ProtectionResult::Disabled, |combined, &result| combined.combine(result))
+    }
+}
+
+impl ProtectionFunction for MultiStagePtoc {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        self.process_stages(frame);
+        self.combined_result()
+    }
+
+    fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+        self.results.fill(ProtectionResult::NoTrip);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PTOC"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protection::{CurveType, TripState};
+    use std::time::Duration;
+
+    /// Low-set inverse stage plus high-set instantaneous stage
+    fn feeder_stages() -> MultiStagePtoc {
+        MultiStagePtoc::new(vec![
+            PtocConfig {
+                iset: 100.0,
+                curve: CurveType::IecStandardInverse,
+                tms: 0.1,
+                ..PtocConfig::default()
+            },
+            PtocConfig {
+                iset: 1000.0,
+                tset: 0,
+                ..PtocConfig::default()
+            },
+        ])
+    }
+
+    #[test]
+    fn test_stage_names() {
+        let stages = feeder_stages();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages.stage_name(0), Some("PTOC1"));
+        assert_eq!(stages.stage_name(1), Some("PTOC2"));
+        assert_eq!(stages.stage_name(2), None);
+    }
+
+    #[test]
+    fn test_low_set_stage_only() {
+        let mut stages = feeder_stages();
+
+        let results = stages.process_stages(&MeasurementFrame::balanced(300.0, 0));
+        assert!(matches!(results[0], ProtectionResult::TripPending(_)));
+        assert_eq!(results[1], ProtectionResult::NoTrip);
+        assert!(matches!(
+            stages.process(&MeasurementFrame::balanced(300.0, 1_000)),
+            ProtectionResult::TripPending(_)
+        ));
+    }
+
+    #[test]
+    fn test_high_set_stage_trips_instantaneously() {
+        let mut stages = feeder_stages();
+
+        assert_eq!(
+            stages.process(&MeasurementFrame::balanced(2000.0, 0)),
+            ProtectionResult::Trip
+        );
+        assert_eq!(stages.stage(0).unwrap().state(), TripState::Pickup);
+        assert_eq!(stages.stage(1).unwrap().state(), TripState::Trip);
+        assert_eq!(stages.tripped_stages().collect::<Vec<_>>(), vec![1]);
+        assert!(stages.trip_phases().general());
+        assert!(stages.start_phases().phs_a);
+    }
+
+    #[test]
+    fn test_disabled_stage_is_skipped() {
+        let mut stages = feeder_stages();
+        stages.stage_mut(1).unwrap().set_enabled(false);
+
+        stages.process(&MeasurementFrame::balanced(2000.0, 0));
+        assert_eq!(stages.results()[1], ProtectionResult::Disabled);
+        assert!(matches!(stages.results()[0], ProtectionResult::TripPending(_)));
+    }
+
+    #[test]
+    fn test_combined_result_reports_shortest_delay() {
+        let mut stages = MultiStagePtoc::new(vec![
+            PtocConfig {
+                iset: 100.0,
+                tset: 500,
+                ..PtocConfig::default()
+            },
+            PtocConfig {
+                iset: 200.0,
+                tset: 50,
+                ..PtocConfig::default()
+            },
+        ]);
+
+        assert_eq!(
+            stages.process(&MeasurementFrame::balanced(300.0, 0)),
+            ProtectionResult::TripPending(Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn test_element_disable_and_reset() {
+        let mut stages = feeder_stages();
+        stages.process(&MeasurementFrame::balanced(2000.0, 0));
+
+        stages.set_enabled(false);
+        assert_eq!(
+            stages.process(&MeasurementFrame::balanced(2000.0, 1_000)),
+            ProtectionResult::Disabled
+        );
+        assert!(stages.stages().iter().all(|s| s.state().is_idle()));
+    }
+}
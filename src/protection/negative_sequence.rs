@@ -0,0 +1,191 @@
+/// Negative-sequence time overcurrent protection on I2
+use super::element::{self, OvercurrentElement};
+use super::rdir::Directions;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::PtocConfig;
+use crate::measurement::MeasurementFrame;
+use std::time::Duration;
+
+/// Negative-sequence stage (PTOC on I2) with definite or inverse time characteristic
+///
+/// Unbalanced load, open phases and unbalanced faults produce negative
+/// sequence current, which heats rotating machines at twice the system
+/// frequency while the phase currents may stay below the phase overcurrent
+/// settings. The stage uses the `PtocConfig` settings with Iset applied to
+/// the magnitude of the fundamental negative sequence current I2 (not 3I2),
+/// so `measuring_mode` has no effect.
+///
+/// The function is not phase selective: pickup and trip are reported on all
+/// three phases. A directional stage is released by the RDIR negative
+/// sequence element (3I2 against −3V2), independent of the earth polarisation.
+pub struct NegativeSequencePtoc {
+    config: PtocConfig,
+    element: OvercurrentElement,
+    directions: Directions,
+}
+
+impl NegativeSequencePtoc {
+    /// Create a new negative-sequence stage
+    ///
+    /// # Arguments
+    /// * `config` - Pickup, delay, curve, reset and direction settings
+    pub fn new(config: PtocConfig) -> Self {
+        Self {
+            config,
+            element: OvercurrentElement::new(),
+            directions: Directions::default(),
+        }
+    }
+
+    /// Get the trip state
+    pub fn state(&self) -> TripState {
+        self.element.state()
+    }
+
+    /// Get the start flags (Str.phsA/phsB/phsC while picked up or tripped)
+    pub fn start_phases(&self) -> PhaseFlags {
+        let state = self.state();
+        PhaseFlags::all(state.is_pickup() || state.is_tripped())
+    }
+
+    /// Get the trip flags (Op.phsA/phsB/phsC)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        PhaseFlags::all(self.state().is_tripped())
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PtocConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    pub fn set_config(&mut self, config: PtocConfig) {
+        self.config = config;
+        if !self.config.enabled {
+            self.reset();
+        }
+    }
+
+    /// Set the fault directions used by a directional stage for the next evaluations
+    pub fn set_directions(&mut self, directions: Directions) {
+        self.directions = directions;
+    }
+
+    /// Calculate the operate time for a constant negative sequence current
+    ///
+    /// # Returns
+    /// Operate time according to the configured characteristic, or `None`
+    /// if the current does not exceed the pickup setting
+    pub fn operate_time(&self, current: f64) -> Option<Duration> {
+        element::operate_time(&self.config, current)
+    }
+}
+
+impl ProtectionFunction for NegativeSequencePtoc {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let sequence = frame.current_sequence();
+        let released = self.config.direction.releases(self.directions.negative);
+        let current = if sequence.quality.is_invalid() || !released {
+            0.0
+        } else {
+            sequence.negative.magnitude()
+        };
+        self.element.process(&self.config, current, frame.timestamp)
+    }
+
+    fn reset(&mut self) {
+        self.element.reset();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PTOC (negative sequence)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::{Phase, Quality};
+    use crate::protection::{CurveType, Direction, DirectionMode};
+
+    fn stage(iset: f64, tset: u64) -> PtocConfig {
+        PtocConfig {
+            iset,
+            tset,
+            ..PtocConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_unbalance_trip() {
+        let mut ptoc = NegativeSequencePtoc::new(stage(50.0, 200));
+
+        // Heavy balanced load has no negative sequence
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(500.0, 0)), ProtectionResult::NoTrip);
+
+        // Phase C open at 300 A load: I2 = 100 A
+        let open_phase = MeasurementFrame::from_phase_currents([300.0, 300.0, 0.0], 0);
+        assert!(matches!(ptoc.process(&open_phase), ProtectionResult::TripPending(_)));
+        let open_phase = MeasurementFrame::from_phase_currents([300.0, 300.0, 0.0], 200_000);
+        assert_eq!(ptoc.process(&open_phase), ProtectionResult::Trip);
+        assert_eq!(ptoc.trip_phases(), PhaseFlags::all(true));
+    }
+
+    #[test]
+    fn test_inverse_curve() {
+        let ptoc = NegativeSequencePtoc::new(PtocConfig {
+            curve: CurveType::IecVeryInverse,
+            tms: 0.5,
+            ..stage(40.0, 0)
+        });
+        // IEC VI at 4 × Is, TMS 0.5: 13.5 / 3 × 0.5 = 2.25 s
+        let time = ptoc.operate_time(160.0).unwrap();
+        assert!((time.as_secs_f64() - 2.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_directional_uses_negative_sequence_element() {
+        let mut ptoc = NegativeSequencePtoc::new(PtocConfig {
+            direction: DirectionMode::Forward,
+            ..stage(50.0, 0)
+        });
+        let open_phase = MeasurementFrame::from_phase_currents([300.0, 300.0, 0.0], 0);
+
+        // The earth element does not release a negative sequence stage
+        ptoc.set_directions(Directions {
+            earth: Direction::Forward,
+            negative: Direction::Backward,
+            ..Directions::default()
+        });
+        assert_eq!(ptoc.process(&open_phase), ProtectionResult::NoTrip);
+
+        ptoc.set_directions(Directions {
+            negative: Direction::Forward,
+            ..Directions::default()
+        });
+        assert_eq!(ptoc.process(&open_phase), ProtectionResult::Trip);
+    }
+
+    #[test]
+    fn test_invalid_quality() {
+        let mut ptoc = NegativeSequencePtoc::new(stage(50.0, 0));
+        let mut frame = MeasurementFrame::from_phase_currents([300.0, 300.0, 0.0], 0);
+        frame.current_mut(Phase::C).quality = Quality::INVALID;
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+    }
+}
@@ -0,0 +1,318 @@
+/// PIOC (Instantaneous Overcurrent Protection) on sample peaks or half-cycle phasors
+use super::element::{self, OvercurrentElement};
+use super::rdir::Directions;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::{PiocConfig, PtocConfig};
+use crate::measurement::{FourierFilter, MeasurementFrame, MimicFilter, PeakDetector, Phase};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::SQRT_2;
+
+/// Operating quantity of an instantaneous overcurrent stage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PiocMeasuringMode {
+    /// Peak of the sampled values over the last half cycle, divided by √2
+    #[default]
+    Peak,
+    /// Magnitude of the half-cycle Fourier filter
+    HalfCycle,
+}
+
+/// PIOC protection function operating within a fraction of a cycle
+///
+/// Each phase is evaluated on every sample instead of the one-cycle RMS or
+/// phasor used by `Ptoc`. The sampled values first pass a mimic filter that
+/// removes the decaying DC offset of the fault current, so a fully offset
+/// current below Iset does not overreach. The stage starts once the operating
+/// quantity has exceeded Iset on `confirm_samples` consecutive samples and
+/// trips after the optional delay `tset`.
+///
+/// In a directional stage a phase whose direction does not match the
+/// configured mode is evaluated as zero current.
+pub struct Pioc {
+    config: PiocConfig,
+    stage: PtocConfig,
+    samples_per_cycle: usize,
+    nominal_frequency: f64,
+    filters: [MimicFilter; 3],
+    peaks: [PeakDetector; 3],
+    dft: [FourierFilter; 3],
+    confirmed: [usize; 3],
+    elements: [OvercurrentElement; 3],
+    directions: Directions,
+}
+
+impl Pioc {
+    /// Create a new PIOC function
+    ///
+    /// # Arguments
+    /// * `config` - Pickup, delay and measuring settings
+    /// * `samples_per_cycle` - Number of samples per cycle of the SV stream
+    /// * `nominal_frequency` - Nominal system frequency in Hz
+    pub fn new(config: PiocConfig, samples_per_cycle: usize, nominal_frequency: f64) -> Self {
+        let filter = MimicFilter::new(config.dc_time_constant, samples_per_cycle, nominal_frequency);
+        Self {
+            stage: element::definite_time_stage(config.iset, config.tset, config.enabled, config.dropout_ratio),
+            config,
+            samples_per_cycle,
+            nominal_frequency,
+            filters: std::array::from_fn(|_| filter.clone()),
+            peaks: std::array::from_fn(|_| PeakDetector::half_cycle(samples_per_cycle)),
+            dft: std::array::from_fn(|_| FourierFilter::half_cycle(samples_per_cycle)),
+            confirmed: [0; 3],
+            elements: Default::default(),
+            directions: Directions::default(),
+        }
+    }
+
+    /// Set the fault directions used by a directional stage for the next evaluations
+    pub fn set_directions(&mut self, directions: Directions) {
+        self.directions = directions;
+    }
+
+    /// Get the overall trip state (most severe phase state)
+    pub fn state(&self) -> TripState {
+        let states = self.elements.iter().map(OvercurrentElement::state);
+        if states.clone().any(|s| s.is_tripped()) {
+            TripState::Trip
+        } else if states.clone().any(|s| s.is_pickup()) {
+            TripState::Pickup
+        } else {
+            TripState::Idle
+        }
+    }
+
+    /// Get the trip state of one phase (the neutral is always idle)
+    pub fn phase_state(&self, phase: Phase) -> TripState {
+        match phase {
+            Phase::N => TripState::Idle,
+            _ => self.elements[phase.index()].state(),
+        }
+    }
+
+    /// Get the phases that are picked up or tripped (Str.phsA/phsB/phsC)
+    pub fn start_phases(&self) -> PhaseFlags {
+        let mut flags = PhaseFlags::default();
+        for phase in Phase::PHASES {
+            let state = self.phase_state(phase);
+            flags.set(phase, state.is_pickup() || state.is_tripped());
+        }
+        flags
+    }
+
+    /// Get the phases that have tripped (Op.phsA/phsB/phsC)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        let mut flags = PhaseFlags::default();
+        for phase in Phase::PHASES {
+            flags.set(phase, self.phase_state(phase).is_tripped());
+        }
+        flags
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PiocConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    ///
+    /// The filters are rebuilt and all phases are reset.
+    pub fn set_config(&mut self, config: PiocConfig) {
+        *self = Self::new(config, self.samples_per_cycle, self.nominal_frequency);
+    }
+
+    /// Get the pickup current setting (Iset, RMS primary amperes)
+    pub fn iset(&self) -> f64 {
+        self.config.iset
+    }
+
+    /// Operating quantity of one phase for the next sample
+    fn measure(&mut self, index: usize, sample: f64) -> f64 {
+        let filtered = self.filters[index].add_sample(sample);
+        let (level, measured) = match self.config.measuring_mode {
+            PiocMeasuringMode::Peak => {
+                let level = filtered.abs() / SQRT_2;
+                (level, self.peaks[index].add_sample(level))
+            }
+            PiocMeasuringMode::HalfCycle => {
+                let magnitude = self.dft[index].add_sample(filtered).magnitude();
+                (magnitude, magnitude)
+            }
+        };
+
+        // Start only after consecutive samples above pickup, then hold on the measured value
+        self.confirmed[index] = if level > self.config.iset { self.confirmed[index] + 1 } else { 0 };
+        if self.confirmed[index] >= self.config.confirm_samples || !self.elements[index].state().is_idle() {
+            measured
+        } else {
+            0.0
+        }
+    }
+
+    /// Clear the filter history of one phase
+    fn reset_phase(&mut self, index: usize) {
+        self.filters[index].reset();
+        self.peaks[index].reset();
+        self.dft[index].reset();
+        self.confirmed[index] = 0;
+    }
+}
+
+impl ProtectionFunction for Pioc {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let mut result = ProtectionResult::NoTrip;
+        for phase in Phase::PHASES {
+            let index = phase.index();
+            let channel = frame.current(phase);
+            let current = if channel.quality.is_invalid() {
+                self.reset_phase(index);
+                0.0
+            } else {
+                // Filters keep running while blocked, so a phase is measured as soon as it is released
+                let measured = self.measure(index, channel.instantaneous);
+                if self.config.direction.releases(self.directions.get(phase)) {
+                    measured
+                } else {
+                    0.0
+                }
+            };
+            let phase_result = self.elements[index].process(&self.stage, current, frame.timestamp);
+            result = result.combine(phase_result);
+        }
+        result
+    }
+
+    fn reset(&mut self) {
+        for index in 0..self.elements.len() {
+            self.reset_phase(index);
+            self.elements[index].reset();
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        self.stage.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PIOC"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::{ChannelMeasurement, Quality};
+    use std::f64::consts::PI;
+
+    const SAMPLE_PERIOD_US: u64 = 250;
+
+    fn config(iset: f64, measuring_mode: PiocMeasuringMode) -> PiocConfig {
+        PiocConfig {
+            iset,
+            measuring_mode,
+            ..PiocConfig::default()
+        }
+    }
+
+    /// Frame with the same sampled value on all three phases
+    fn frame(value: f64, n: u64) -> MeasurementFrame {
+        let mut frame = MeasurementFrame::new(n * SAMPLE_PERIOD_US);
+        for phase in Phase::PHASES {
+            *frame.current_mut(phase) = ChannelMeasurement { instantaneous: value, ..Default::default() };
+        }
+        frame
+    }
+
+    /// Feed a fault current of the given RMS value and fraction of full DC offset (τ = 40 ms)
+    ///
+    /// Returns the sample index of the trip, if any.
+    fn feed_fault(pioc: &mut Pioc, rms: f64, offset: f64, count: u64) -> Option<u64> {
+        let peak = rms * SQRT_2;
+        for n in 0..count {
+            let angle = 2.0 * PI * n as f64 / 80.0;
+            let t_ms = n as f64 * SAMPLE_PERIOD_US as f64 / 1000.0;
+            let value = peak * (-angle.cos() + offset * (-t_ms / 40.0).exp());
+            if pioc.process(&frame(value, n)) == ProtectionResult::Trip {
+                return Some(n);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_pioc_trips_within_half_cycle() {
+        // 5 × Iset: trips within a quarter cycle (20 samples)
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::Peak), 80, 50.0);
+        let trip = feed_fault(&mut pioc, 5000.0, 0.0, 80).expect("no trip");
+        assert!(trip < 20, "tripped at sample {}", trip);
+        assert!(pioc.trip_phases().phs_a);
+        assert_eq!(pioc.state(), TripState::Trip);
+    }
+
+    #[test]
+    fn test_pioc_no_trip_below_pickup() {
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::Peak), 80, 50.0);
+        assert_eq!(feed_fault(&mut pioc, 900.0, 0.0, 400), None);
+        assert_eq!(pioc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_pioc_dc_offset_immunity() {
+        // Fully offset current at 90 % of Iset nearly doubles the first peak
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::Peak), 80, 50.0);
+        assert_eq!(feed_fault(&mut pioc, 900.0, 1.0, 400), None);
+
+        // Without the mimic filter the same current overreaches
+        let unfiltered = PiocConfig {
+            dc_time_constant: 0.0,
+            ..config(1000.0, PiocMeasuringMode::Peak)
+        };
+        let mut pioc = Pioc::new(unfiltered, 80, 50.0);
+        assert!(feed_fault(&mut pioc, 900.0, 1.0, 400).is_some());
+    }
+
+    #[test]
+    fn test_pioc_half_cycle() {
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::HalfCycle), 80, 50.0);
+        let trip = feed_fault(&mut pioc, 3000.0, 1.0, 80).expect("no trip");
+        assert!(trip < 40, "tripped at sample {}", trip);
+
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::HalfCycle), 80, 50.0);
+        assert_eq!(feed_fault(&mut pioc, 900.0, 1.0, 400), None);
+    }
+
+    #[test]
+    fn test_pioc_delay_and_disable() {
+        let delayed = PiocConfig { tset: 20, ..config(1000.0, PiocMeasuringMode::Peak) };
+        let mut pioc = Pioc::new(delayed, 80, 50.0);
+        let trip = feed_fault(&mut pioc, 5000.0, 0.0, 200).expect("no trip");
+        assert!(trip >= 80, "tripped at sample {}", trip);
+
+        pioc.set_enabled(false);
+        assert_eq!(pioc.process(&frame(10_000.0, 0)), ProtectionResult::Disabled);
+        assert_eq!(pioc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_pioc_invalid_quality() {
+        let mut pioc = Pioc::new(config(1000.0, PiocMeasuringMode::Peak), 80, 50.0);
+        for n in 0..80 {
+            let mut frame = frame(10_000.0, n);
+            for phase in Phase::PHASES {
+                frame.current_mut(phase).quality = Quality::INVALID;
+            }
+            assert_eq!(pioc.process(&frame), ProtectionResult::NoTrip);
+        }
+    }
+}
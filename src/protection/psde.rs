@@ -0,0 +1,251 @@
+/// PSDE (Sensitive Directional Earth Fault) with wattmetric and varmetric criteria
+use super::element::{self, OvercurrentElement};
+use super::rdir::DirectionMode;
+use super::residual::ResidualSource;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::{PsdeConfig, PtocConfig};
+use crate::measurement::{ChannelMeasurement, MeasurementFrame, Phase};
+use serde::{Deserialize, Serialize};
+
+/// Component of the residual current compared with the setting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SefCriterion {
+    /// Active component I0·cos(φ), for resonant-earthed (Petersen coil) networks
+    #[default]
+    Wattmetric,
+    /// Reactive component I0·sin(φ), for isolated networks
+    Varmetric,
+}
+
+/// PSDE protection function for compensated and isolated networks
+///
+/// In these networks the earth-fault current is of the order of the load
+/// imbalance and does not depend on the fault location, so the faulted feeder
+/// is found by the phase angle of 3I0 against the polarising voltage −3V0
+/// instead of its magnitude. φ is the angle by which 3I0 leads −3V0, reduced
+/// by `angle_correction`. On the faulted feeder the component selected by the
+/// criterion is positive (forward); on a healthy feeder the capacitive
+/// current makes I0·sin(φ) negative and I0·cos(φ) about zero.
+///
+/// The residual current is the measured neutral channel, normally a
+/// core-balance CT scaled with `neutral_ct`. The function is released only
+/// while 3V0 exceeds `min_residual_voltage`. The component is evaluated like
+/// a definite time overcurrent stage and reported on the neutral.
+pub struct Psde {
+    config: PsdeConfig,
+    stage: PtocConfig,
+    element: OvercurrentElement,
+}
+
+impl Psde {
+    /// Create a new PSDE function
+    ///
+    /// # Arguments
+    /// * `config` - Pickup, delay, criterion and release settings
+    pub fn new(config: PsdeConfig) -> Self {
+        Self {
+            stage: element::definite_time_stage(config.iset, config.tset, config.enabled, config.dropout_ratio),
+            config,
+            element: OvercurrentElement::new(),
+        }
+    }
+
+    /// Get the trip state
+    pub fn state(&self) -> TripState {
+        self.element.state()
+    }
+
+    /// Get the start flags (Str.neut while picked up or tripped)
+    pub fn start_phases(&self) -> PhaseFlags {
+        let state = self.state();
+        PhaseFlags {
+            neut: state.is_pickup() || state.is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the trip flags (Op.neut)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        PhaseFlags {
+            neut: self.state().is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PsdeConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    pub fn set_config(&mut self, config: PsdeConfig) {
+        self.stage = element::definite_time_stage(config.iset, config.tset, config.enabled, config.dropout_ratio);
+        self.config = config;
+        if !self.config.enabled {
+            self.reset();
+        }
+    }
+
+    /// Get the residual voltage of a frame according to the configured source
+    pub fn residual_voltage(&self, frame: &MeasurementFrame) -> ChannelMeasurement {
+        match self.config.voltage_source {
+            ResidualSource::Calculated => frame.residual_voltage(),
+            ResidualSource::Measured => *frame.voltage(Phase::N),
+        }
+    }
+
+    /// Calculate the signed residual current component of a frame
+    ///
+    /// # Returns
+    /// I0·cos(φ) or I0·sin(φ) in primary amperes, positive in forward
+    /// direction, or `None` if a quantity is invalid or 3V0 is below
+    /// `min_residual_voltage`
+    pub fn component(&self, frame: &MeasurementFrame) -> Option<f64> {
+        let current = frame.current(Phase::N);
+        let voltage = self.residual_voltage(frame);
+        if current.quality.is_invalid() || voltage.quality.is_invalid() {
+            return None;
+        }
+        if voltage.phasor.magnitude() < self.config.min_residual_voltage {
+            return None;
+        }
+
+        let polarising = -voltage.phasor;
+        let phi = current.phasor.angle() - polarising.angle() - self.config.angle_correction.to_radians();
+        let magnitude = current.phasor.magnitude();
+        Some(match self.config.criterion {
+            SefCriterion::Wattmetric => magnitude * phi.cos(),
+            SefCriterion::Varmetric => magnitude * phi.sin(),
+        })
+    }
+}
+
+impl ProtectionFunction for Psde {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let current = match self.component(frame) {
+            Some(component) => match self.config.direction {
+                DirectionMode::NonDirectional => component.abs(),
+                DirectionMode::Forward => component.max(0.0),
+                DirectionMode::Reverse => (-component).max(0.0),
+            },
+            None => 0.0,
+        };
+        self.element.process(&self.stage, current, frame.timestamp)
+    }
+
+    fn reset(&mut self) {
+        self.element.reset();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        self.stage.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PSDE"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::{Phasor, Quality};
+
+    /// Frame with a displaced neutral (phase A to earth) and the given 3I0 relative to −3V0
+    fn fault_frame(current: f64, angle_deg: f64, timestamp: u64) -> MeasurementFrame {
+        let mut frame = MeasurementFrame::new(timestamp);
+        // Solid fault on phase A: 3V0 = −3·Va, −3V0 at 0°
+        let v0 = Phasor::from_polar_deg(3.0 * 11547.0, 180.0);
+        *frame.voltage_mut(Phase::N) = ChannelMeasurement::new(v0.magnitude(), v0);
+        *frame.current_mut(Phase::N) =
+            ChannelMeasurement::new(current, Phasor::from_polar_deg(current, angle_deg));
+        frame
+    }
+
+    fn config(criterion: SefCriterion, direction: DirectionMode) -> PsdeConfig {
+        PsdeConfig {
+            iset: 2.0,
+            tset: 0,
+            criterion,
+            direction,
+            voltage_source: ResidualSource::Measured,
+            ..PsdeConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_wattmetric_compensated_network() {
+        let mut psde = Psde::new(config(SefCriterion::Wattmetric, DirectionMode::Forward));
+
+        // Faulted feeder: 3 A active component in phase with −3V0
+        let faulted = fault_frame(5.0, 53.13, 0);
+        assert!((psde.component(&faulted).unwrap() - 3.0).abs() < 0.01);
+        assert_eq!(psde.process(&faulted), ProtectionResult::Trip);
+        assert!(psde.trip_phases().neut);
+
+        // Healthy feeder: 20 A capacitive current lagging −3V0 by 90°
+        let mut psde = Psde::new(config(SefCriterion::Wattmetric, DirectionMode::Forward));
+        assert_eq!(psde.process(&fault_frame(20.0, -90.0, 0)), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_varmetric_isolated_network() {
+        let mut psde = Psde::new(config(SefCriterion::Varmetric, DirectionMode::Forward));
+        // Faulted feeder: capacitive current of the rest of the network leads −3V0 by 90°
+        assert_eq!(psde.process(&fault_frame(10.0, 90.0, 0)), ProtectionResult::Trip);
+
+        // Healthy feeder is reverse
+        let mut psde = Psde::new(config(SefCriterion::Varmetric, DirectionMode::Forward));
+        assert_eq!(psde.process(&fault_frame(10.0, -90.0, 0)), ProtectionResult::NoTrip);
+        let mut psde = Psde::new(config(SefCriterion::Varmetric, DirectionMode::Reverse));
+        assert_eq!(psde.process(&fault_frame(10.0, -90.0, 0)), ProtectionResult::Trip);
+    }
+
+    #[test]
+    fn test_residual_voltage_release() {
+        let mut psde = Psde::new(config(SefCriterion::Wattmetric, DirectionMode::Forward));
+        let mut frame = fault_frame(5.0, 0.0, 0);
+        let v0 = Phasor::from_polar_deg(1000.0, 180.0);
+        *frame.voltage_mut(Phase::N) = ChannelMeasurement::new(1000.0, v0);
+        assert_eq!(psde.component(&frame), None);
+        assert_eq!(psde.process(&frame), ProtectionResult::NoTrip);
+
+        let mut frame = fault_frame(5.0, 0.0, 0);
+        frame.current_mut(Phase::N).quality = Quality::INVALID;
+        assert_eq!(psde.process(&frame), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_calculated_residual_voltage_and_delay() {
+        let mut psde = Psde::new(PsdeConfig {
+            tset: 500,
+            ..config(SefCriterion::Wattmetric, DirectionMode::Forward)
+        });
+        // Phase A earthed: Va = 0, 3V0 = Vb + Vc = −Va of the healthy system
+        let mut frame = MeasurementFrame::new(0);
+        for (phase, angle) in [(Phase::B, -150.0), (Phase::C, 150.0)] {
+            *frame.voltage_mut(phase) = ChannelMeasurement::new(20000.0, Phasor::from_polar_deg(20000.0, angle));
+        }
+        *frame.current_mut(Phase::N) = ChannelMeasurement::new(4.0, Phasor::from_polar_deg(4.0, 0.0));
+
+        psde.set_config(PsdeConfig {
+            voltage_source: ResidualSource::Calculated,
+            ..psde.config().clone()
+        });
+        assert!(matches!(psde.process(&frame), ProtectionResult::TripPending(_)));
+        frame.timestamp = 500_000;
+        assert_eq!(psde.process(&frame), ProtectionResult::Trip);
+    }
+}
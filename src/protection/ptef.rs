@@ -0,0 +1,271 @@
+/// PTEF (Transient Earth Fault) detecting intermittent, re-striking earth faults
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::PtefConfig;
+use crate::measurement::{MeasurementFrame, Phase};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// PTEF protection function counting residual current pulses
+///
+/// An intermittent earth fault in a compensated or isolated network
+/// extinguishes at the current zero and re-strikes when the recovering
+/// voltage breaks the insulation down again. Each re-strike discharges the
+/// healthy phase capacitances as a short, high residual current spike, while
+/// the fundamental frequency 3I0 stays too low and too short for PSDE.
+///
+/// Every sample of the measured neutral current whose absolute value rises
+/// above `pulse_threshold` starts a pulse. A rising edge within
+/// `blanking_time` of the last counted pulse belongs to the ringing of that
+/// pulse and is not counted again. The function picks up on the first pulse
+/// and trips when `pulse_count` pulses fall within `window`. It returns to
+/// idle when the last pulse leaves the window, so a trip drops out one window
+/// after the re-strikes stopped.
+///
+/// Only the last `pulse_count` pulses are kept, in a buffer allocated when
+/// the function is created. Invalid neutral current clears the pulse history
+/// unless the function has tripped; no new pulses are then counted and the
+/// trip drops out when the last pulse leaves the window.
+pub struct Ptef {
+    config: PtefConfig,
+    pulses: VecDeque<u64>,
+    above: bool,
+    state: TripState,
+}
+
+impl Ptef {
+    /// Create a new PTEF function
+    ///
+    /// # Arguments
+    /// * `config` - Pulse threshold, count and window settings
+    pub fn new(config: PtefConfig) -> Self {
+        Self {
+            pulses: VecDeque::with_capacity(config.pulse_count),
+            config,
+            above: false,
+            state: TripState::Idle,
+        }
+    }
+
+    /// Get the trip state
+    pub fn state(&self) -> TripState {
+        self.state
+    }
+
+    /// Get the number of pulses within the window, at most `pulse_count`
+    pub fn pulse_count(&self) -> usize {
+        self.pulses.len()
+    }
+
+    /// Get the start flags (Str.neut while picked up or tripped)
+    pub fn start_phases(&self) -> PhaseFlags {
+        PhaseFlags {
+            neut: self.state.is_pickup() || self.state.is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the trip flags (Op.neut)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        PhaseFlags {
+            neut: self.state.is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PtefConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    pub fn set_config(&mut self, config: PtefConfig) {
+        self.config = config;
+        self.reset();
+        self.pulses.reserve(self.config.pulse_count);
+    }
+
+    /// Detect a new pulse in one sampled value
+    fn detect_pulse(&mut self, sample: f64, timestamp: u64) -> bool {
+        let above = sample.abs() > self.config.pulse_threshold;
+        let rising = above && !self.above;
+        self.above = above;
+
+        let blanking_us = self.config.blanking_time * 1000;
+        rising
+            && self
+                .pulses
+                .back()
+                .is_none_or(|&last| timestamp.saturating_sub(last) >= blanking_us)
+    }
+}
+
+impl ProtectionFunction for Ptef {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let channel = frame.current(Phase::N);
+        let valid = !channel.quality.is_invalid();
+        if !valid && !self.state.is_tripped() {
+            self.reset();
+            return ProtectionResult::NoTrip;
+        }
+
+        let window_us = self.config.window * 1000;
+        while self
+            .pulses
+            .front()
+            .is_some_and(|&first| frame.timestamp.saturating_sub(first) >= window_us)
+        {
+            self.pulses.pop_front();
+        }
+        if valid && self.detect_pulse(channel.instantaneous, frame.timestamp) {
+            if self.pulses.len() >= self.config.pulse_count {
+                self.pulses.pop_front();
+            }
+            self.pulses.push_back(frame.timestamp);
+        }
+
+        match self.state {
+            // Tripped while any pulse remains within the window
+            TripState::Trip if !self.pulses.is_empty() => ProtectionResult::Trip,
+            TripState::Trip => {
+                self.reset();
+                ProtectionResult::NoTrip
+            }
+            _ if self.pulses.len() >= self.config.pulse_count => {
+                self.state = TripState::Trip;
+                ProtectionResult::Trip
+            }
+            _ => match self.pulses.front() {
+                Some(&first) => {
+                    // Remaining pulses must follow before the first one leaves the window
+                    self.state = TripState::Pickup;
+                    let remaining = (first + window_us).saturating_sub(frame.timestamp);
+                    ProtectionResult::TripPending(Duration::from_micros(remaining))
+                }
+                None => {
+                    self.state = TripState::Idle;
+                    ProtectionResult::NoTrip
+                }
+            },
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pulses.clear();
+        self.above = false;
+        self.state = TripState::Idle;
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PTEF"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::Quality;
+
+    const SAMPLE_PERIOD_US: u64 = 250;
+
+    fn config() -> PtefConfig {
+        PtefConfig {
+            pulse_threshold: 50.0,
+            pulse_count: 4,
+            window: 500,
+            blanking_time: 5,
+            ..PtefConfig::default()
+        }
+    }
+
+    fn frame(value: f64, n: u64) -> MeasurementFrame {
+        let mut frame = MeasurementFrame::new(n * SAMPLE_PERIOD_US);
+        frame.current_mut(Phase::N).instantaneous = value;
+        frame
+    }
+
+    /// Feed `count` samples with a ringing re-strike spike every `interval` samples
+    fn feed(ptef: &mut Ptef, start: u64, count: u64, interval: u64) -> ProtectionResult {
+        let mut result = ProtectionResult::NoTrip;
+        for n in start..start + count {
+            // Spike rings over four samples with alternating polarity
+            let value = match (n - start) % interval {
+                0 => 200.0,
+                1 => -150.0,
+                2 => 100.0,
+                3 => -60.0,
+                _ => 2.0,
+            };
+            result = ptef.process(&frame(value, n));
+        }
+        result
+    }
+
+    #[test]
+    fn test_restriking_pulses_trip() {
+        let mut ptef = Ptef::new(config());
+        // Re-strike every 100 ms (400 samples): the fourth pulse at 300 ms trips
+        assert!(matches!(feed(&mut ptef, 0, 1200, 400), ProtectionResult::TripPending(_)));
+        assert_eq!(ptef.pulse_count(), 3);
+        assert_eq!(feed(&mut ptef, 1200, 100, 400), ProtectionResult::Trip);
+        assert!(ptef.trip_phases().neut);
+    }
+
+    #[test]
+    fn test_ringing_counted_once() {
+        let mut ptef = Ptef::new(config());
+        feed(&mut ptef, 0, 10, 400);
+        assert_eq!(ptef.pulse_count(), 1);
+        assert_eq!(ptef.state(), TripState::Pickup);
+    }
+
+    #[test]
+    fn test_slow_pulses_drop_out() {
+        let mut ptef = Ptef::new(config());
+        // One re-strike every 200 ms: never four within 500 ms
+        assert_ne!(feed(&mut ptef, 0, 8000, 800), ProtectionResult::Trip);
+        assert!(ptef.pulse_count() < 4);
+
+        // Pulses stop: back to idle once the window has passed
+        let result = (8000..10_100).map(|n| ptef.process(&frame(0.0, n))).last();
+        assert_eq!(result, Some(ProtectionResult::NoTrip));
+        assert_eq!(ptef.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_trip_drops_out_after_window() {
+        let mut ptef = Ptef::new(config());
+        assert_eq!(feed(&mut ptef, 0, 1300, 400), ProtectionResult::Trip);
+
+        // Re-strikes stopped after the pulse at 300 ms: trip holds for one window
+        let results: Vec<ProtectionResult> = (1300..6000).map(|n| ptef.process(&frame(0.0, n))).collect();
+        assert_eq!(results[0], ProtectionResult::Trip);
+        assert_eq!(results.last(), Some(&ProtectionResult::NoTrip));
+        assert_eq!(ptef.state(), TripState::Idle);
+        assert!(!ptef.trip_phases().neut);
+    }
+
+    #[test]
+    fn test_invalid_quality_clears_pulses() {
+        let mut ptef = Ptef::new(config());
+        feed(&mut ptef, 0, 1100, 400);
+        let mut invalid = frame(200.0, 1100);
+        invalid.current_mut(Phase::N).quality = Quality::INVALID;
+        assert_eq!(ptef.process(&invalid), ProtectionResult::NoTrip);
+        assert_eq!(ptef.pulse_count(), 0);
+    }
+}
@@ -1,13 +1,23 @@
 /// PTOC (Time Overcurrent Protection) implementation
-use super::traits::{ProtectionFunction, ProtectionResult, TripState};
+use super::element::{self, OvercurrentElement};
+use super::rdir::Directions;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
 use crate::config::PtocConfig;
+use crate::measurement::{MeasurementFrame, Phase};
 use std::time::Duration;
 
-/// PTOC protection function with definite time characteristic
+/// PTOC protection function with definite or inverse time characteristic
+///
+/// Each phase (A, B, C) has its own measuring element and time delay, so
+/// pickup (Str) and trip (Op) are reported phase-selectively. The overall
+/// result is the most severe of the three phases.
+///
+/// In a directional stage a phase whose direction (see `set_directions`)
+/// does not match the configured mode is evaluated as zero current.
 pub struct Ptoc {
     config: PtocConfig,
-    state: TripState,
-    pickup_time: Option<u64>,
+    elements: [OvercurrentElement; 3],
+    directions: Directions,
 }
 
 impl Ptoc {
@@ -15,14 +25,59 @@ impl Ptoc {
     pub fn new(config: PtocConfig) -> Self {
         Self {
             config,
-            state: TripState::Idle,
-            pickup_time: None,
+            elements: Default::default(),
+            directions: Directions::default(),
         }
     }
 
-    /// Get the current trip state
+    /// Set the fault directions used by a directional stage for the next evaluations
+    pub fn set_directions(&mut self, directions: Directions) {
+        self.directions = directions;
+    }
+
+    /// Get the overall trip state (most severe phase state)
     pub fn state(&self) -> TripState {
-        self.state
+        let states = self.elements.iter().map(OvercurrentElement::state);
+        if states.clone().any(|s| s.is_tripped()) {
+            TripState::Trip
+        } else if states.clone().any(|s| s.is_pickup()) {
+            TripState::Pickup
+        } else if states.clone().any(|s| s.is_resetting()) {
+            TripState::Resetting
+        } else {
+            TripState::Idle
+        }
+    }
+
+    /// Get the trip state of one phase
+    ///
+    /// The neutral has no element in phase PTOC and is always idle.
+    pub fn phase_state(&self, phase: Phase) -> TripState {
+        match phase {
+            Phase::N => TripState::Idle,
+            _ => self.elements[phase.index()].state(),
+        }
+    }
+
+    /// Get the phases that are picked up (Str.phsA/phsB/phsC)
+    ///
+    /// Tripped phases are included, as the start stays active while operating.
+    pub fn start_phases(&self) -> PhaseFlags {
+        let mut flags = PhaseFlags::default();
+        for phase in Phase::PHASES {
+            let state = self.phase_state(phase);
+            flags.set(phase, state.is_pickup() || state.is_tripped());
+        }
+        flags
+    }
+
+    /// Get the phases that have tripped (Op.phsA/phsB/phsC)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        let mut flags = PhaseFlags::default();
+        for phase in Phase::PHASES {
+            flags.set(phase, self.phase_state(phase).is_tripped());
+        }
+        flags
     }
 
     /// Get the configuration
@@ -49,74 +104,59 @@ impl Ptoc {
         self.config.tset
     }
 
-    /// Check if current exceeds pickup setting
-    fn is_overcurrent(&self, current: f64) -> bool {
-        current > self.config.iset
+    /// Get the dropout current threshold in primary amperes
+    pub fn dropout_current(&self) -> f64 {
+        self.config.iset * self.config.dropout_ratio
     }
 
-    /// Calculate time elapsed since pickup in milliseconds
-    fn time_since_pickup(&self, current_time: u64) -> Option<u64> {
-        self.pickup_time.map(|pickup| {
-            // Convert microseconds to milliseconds
-            (current_time.saturating_sub(pickup)) / 1000
-        })
+    /// Get the highest operate progress of the three phases
+    /// (0.0 = just picked up, 1.0 = trip)
+    pub fn progress(&self) -> f64 {
+        self.elements
+            .iter()
+            .map(OvercurrentElement::progress)
+            .fold(0.0, f64::max)
+    }
+
+    /// Calculate the operate time for a constant current
+    ///
+    /// # Arguments
+    /// * `current` - RMS current value in primary amperes
+    ///
+    /// # Returns
+    /// Operate time according to the configured characteristic, or `None`
+    /// if the current does not exceed the pickup setting
+    pub fn operate_time(&self, current: f64) -> Option<Duration> {
+        element::operate_time(&self.config, current)
     }
 }
 
 impl ProtectionFunction for Ptoc {
-    fn process(&mut self, current: f64, timestamp: u64) -> ProtectionResult {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
         if !self.config.enabled {
             return ProtectionResult::Disabled;
         }
 
-        let is_overcurrent = self.is_overcurrent(current);
-
-        match self.state {
-            TripState::Idle => {
-                if is_overcurrent {
-                    // Current exceeded pickup, start timing
-                    self.state = TripState::Pickup;
-                    self.pickup_time = Some(timestamp);
-                    ProtectionResult::TripPending(Duration::from_millis(self.config.tset))
-                } else {
-                    ProtectionResult::NoTrip
-                }
-            }
-            TripState::Pickup => {
-                if !is_overcurrent {
-                    // Current dropped below pickup, reset
-                    self.state = TripState::Idle;
-                    self.pickup_time = None;
-                    ProtectionResult::NoTrip
-                } else {
-                    // Check if time delay has expired
-                    if let Some(elapsed) = self.time_since_pickup(timestamp) {
-                        if elapsed >= self.config.tset {
-                            // Time delay expired, issue trip
-                            self.state = TripState::Trip;
-                            ProtectionResult::Trip
-                        } else {
-                            // Still waiting for time delay
-                            let remaining = self.config.tset - elapsed;
-                            ProtectionResult::TripPending(Duration::from_millis(remaining))
-                        }
-                    } else {
-                        // This shouldn't happen, but handle it gracefully
-                        self.pickup_time = Some(timestamp);
-                        ProtectionResult::TripPending(Duration::from_millis(self.config.tset))
-                    }
-                }
-            }
-            TripState::Trip => {
-                // Once tripped, stay tripped until reset
-                ProtectionResult::Trip
-            }
+        let mut result = ProtectionResult::NoTrip;
+        for phase in Phase::PHASES {
+            let channel = frame.current(phase);
+            let released = self.config.direction.releases(self.directions.get(phase));
+            let current = if channel.quality.is_invalid() || !released {
+                0.0
+            } else {
+                channel.magnitude(self.config.measuring_mode)
+            };
+            let phase_result =
+                self.elements[phase.index()].process(&self.config, current, frame.timestamp);
+            result = result.combine(phase_result);
         }
+        result
     }
 
     fn reset(&mut self) {
-        self.state = TripState::Idle;
-        self.pickup_time = None;
+        for element in &mut self.elements {
+            element.reset();
+        }
     }
 
     fn is_enabled(&self) -> bool {
@@ -138,6 +178,8 @@ impl ProtectionFunction for Ptoc {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::measurement::{ChannelMeasurement, MeasuringMode, Phasor, Quality};
+    use crate::protection::{CurveType, Direction, DirectionMode, ResetMode};
 
     #[test]
     fn test_ptoc_no_trip_below_pickup() {
@@ -145,11 +187,12 @@ mod tests {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Current below pickup
-        let result = ptoc.process(50.0, 0);
+        let result = ptoc.process(&MeasurementFrame::balanced(50.0, 0));
         assert_eq!(result, ProtectionResult::NoTrip);
         assert_eq!(ptoc.state(), TripState::Idle);
     }
@@ -160,11 +203,12 @@ mod tests {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Current exceeds pickup
-        let result = ptoc.process(150.0, 0);
+        let result = ptoc.process(&MeasurementFrame::balanced(150.0, 0));
         assert!(matches!(result, ProtectionResult::TripPending(_)));
         assert_eq!(ptoc.state(), TripState::Pickup);
     }
@@ -175,52 +219,199 @@ mod tests {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Current exceeds pickup at t=0
-        ptoc.process(150.0, 0);
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
 
         // Still overcurrent at t=50ms (50000 microseconds)
-        let result = ptoc.process(150.0, 50_000);
+        let result = ptoc.process(&MeasurementFrame::balanced(150.0, 50_000));
         assert!(matches!(result, ProtectionResult::TripPending(_)));
 
         // Still overcurrent at t=100ms (100000 microseconds) - should trip
-        let result = ptoc.process(150.0, 100_000);
+        let result = ptoc.process(&MeasurementFrame::balanced(150.0, 100_000));
         assert_eq!(result, ProtectionResult::Trip);
         assert_eq!(ptoc.state(), TripState::Trip);
     }
 
+    #[test]
+    fn test_ptoc_zero_delay_trips_on_pickup() {
+        let config = PtocConfig {
+            iset: 1000.0,
+            tset: 0,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(1500.0, 0)), ProtectionResult::Trip);
+        assert_eq!(ptoc.state(), TripState::Trip);
+    }
+
     #[test]
     fn test_ptoc_reset_on_current_drop() {
         let config = PtocConfig {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Current exceeds pickup
-        ptoc.process(150.0, 0);
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
         assert_eq!(ptoc.state(), TripState::Pickup);
 
         // Current drops before delay expires
-        let result = ptoc.process(50.0, 50_000);
+        let result = ptoc.process(&MeasurementFrame::balanced(50.0, 50_000));
         assert_eq!(result, ProtectionResult::NoTrip);
         assert_eq!(ptoc.state(), TripState::Idle);
     }
 
+    #[test]
+    fn test_ptoc_dropout_ratio_hysteresis() {
+        let config = PtocConfig {
+            iset: 100.0,
+            tset: 100,
+            dropout_ratio: 0.95,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 98A does not pick up
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(98.0, 0)), ProtectionResult::NoTrip);
+
+        // Pickup above Iset, then 98A keeps the function picked up
+        ptoc.process(&MeasurementFrame::balanced(110.0, 10_000));
+        let result = ptoc.process(&MeasurementFrame::balanced(98.0, 60_000));
+        assert!(matches!(result, ProtectionResult::TripPending(_)));
+        assert_eq!(ptoc.state(), TripState::Pickup);
+
+        // Definite time keeps running inside the hysteresis band
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(98.0, 110_000)), ProtectionResult::Trip);
+    }
+
+    #[test]
+    fn test_ptoc_drops_out_below_dropout_ratio() {
+        let config = PtocConfig {
+            iset: 100.0,
+            tset: 100,
+            dropout_ratio: 0.95,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        ptoc.process(&MeasurementFrame::balanced(110.0, 0));
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(94.0, 50_000)), ProtectionResult::NoTrip);
+        assert_eq!(ptoc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_ptoc_definite_time_reset_holds_timer() {
+        let config = PtocConfig {
+            iset: 100.0,
+            tset: 100,
+            reset_mode: ResetMode::DefiniteTime,
+            reset_time: 50,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 60ms of overcurrent, then a 30ms gap (shorter than reset time)
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(150.0, 60_000));
+        ptoc.process(&MeasurementFrame::balanced(50.0, 60_000));
+        assert_eq!(ptoc.state(), TripState::Resetting);
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(50.0, 90_000)), ProtectionResult::NoTrip);
+
+        // Fault re-strikes: only the remaining 40ms are needed
+        let result = ptoc.process(&MeasurementFrame::balanced(150.0, 90_000));
+        assert_eq!(result, ProtectionResult::TripPending(Duration::from_millis(40)));
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(150.0, 130_000)), ProtectionResult::Trip);
+    }
+
+    #[test]
+    fn test_ptoc_definite_time_reset_expires() {
+        let config = PtocConfig {
+            iset: 100.0,
+            tset: 100,
+            reset_mode: ResetMode::DefiniteTime,
+            reset_time: 50,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(150.0, 60_000));
+        ptoc.process(&MeasurementFrame::balanced(50.0, 60_000));
+        ptoc.process(&MeasurementFrame::balanced(50.0, 110_000));
+        assert_eq!(ptoc.state(), TripState::Idle);
+        assert_eq!(ptoc.progress(), 0.0);
+    }
+
+    #[test]
+    fn test_ptoc_inverse_reset_disk_emulation() {
+        let config = PtocConfig {
+            iset: 100.0,
+            curve: CurveType::IeeeVeryInverse,
+            tms: 1.0,
+            reset_mode: ResetMode::Inverse,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // Accumulate 50% progress at 5x pickup
+        let operate = ptoc.operate_time(500.0).unwrap().as_micros() as u64;
+        ptoc.process(&MeasurementFrame::balanced(500.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(500.0, operate / 2));
+        assert!((ptoc.progress() - 0.5).abs() < 1e-6);
+
+        // At zero current a full reset takes tr = 21.6s, so 2.16s removes 10%
+        ptoc.process(&MeasurementFrame::balanced(0.0, operate / 2));
+        assert_eq!(ptoc.state(), TripState::Resetting);
+        ptoc.process(&MeasurementFrame::balanced(0.0, operate / 2 + 2_160_000));
+        assert!((ptoc.progress() - 0.4).abs() < 1e-6);
+
+        // The rest of the reset (8.64s) returns the function to idle
+        ptoc.process(&MeasurementFrame::balanced(0.0, operate / 2 + 2_160_000 + 8_640_000));
+        assert_eq!(ptoc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_ptoc_intermittent_fault_accumulates() {
+        let config = PtocConfig {
+            iset: 100.0,
+            tset: 100,
+            reset_mode: ResetMode::DefiniteTime,
+            reset_time: 200,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // Arcing fault: 30ms on, 30ms off; trips on the fourth strike
+        let mut result = ProtectionResult::NoTrip;
+        for strike in 0..4u64 {
+            let start = strike * 60_000;
+            ptoc.process(&MeasurementFrame::balanced(150.0, start));
+            result = ptoc.process(&MeasurementFrame::balanced(150.0, start + 30_000));
+            ptoc.process(&MeasurementFrame::balanced(50.0, start + 30_000));
+        }
+        assert_eq!(result, ProtectionResult::Trip);
+    }
+
     #[test]
     fn test_ptoc_disabled() {
         let config = PtocConfig {
             iset: 100.0,
             tset: 100,
             enabled: false,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Current exceeds pickup but function is disabled
-        let result = ptoc.process(150.0, 0);
+        let result = ptoc.process(&MeasurementFrame::balanced(150.0, 0));
         assert_eq!(result, ProtectionResult::Disabled);
         assert_eq!(ptoc.state(), TripState::Idle);
     }
@@ -231,12 +422,13 @@ mod tests {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Trip the function
-        ptoc.process(150.0, 0);
-        ptoc.process(150.0, 100_000);
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(150.0, 100_000));
         assert_eq!(ptoc.state(), TripState::Trip);
 
         // Reset
@@ -245,22 +437,191 @@ mod tests {
     }
 
     #[test]
-    fn test_ptoc_stays_tripped() {
+    fn test_ptoc_trip_drops_out() {
         let config = PtocConfig {
             iset: 100.0,
             tset: 100,
             enabled: true,
+            ..PtocConfig::default()
         };
         let mut ptoc = Ptoc::new(config);
 
         // Trip the function
-        ptoc.process(150.0, 0);
-        ptoc.process(150.0, 100_000);
+        ptoc.process(&MeasurementFrame::balanced(150.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(150.0, 100_000));
         assert_eq!(ptoc.state(), TripState::Trip);
 
-        // Even if current drops, should stay tripped
-        let result = ptoc.process(50.0, 200_000);
+        // Between dropout (95 A) and pickup the trip is held
+        let result = ptoc.process(&MeasurementFrame::balanced(97.0, 200_000));
         assert_eq!(result, ProtectionResult::Trip);
         assert_eq!(ptoc.state(), TripState::Trip);
+
+        // Below dropout the trip drops out
+        let result = ptoc.process(&MeasurementFrame::balanced(50.0, 300_000));
+        assert_eq!(result, ProtectionResult::NoTrip);
+        assert_eq!(ptoc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_ptoc_inverse_trip_time() {
+        let config = PtocConfig {
+            iset: 100.0,
+            curve: CurveType::IecVeryInverse,
+            tms: 0.1,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 500A = 5 x Iset: t = 0.1 * 13.5 / (5 - 1) = 337.5ms
+        assert_eq!(
+            ptoc.operate_time(500.0),
+            Some(Duration::from_secs_f64(0.3375))
+        );
+
+        // Feed one RMS value per 1ms
+        let mut trip_time = None;
+        for ms in 0..1000u64 {
+            if ptoc.process(&MeasurementFrame::balanced(500.0, ms * 1000)) == ProtectionResult::Trip {
+                trip_time = Some(ms);
+                break;
+            }
+        }
+        assert_eq!(trip_time, Some(338));
+    }
+
+    #[test]
+    fn test_ptoc_inverse_integrates_varying_current() {
+        let config = PtocConfig {
+            iset: 100.0,
+            curve: CurveType::IecVeryInverse,
+            tms: 0.1,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 200A (t = 1.35s) for 675ms covers half of the operate time
+        ptoc.process(&MeasurementFrame::balanced(200.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(200.0, 675_000));
+        assert!((ptoc.progress() - 0.5).abs() < 1e-6);
+
+        // 500A (t = 337.5ms): remaining half needs ~169ms
+        let result = ptoc.process(&MeasurementFrame::balanced(500.0, 675_000 + 160_000));
+        assert!(matches!(result, ProtectionResult::TripPending(_)));
+        let result = ptoc.process(&MeasurementFrame::balanced(500.0, 675_000 + 170_000));
+        assert_eq!(result, ProtectionResult::Trip);
+    }
+
+    #[test]
+    fn test_ptoc_inverse_holds_in_hysteresis_band() {
+        let config = PtocConfig {
+            iset: 100.0,
+            curve: CurveType::IecVeryInverse,
+            tms: 0.1,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 200A (t = 1.35s) for 675ms, then 98A inside the band holds the timer
+        ptoc.process(&MeasurementFrame::balanced(200.0, 0));
+        ptoc.process(&MeasurementFrame::balanced(200.0, 675_000));
+        let result = ptoc.process(&MeasurementFrame::balanced(98.0, 1_000_000));
+        assert_eq!(ptoc.state(), TripState::Pickup);
+        assert!((ptoc.progress() - 0.5).abs() < 1e-6);
+
+        // Remaining time of the last current above pickup, not an unbounded wait
+        match result {
+            ProtectionResult::TripPending(remaining) => {
+                assert!((remaining.as_secs_f64() - 0.675).abs() < 1e-6)
+            }
+            other => panic!("expected TripPending, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ptoc_definite_time_independent_of_current() {
+        let ptoc = Ptoc::new(PtocConfig::default());
+        assert_eq!(ptoc.operate_time(50.0), None);
+        assert_eq!(ptoc.operate_time(150.0), Some(Duration::from_millis(100)));
+        assert_eq!(ptoc.operate_time(5000.0), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_ptoc_phase_selective_trip() {
+        let mut ptoc = Ptoc::new(PtocConfig::default());
+
+        // Phase B fault only
+        let fault = |t| MeasurementFrame::from_phase_currents([50.0, 300.0, 50.0], t);
+        ptoc.process(&fault(0));
+        assert_eq!(
+            ptoc.start_phases(),
+            PhaseFlags { phs_b: true, ..PhaseFlags::default() }
+        );
+        assert_eq!(ptoc.phase_state(Phase::A), TripState::Idle);
+
+        assert_eq!(ptoc.process(&fault(100_000)), ProtectionResult::Trip);
+        assert_eq!(
+            ptoc.trip_phases(),
+            PhaseFlags { phs_b: true, ..PhaseFlags::default() }
+        );
+        assert!(ptoc.trip_phases().general());
+    }
+
+    #[test]
+    fn test_ptoc_phases_time_independently() {
+        let mut ptoc = Ptoc::new(PtocConfig::default());
+
+        // Phase A picks up at t=0, phase C at t=50ms
+        ptoc.process(&MeasurementFrame::from_phase_currents([200.0, 0.0, 0.0], 0));
+        ptoc.process(&MeasurementFrame::from_phase_currents([200.0, 0.0, 200.0], 50_000));
+        ptoc.process(&MeasurementFrame::from_phase_currents([200.0, 0.0, 200.0], 100_000));
+        assert_eq!(ptoc.phase_state(Phase::A), TripState::Trip);
+        assert_eq!(ptoc.phase_state(Phase::C), TripState::Pickup);
+        assert_eq!(ptoc.state(), TripState::Trip);
+    }
+
+    #[test]
+    fn test_ptoc_fundamental_measuring_mode() {
+        let config = PtocConfig {
+            measuring_mode: MeasuringMode::Fundamental,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+
+        // 120A RMS with a strong harmonic content, but only 90A fundamental
+        let mut frame = MeasurementFrame::new(0);
+        *frame.current_mut(Phase::A) = ChannelMeasurement::new(120.0, Phasor::from_polar(90.0, 0.0));
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_ptoc_blocks_invalid_quality() {
+        let mut ptoc = Ptoc::new(PtocConfig::default());
+
+        let mut frame = MeasurementFrame::from_phase_currents([500.0, 0.0, 0.0], 0);
+        frame.current_mut(Phase::A).quality = Quality::INVALID;
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+        assert_eq!(ptoc.state(), TripState::Idle);
+    }
+
+    #[test]
+    fn test_ptoc_directional_supervision() {
+        let config = PtocConfig {
+            tset: 0,
+            direction: DirectionMode::Forward,
+            ..PtocConfig::default()
+        };
+        let mut ptoc = Ptoc::new(config);
+        let frame = MeasurementFrame::balanced(500.0, 0);
+
+        // Without a forward direction the stage does not start
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+        ptoc.set_directions(Directions::all(Direction::Backward));
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+
+        let mut directions = Directions::all(Direction::Unknown);
+        directions.phases[1] = Direction::Forward;
+        ptoc.set_directions(directions);
+        assert_eq!(ptoc.process(&frame), ProtectionResult::Trip);
+        assert_eq!(ptoc.trip_phases(), PhaseFlags { phs_b: true, ..PhaseFlags::default() });
     }
 }
@@ -0,0 +1,402 @@
+/// RDIR (Directional Element) with quadrature, zero and negative sequence polarisation
+use crate::config::RdirConfig;
+use crate::measurement::phasor::normalize_angle;
+use crate::measurement::{ChannelMeasurement, MeasurementFrame, Phase, Phasor, SequenceComponents};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Directional mode of a protection stage (IEC 61850 DirMod)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DirectionMode {
+    /// Operates regardless of direction
+    #[default]
+    NonDirectional,
+    /// Operates for faults in front of the relay (towards the protected line)
+    Forward,
+    /// Operates for faults behind the relay (towards the busbar)
+    Reverse,
+}
+
+impl DirectionMode {
+    /// Check if a stage with this mode may operate for the given direction
+    pub fn releases(&self, direction: Direction) -> bool {
+        match self {
+            DirectionMode::NonDirectional => true,
+            DirectionMode::Forward => direction == Direction::Forward,
+            DirectionMode::Reverse => direction == Direction::Backward,
+        }
+    }
+}
+
+/// Polarising quantity of the earth-fault directional element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EarthPolarisation {
+    /// 3I0 against −3V0
+    #[default]
+    ZeroSequence,
+    /// 3I2 against −3V2 (for mutually coupled lines or without 3V0)
+    NegativeSequence,
+}
+
+/// Fault direction determined by RDIR (IEC 61850 Dir.dirGeneral)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// No direction: quantities too small or outside both sectors
+    #[default]
+    Unknown,
+    /// Fault in front of the relay
+    Forward,
+    /// Fault behind the relay
+    Backward,
+}
+
+/// Directions of the three phase elements, the earth element and the negative sequence element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Directions {
+    /// Phase A, B and C elements (quadrature polarised)
+    pub phases: [Direction; 3],
+    /// Earth element (zero or negative sequence polarised)
+    pub earth: Direction,
+    /// Negative sequence element (3I2 against −3V2), for negative sequence stages
+    pub negative: Direction,
+}
+
+impl Directions {
+    /// Directions that release every directional stage in the given direction
+    pub fn all(direction: Direction) -> Self {
+        Self {
+            phases: [direction; 3],
+            earth: direction,
+            negative: direction,
+        }
+    }
+
+    /// Get the direction of a conductor (the neutral returns the earth element)
+    pub fn get(&self, phase: Phase) -> Direction {
+        match phase {
+            Phase::N => self.earth,
+            _ => self.phases[phase.index()],
+        }
+    }
+}
+
+/// Polarising voltage held from before a close-in fault
+#[derive(Debug, Clone, Copy, Default)]
+struct VoltageMemory {
+    /// Voltage stored at least one cycle ago, with its timestamp
+    stored: Option<(Phasor, u64)>,
+    /// Most recent healthy voltage, moved to `stored` one cycle later
+    candidate: Option<(Phasor, u64)>,
+}
+
+impl VoltageMemory {
+    /// Record a healthy polarising voltage
+    ///
+    /// The candidate is promoted only after a full cycle, so the memory never
+    /// holds a phasor whose window already contains the fault inception.
+    fn update(&mut self, voltage: Phasor, timestamp: u64, cycle_us: u64) {
+        match self.candidate {
+            Some((_, since)) if timestamp.saturating_sub(since) < cycle_us => {}
+            _ => {
+                if self.candidate.is_some() {
+                    self.stored = self.candidate;
+                }
+                self.candidate = Some((voltage, timestamp));
+            }
+        }
+    }
+
+    /// Get the stored voltage if it is not older than `memory_us`
+    fn recall(&self, timestamp: u64, memory_us: u64) -> Option<Phasor> {
+        self.stored
+            .filter(|&(_, stored_at)| timestamp.saturating_sub(stored_at) <= memory_us)
+            .map(|(voltage, _)| voltage)
+    }
+}
+
+/// Directional element for phase and earth faults
+///
+/// Phase elements compare each phase current with the quadrature voltage
+/// (Ia with Vbc, Ib with Vca, Ic with Vab), which stays healthy for
+/// single-phase and phase-to-phase faults. When the polarising voltage
+/// collapses in a close-in three-phase fault, the pre-fault voltage from
+/// memory is used for `memory_time`.
+///
+/// The earth element compares 3I0 with −3V0, or 3I2 with −3V2, both
+/// calculated from the phase quantities. Negative sequence stages use a
+/// separate element that always compares 3I2 with −3V2, whatever the earth
+/// polarisation; it shares the characteristic angle and thresholds of the
+/// earth element.
+///
+/// A direction is forward when the operating current leads the polarising
+/// voltage by the characteristic angle ± `sector_width`, backward in the
+/// opposite sector, and unknown otherwise or when a quantity is below its
+/// minimum or invalid.
+pub struct Rdir {
+    config: RdirConfig,
+    cycle_us: u64,
+    memory: [VoltageMemory; 3],
+    directions: Directions,
+}
+
+impl Rdir {
+    /// Create a new directional element
+    ///
+    /// # Arguments
+    /// * `config` - Characteristic angles, thresholds and voltage memory
+    /// * `nominal_frequency` - Nominal system frequency in Hz
+    pub fn new(config: RdirConfig, nominal_frequency: f64) -> Self {
+        Self {
+            config,
+            cycle_us: (1_000_000.0 / nominal_frequency.max(1.0)) as u64,
+            memory: Default::default(),
+            directions: Directions::default(),
+        }
+    }
+
+    /// Determine the directions for a new measurement frame
+    pub fn process(&mut self, frame: &MeasurementFrame) -> Directions {
+        for phase in Phase::PHASES {
+            self.directions.phases[phase.index()] = self.phase_direction(frame, phase);
+        }
+        let currents = frame.current_sequence();
+        let voltages = frame.voltage_sequence();
+        self.directions.earth = self.sequence_direction(&currents, &voltages, self.config.earth_polarisation);
+        self.directions.negative = self.sequence_direction(&currents, &voltages, EarthPolarisation::NegativeSequence);
+        self.directions
+    }
+
+    /// Get the directions of the last processed frame
+    pub fn directions(&self) -> Directions {
+        self.directions
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &RdirConfig {
+        &self.config
+    }
+
+    /// Clear the voltage memory and directions
+    pub fn reset(&mut self) {
+        self.memory = Default::default();
+        self.directions = Directions::default();
+    }
+
+    /// Direction of one phase element with quadrature polarisation
+    fn phase_direction(&mut self, frame: &MeasurementFrame, phase: Phase) -> Direction {
+        let (lead, lag) = match phase {
+            Phase::A => (Phase::B, Phase::C),
+            Phase::B => (Phase::C, Phase::A),
+            _ => (Phase::A, Phase::B),
+        };
+        let (v1, v2) = (frame.voltage(lead), frame.voltage(lag));
+        let index = phase.index();
+
+        let measured = (is_valid(v1) && is_valid(v2))
+            .then(|| v1.phasor - v2.phasor)
+            .filter(|voltage| voltage.magnitude() >= self.config.min_voltage);
+        let polarising = match measured {
+            Some(voltage) => {
+                self.memory[index].update(voltage, frame.timestamp, self.cycle_us);
+                Some(voltage)
+            }
+            None => self.memory[index].recall(frame.timestamp, self.config.memory_time * 1000),
+        };
+
+        let current = frame.current(phase);
+        match polarising {
+            Some(voltage) if is_valid(current) && current.phasor.magnitude() >= self.config.min_current => {
+                direction(current.phasor, voltage, self.config.characteristic_angle, self.config.sector_width)
+            }
+            _ => Direction::Unknown,
+        }
+    }
+
+    /// Direction of a sequence element (earth or negative sequence)
+    fn sequence_direction(
+        &self,
+        currents: &SequenceComponents,
+        voltages: &SequenceComponents,
+        polarisation: EarthPolarisation,
+    ) -> Direction {
+        if currents.quality.is_invalid() || voltages.quality.is_invalid() {
+            return Direction::Unknown;
+        }
+
+        // 3I0 against −3V0 or 3I2 against −3V2
+        let (operating, polarising) = match polarisation {
+            EarthPolarisation::ZeroSequence => (currents.zero, -voltages.zero),
+            EarthPolarisation::NegativeSequence => (currents.negative, -voltages.negative),
+        };
+        let (operating, polarising) = (operating.scale(3.0), polarising.scale(3.0));
+
+        if operating.magnitude() < self.config.min_residual_current
+            || polarising.magnitude() < self.config.min_residual_voltage
+        {
+            return Direction::Unknown;
+        }
+        direction(
+            operating,
+            polarising,
+            self.config.earth_characteristic_angle,
+            self.config.sector_width,
+        )
+    }
+}
+
+/// Check if a channel can be used for direction (good or questionable quality)
+fn is_valid(channel: &ChannelMeasurement) -> bool {
+    !channel.quality.is_invalid()
+}
+
+/// Classify the angle between operating current and polarising voltage
+///
+/// # Arguments
+/// * `operating` - Operating current phasor
+/// * `polarising` - Polarising voltage phasor
+/// * `characteristic_angle` - Angle of maximum sensitivity in degrees (current leading)
+/// * `sector_width` - Half width of the forward and reverse sectors in degrees
+pub fn direction(operating: Phasor, polarising: Phasor, characteristic_angle: f64, sector_width: f64) -> Direction {
+    let deviation = normalize_angle(operating.angle() - polarising.angle() - characteristic_angle.to_radians()).abs();
+    let sector = sector_width.to_radians();
+    if deviation < sector {
+        Direction::Forward
+    } else if PI - deviation < sector {
+        Direction::Backward
+    } else {
+        Direction::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHASE_VOLTAGE: f64 = 11547.0;
+    const CYCLE_US: u64 = 20_000;
+
+    /// Frame with the given phase voltages and currents as (magnitude, angle in degrees)
+    fn frame(voltages: [(f64, f64); 3], currents: [(f64, f64); 3], timestamp: u64) -> MeasurementFrame {
+        let mut frame = MeasurementFrame::new(timestamp);
+        for (index, phase) in Phase::PHASES.iter().enumerate() {
+            let (magnitude, angle) = voltages[index];
+            *frame.voltage_mut(*phase) = ChannelMeasurement::new(magnitude, Phasor::from_polar_deg(magnitude, angle));
+            let (magnitude, angle) = currents[index];
+            *frame.current_mut(*phase) = ChannelMeasurement::new(magnitude, Phasor::from_polar_deg(magnitude, angle));
+        }
+        frame
+    }
+
+    fn healthy_voltages() -> [(f64, f64); 3] {
+        [(PHASE_VOLTAGE, 0.0), (PHASE_VOLTAGE, -120.0), (PHASE_VOLTAGE, 120.0)]
+    }
+
+    /// Balanced currents lagging the phase voltages by `angle` degrees
+    fn currents(magnitude: f64, angle: f64) -> [(f64, f64); 3] {
+        [(magnitude, -angle), (magnitude, -120.0 - angle), (magnitude, 120.0 - angle)]
+    }
+
+    #[test]
+    fn test_direction_sectors() {
+        let v = Phasor::from_polar_deg(1.0, 0.0);
+        assert_eq!(direction(Phasor::from_polar_deg(1.0, 30.0), v, 45.0, 85.0), Direction::Forward);
+        assert_eq!(direction(Phasor::from_polar_deg(1.0, -150.0), v, 45.0, 85.0), Direction::Backward);
+        assert_eq!(direction(Phasor::from_polar_deg(1.0, 135.0), v, 45.0, 85.0), Direction::Unknown);
+    }
+
+    #[test]
+    fn test_phase_forward_and_reverse() {
+        let mut rdir = Rdir::new(RdirConfig::default(), 50.0);
+
+        // Fault current lagging the phase voltage by 60°: Ia leads Vbc by 30°
+        let directions = rdir.process(&frame(healthy_voltages(), currents(1000.0, 60.0), 0));
+        assert_eq!(directions.phases, [Direction::Forward; 3]);
+
+        let directions = rdir.process(&frame(healthy_voltages(), currents(1000.0, 240.0), 1000));
+        assert_eq!(directions.phases, [Direction::Backward; 3]);
+    }
+
+    #[test]
+    fn test_phase_minimum_current_and_invalid_voltage() {
+        let mut rdir = Rdir::new(RdirConfig::default(), 50.0);
+        let directions = rdir.process(&frame(healthy_voltages(), currents(1.0, 60.0), 0));
+        assert_eq!(directions.get(Phase::A), Direction::Unknown);
+
+        let mut invalid = frame(healthy_voltages(), currents(1000.0, 60.0), 0);
+        invalid.voltage_mut(Phase::B).quality = crate::measurement::Quality::INVALID;
+        let directions = Rdir::new(RdirConfig::default(), 50.0).process(&invalid);
+        // Vbc and Vab use phase B; Vca is still available
+        assert_eq!(directions.phases, [Direction::Unknown, Direction::Forward, Direction::Unknown]);
+    }
+
+    #[test]
+    fn test_voltage_memory_for_close_in_fault() {
+        let mut rdir = Rdir::new(RdirConfig::default(), 50.0);
+        for cycle in 0..3 {
+            rdir.process(&frame(healthy_voltages(), currents(100.0, 20.0), cycle * CYCLE_US));
+        }
+
+        // Three-phase fault at the relay: all voltages collapse
+        let collapsed = [(10.0, 0.0), (10.0, -120.0), (10.0, 120.0)];
+        let fault_start = 3 * CYCLE_US;
+        let directions = rdir.process(&frame(collapsed, currents(5000.0, 80.0), fault_start));
+        assert_eq!(directions.phases, [Direction::Forward; 3]);
+
+        // Memory expires after memory_time (500 ms)
+        let directions = rdir.process(&frame(collapsed, currents(5000.0, 80.0), fault_start + 600_000));
+        assert_eq!(directions.phases, [Direction::Unknown; 3]);
+    }
+
+    #[test]
+    fn test_earth_zero_sequence() {
+        let mut rdir = Rdir::new(RdirConfig::default(), 50.0);
+
+        // Phase A to earth fault in front: Va collapses, Ia lags Va by 60°
+        let voltages = [(2000.0, 0.0), (PHASE_VOLTAGE, -120.0), (PHASE_VOLTAGE, 120.0)];
+        let fault = [(1000.0, -60.0), (0.0, 0.0), (0.0, 0.0)];
+        assert_eq!(rdir.process(&frame(voltages, fault, 0)).earth, Direction::Forward);
+
+        let reverse = [(1000.0, 120.0), (0.0, 0.0), (0.0, 0.0)];
+        assert_eq!(rdir.process(&frame(voltages, reverse, 0)).earth, Direction::Backward);
+
+        // No residual voltage on a healthy system
+        assert_eq!(rdir.process(&frame(healthy_voltages(), fault, 0)).earth, Direction::Unknown);
+    }
+
+    #[test]
+    fn test_earth_negative_sequence() {
+        let config = RdirConfig {
+            earth_polarisation: EarthPolarisation::NegativeSequence,
+            ..RdirConfig::default()
+        };
+        let mut rdir = Rdir::new(config, 50.0);
+
+        let voltages = [(2000.0, 0.0), (PHASE_VOLTAGE, -120.0), (PHASE_VOLTAGE, 120.0)];
+        let fault = [(1000.0, -60.0), (0.0, 0.0), (0.0, 0.0)];
+        assert_eq!(rdir.process(&frame(voltages, fault, 0)).earth, Direction::Forward);
+    }
+
+    #[test]
+    fn test_negative_sequence_independent_of_earth_polarisation() {
+        let voltages = [(2000.0, 0.0), (PHASE_VOLTAGE, -120.0), (PHASE_VOLTAGE, 120.0)];
+        let fault = [(1000.0, -60.0), (0.0, 0.0), (0.0, 0.0)];
+        let reverse = [(1000.0, 120.0), (0.0, 0.0), (0.0, 0.0)];
+
+        // Zero sequence polarised earth element
+        let mut rdir = Rdir::new(RdirConfig::default(), 50.0);
+        assert_eq!(rdir.process(&frame(voltages, fault, 0)).negative, Direction::Forward);
+        assert_eq!(rdir.process(&frame(voltages, reverse, 0)).negative, Direction::Backward);
+
+        // No negative sequence voltage on a healthy system
+        assert_eq!(rdir.process(&frame(healthy_voltages(), fault, 0)).negative, Direction::Unknown);
+    }
+
+    #[test]
+    fn test_direction_mode_releases() {
+        assert!(DirectionMode::NonDirectional.releases(Direction::Unknown));
+        assert!(DirectionMode::Forward.releases(Direction::Forward));
+        assert!(!DirectionMode::Forward.releases(Direction::Unknown));
+        assert!(DirectionMode::Reverse.releases(Direction::Backward));
+        assert!(!DirectionMode::Reverse.releases(Direction::Forward));
+    }
+}
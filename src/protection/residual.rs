@@ -0,0 +1,221 @@
+/// Earth-fault time overcurrent protection on the residual current 3I0
+use super::element::{self, OvercurrentElement};
+use super::rdir::Directions;
+use super::traits::{PhaseFlags, ProtectionFunction, ProtectionResult, TripState};
+use crate::config::PtocConfig;
+use crate::measurement::{ChannelMeasurement, MeasurementFrame, Phase};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Source of the residual current 3I0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResidualSource {
+    /// Phasor sum of the three phase currents (Ia + Ib + Ic)
+    #[default]
+    Calculated,
+    /// Neutral current channel (In), scaled with the neutral CT
+    Measured,
+}
+
+/// Earth-fault stage (PTOC on the neutral) with definite or inverse time characteristic
+///
+/// The stage uses the same settings and measuring element as a phase `Ptoc`
+/// stage, applied to one residual current. Pickup and trip are reported on
+/// the neutral (Str.neut, Op.neut). A directional stage is released by the
+/// earth element of RDIR.
+pub struct ResidualPtoc {
+    config: PtocConfig,
+    source: ResidualSource,
+    element: OvercurrentElement,
+    directions: Directions,
+}
+
+impl ResidualPtoc {
+    /// Create a new earth-fault stage
+    ///
+    /// # Arguments
+    /// * `config` - Pickup, delay, curve, reset and direction settings
+    /// * `source` - Calculated or measured residual current
+    pub fn new(config: PtocConfig, source: ResidualSource) -> Self {
+        Self {
+            config,
+            source,
+            element: OvercurrentElement::new(),
+            directions: Directions::default(),
+        }
+    }
+
+    /// Get the trip state
+    pub fn state(&self) -> TripState {
+        self.element.state()
+    }
+
+    /// Get the start flags (Str.neut while picked up or tripped)
+    pub fn start_phases(&self) -> PhaseFlags {
+        let state = self.state();
+        PhaseFlags {
+            neut: state.is_pickup() || state.is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the trip flags (Op.neut)
+    pub fn trip_phases(&self) -> PhaseFlags {
+        PhaseFlags {
+            neut: self.state().is_tripped(),
+            ..PhaseFlags::default()
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PtocConfig {
+        &self.config
+    }
+
+    /// Update the configuration
+    pub fn set_config(&mut self, config: PtocConfig) {
+        self.config = config;
+        if !self.config.enabled {
+            self.reset();
+        }
+    }
+
+    /// Get the residual current source
+    pub fn source(&self) -> ResidualSource {
+        self.source
+    }
+
+    /// Set the fault directions used by a directional stage for the next evaluations
+    pub fn set_directions(&mut self, directions: Directions) {
+        self.directions = directions;
+    }
+
+    /// Get the residual current of a frame according to the configured source
+    pub fn residual_current(&self, frame: &MeasurementFrame) -> ChannelMeasurement {
+        match self.source {
+            ResidualSource::Calculated => frame.residual_current(),
+            ResidualSource::Measured => *frame.current(Phase::N),
+        }
+    }
+
+    /// Calculate the operate time for a constant residual current
+    ///
+    /// # Returns
+    /// Operate time according to the configured characteristic, or `None`
+    /// if the current does not exceed the pickup setting
+    pub fn operate_time(&self, current: f64) -> Option<Duration> {
+        element::operate_time(&self.config, current)
+    }
+}
+
+impl ProtectionFunction for ResidualPtoc {
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult {
+        if !self.config.enabled {
+            return ProtectionResult::Disabled;
+        }
+
+        let channel = self.residual_current(frame);
+        let released = self.config.direction.releases(self.directions.earth);
+        let current = if channel.quality.is_invalid() || !released {
+            0.0
+        } else {
+            channel.magnitude(self.config.measuring_mode)
+        };
+        self.element.process(&self.config, current, frame.timestamp)
+    }
+
+    fn reset(&mut self) {
+        self.element.reset();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.config.enabled = enabled;
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PTOC (earth fault)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::{Phasor, Quality};
+    use crate::protection::{CurveType, Direction, DirectionMode};
+
+    fn stage(iset: f64, tset: u64) -> PtocConfig {
+        PtocConfig {
+            iset,
+            tset,
+            ..PtocConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_calculated_residual_trip() {
+        let mut ptoc = ResidualPtoc::new(stage(50.0, 100), ResidualSource::Calculated);
+
+        // Balanced load far above the earth-fault setting: no residual current
+        assert_eq!(ptoc.process(&MeasurementFrame::balanced(300.0, 0)), ProtectionResult::NoTrip);
+
+        // Phase A to earth fault of 80 A on top of nothing else
+        let fault = MeasurementFrame::from_phase_currents([80.0, 0.0, 0.0], 0);
+        assert!(matches!(ptoc.process(&fault), ProtectionResult::TripPending(_)));
+        let fault = MeasurementFrame::from_phase_currents([80.0, 0.0, 0.0], 100_000);
+        assert_eq!(ptoc.process(&fault), ProtectionResult::Trip);
+        assert_eq!(ptoc.trip_phases(), PhaseFlags { neut: true, ..PhaseFlags::default() });
+    }
+
+    #[test]
+    fn test_measured_residual() {
+        let mut ptoc = ResidualPtoc::new(stage(20.0, 0), ResidualSource::Measured);
+
+        let mut frame = MeasurementFrame::balanced(300.0, 0);
+        *frame.current_mut(Phase::N) = ChannelMeasurement::new(25.0, Phasor::from_polar(25.0, 0.0));
+        assert_eq!(ptoc.process(&frame), ProtectionResult::Trip);
+
+        ptoc.reset();
+        frame.current_mut(Phase::N).quality = Quality::INVALID;
+        assert_eq!(ptoc.process(&frame), ProtectionResult::NoTrip);
+    }
+
+    #[test]
+    fn test_inverse_curve() {
+        let config = PtocConfig {
+            curve: CurveType::IecStandardInverse,
+            tms: 0.1,
+            ..stage(20.0, 0)
+        };
+        let ptoc = ResidualPtoc::new(config, ResidualSource::Calculated);
+        // IEC SI at 10 × Is, TMS 0.1: 0.297 s
+        let time = ptoc.operate_time(200.0).unwrap();
+        assert!((time.as_secs_f64() - 0.297).abs() < 0.001);
+        assert_eq!(ptoc.operate_time(10.0), None);
+    }
+
+    #[test]
+    fn test_directional_earth_fault() {
+        let config = PtocConfig {
+            direction: DirectionMode::Forward,
+            ..stage(50.0, 0)
+        };
+        let mut ptoc = ResidualPtoc::new(config, ResidualSource::Calculated);
+        let fault = MeasurementFrame::from_phase_currents([80.0, 0.0, 0.0], 0);
+
+        // Forward phase directions do not release the earth stage
+        let mut directions = Directions::all(Direction::Forward);
+        directions.earth = Direction::Backward;
+        ptoc.set_directions(directions);
+        assert_eq!(ptoc.process(&fault), ProtectionResult::NoTrip);
+
+        ptoc.set_directions(Directions::all(Direction::Forward));
+        assert_eq!(ptoc.process(&fault), ProtectionResult::Trip);
+    }
+}
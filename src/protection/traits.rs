@@ -1,8 +1,9 @@
 /// Traits for protection functions
+use crate::measurement::{MeasurementFrame, Phase};
 use std::time::Duration;
 
 /// Result of a protection function evaluation
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ProtectionResult {
     /// No trip condition detected
     NoTrip,
@@ -14,17 +15,99 @@ pub enum ProtectionResult {
     Disabled,
 }
 
+impl ProtectionResult {
+    /// Combine two results into the most severe one
+    ///
+    /// Precedence is `Trip`, then the shortest `TripPending`, then `NoTrip`,
+    /// then `Disabled`.
+    pub fn combine(self, other: ProtectionResult) -> ProtectionResult {
+        use ProtectionResult::*;
+        match (self, other) {
+            (Trip, _) | (_, Trip) => Trip,
+            (TripPending(a), TripPending(b)) => TripPending(a.min(b)),
+            (TripPending(a), _) | (_, TripPending(a)) => TripPending(a),
+            (NoTrip, _) | (_, NoTrip) => NoTrip,
+            (Disabled, Disabled) => Disabled,
+        }
+    }
+}
+
+/// Phase-selective flags, following the IEC 61850-7-3 ACD/ACT attributes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseFlags {
+    /// Phase A (phsA)
+    pub phs_a: bool,
+    /// Phase B (phsB)
+    pub phs_b: bool,
+    /// Phase C (phsC)
+    pub phs_c: bool,
+    /// Neutral (neut)
+    pub neut: bool,
+}
+
+impl PhaseFlags {
+    /// Flags with the three phases set to `value` and the neutral cleared
+    ///
+    /// Used by functions that are not phase selective.
+    pub fn all(value: bool) -> Self {
+        PhaseFlags {
+            phs_a: value,
+            phs_b: value,
+            phs_c: value,
+            neut: false,
+        }
+    }
+
+    /// Get the flag of one conductor
+    pub fn get(&self, phase: Phase) -> bool {
+        match phase {
+            Phase::A => self.phs_a,
+            Phase::B => self.phs_b,
+            Phase::C => self.phs_c,
+            Phase::N => self.neut,
+        }
+    }
+
+    /// Set the flag of one conductor
+    pub fn set(&mut self, phase: Phase, value: bool) {
+        match phase {
+            Phase::A => self.phs_a = value,
+            Phase::B => self.phs_b = value,
+            Phase::C => self.phs_c = value,
+            Phase::N => self.neut = value,
+        }
+    }
+
+    /// General flag: set if any conductor is set
+    pub fn general(&self) -> bool {
+        self.phs_a || self.phs_b || self.phs_c || self.neut
+    }
+
+    /// Combine the flags of two functions (logical OR per conductor)
+    pub fn union(&self, other: PhaseFlags) -> PhaseFlags {
+        PhaseFlags {
+            phs_a: self.phs_a || other.phs_a,
+            phs_b: self.phs_b || other.phs_b,
+            phs_c: self.phs_c || other.phs_c,
+            neut: self.neut || other.neut,
+        }
+    }
+}
+
 /// Generic trait for protection functions
+///
+/// Quantities with invalid quality are evaluated as zero, so no function
+/// trips on invalid data.
 pub trait ProtectionFunction {
-    /// Process a new current measurement
+    /// Process a new measurement frame
     /// 
     /// # Arguments
-    /// * `current` - RMS current value in primary amperes
-    /// * `timestamp` - Timestamp of the measurement (microseconds)
+    /// * `frame` - Phase and neutral currents/voltages in primary units,
+    ///   with quality and timestamp (microseconds)
     /// 
     /// # Returns
     /// Protection result indicating trip status
-    fn process(&mut self, current: f64, timestamp: u64) -> ProtectionResult;
+    fn process(&mut self, frame: &MeasurementFrame) -> ProtectionResult;
 
     /// Reset the protection function to initial state
     fn reset(&mut self);
@@ -40,14 +123,17 @@ pub trait ProtectionFunction {
 }
 
 /// Trip state for protection functions
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum TripState {
     /// Function is idle (no overcurrent)
+    #[default]
     Idle,
     /// Overcurrent detected, waiting for time delay
     Pickup,
     /// Time delay expired, trip active
     Trip,
+    /// Current dropped out, time delay is resetting (timer memory active)
+    Resetting,
 }
 
 impl TripState {
@@ -61,8 +147,48 @@ impl TripState {
         matches!(self, TripState::Pickup)
     }
 
+    /// Check if the state is resetting after a dropout
+    pub fn is_resetting(&self) -> bool {
+        matches!(self, TripState::Resetting)
+    }
+
     /// Check if the state is idle
     pub fn is_idle(&self) -> bool {
         matches!(self, TripState::Idle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_results() {
+        let pending = |ms| ProtectionResult::TripPending(Duration::from_millis(ms));
+        assert_eq!(pending(100).combine(ProtectionResult::Trip), ProtectionResult::Trip);
+        assert_eq!(pending(100).combine(pending(50)), pending(50));
+        assert_eq!(ProtectionResult::NoTrip.combine(pending(50)), pending(50));
+        assert_eq!(
+            ProtectionResult::Disabled.combine(ProtectionResult::NoTrip),
+            ProtectionResult::NoTrip
+        );
+        assert_eq!(
+            ProtectionResult::Disabled.combine(ProtectionResult::Disabled),
+            ProtectionResult::Disabled
+        );
+    }
+
+    #[test]
+    fn test_phase_flags() {
+        let mut flags = PhaseFlags::default();
+        assert!(!flags.general());
+        flags.set(Phase::B, true);
+        assert!(flags.get(Phase::B));
+        assert!(flags.general());
+
+        let other = PhaseFlags { phs_c: true, ..PhaseFlags::default() };
+        let combined = flags.union(other);
+        assert!(combined.phs_b && combined.phs_c);
+        assert!(!combined.phs_a && !combined.neut);
+    }
+}
@@ -0,0 +1,180 @@
+/// Real-time thread setup for the protection loop (SCHED_FIFO, CPU affinity, memory locking)
+use crate::config::RealtimeConfig;
+use std::error::Error;
+
+/// Highest SCHED_FIFO priority accepted in the configuration
+pub const MAX_RT_PRIORITY: i32 = 99;
+
+/// Configure the calling thread for real-time operation
+///
+/// Memory is locked first, so every page touched afterwards stays resident,
+/// then the stack is pre-faulted, the thread is pinned to the configured CPUs
+/// and finally switched to SCHED_FIFO. Call this from the thread that runs the
+/// protection loop, after all buffers have been allocated.
+/// Requires CAP_SYS_NICE and CAP_IPC_LOCK (or root privileges) on Linux.
+///
+/// # Arguments
+/// * `config` - Real-time settings
+pub fn configure_current_thread(config: &RealtimeConfig) -> Result<(), Box<dyn Error>> {
+    validate(config)?;
+
+    if config.lock_memory {
+        lock_memory()?;
+    }
+    prefault_stack(config.prefault_stack);
+    if !config.cpu_affinity.is_empty() {
+        set_cpu_affinity(&config.cpu_affinity)?;
+    }
+    set_fifo_priority(config.priority)?;
+
+    log::info!(
+        "Real-time scheduling: SCHED_FIFO priority {}, CPUs {:?}, memory locked: {}",
+        config.priority,
+        config.cpu_affinity,
+        config.lock_memory
+    );
+    Ok(())
+}
+
+/// Check the real-time settings before changing the thread
+///
+/// # Returns
+/// Error if the priority is outside 1..99 or a CPU index is out of range
+pub fn validate(config: &RealtimeConfig) -> Result<(), Box<dyn Error>> {
+    if !(1..=MAX_RT_PRIORITY).contains(&config.priority) {
+        return Err(format!("Real-time priority {} outside 1..{}", config.priority, MAX_RT_PRIORITY).into());
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(cpu) = config.cpu_affinity.iter().find(|&&cpu| cpu >= libc::CPU_SETSIZE as usize) {
+        return Err(format!("CPU {} exceeds the CPU set size", cpu).into());
+    }
+    Ok(())
+}
+
+/// Touch `size` bytes of stack so later calls never page fault on the stack
+///
+/// # Arguments
+/// * `size` - Number of bytes to pre-fault
+pub fn prefault_stack(size: usize) {
+    const CHUNK: usize = 4096;
+
+    #[inline(never)]
+    fn touch(remaining: usize) {
+        let page = std::hint::black_box([0u8; CHUNK]);
+        if remaining > CHUNK {
+            touch(remaining - CHUNK);
+        }
+        std::hint::black_box(&page);
+    }
+
+    if size > 0 {
+        touch(size);
+    }
+}
+
+/// Lock all current and future pages of the process in memory
+pub fn lock_memory() -> Result<(), Box<dyn Error>> {
+    #[cfg(target_os = "linux")]
+    {
+        let ret = unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) };
+        if ret != 0 {
+            return Err(format!("mlockall failed: {}", std::io::Error::last_os_error()).into());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Memory locking is only supported on Linux".into())
+    }
+}
+
+/// Restrict the calling thread to the given CPUs
+///
+/// # Arguments
+/// * `cpus` - CPU indices the thread may run on
+pub fn set_cpu_affinity(cpus: &[usize]) -> Result<(), Box<dyn Error>> {
+    #[cfg(target_os = "linux")]
+    {
+        let ret = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_ZERO(&mut set);
+            for &cpu in cpus {
+                libc::CPU_SET(cpu, &mut set);
+            }
+            // pid 0 is the calling thread
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+        };
+        if ret != 0 {
+            return Err(format!("sched_setaffinity failed: {}", std::io::Error::last_os_error()).into());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cpus;
+        Err("CPU affinity is only supported on Linux".into())
+    }
+}
+
+/// Switch the calling thread to SCHED_FIFO
+///
+/// # Arguments
+/// * `priority` - SCHED_FIFO priority (1..99)
+pub fn set_fifo_priority(priority: i32) -> Result<(), Box<dyn Error>> {
+    #[cfg(target_os = "linux")]
+    {
+        let param = libc::sched_param { sched_priority: priority };
+        // pid 0 is the calling thread
+        let ret = unsafe { libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) };
+        if ret != 0 {
+            return Err(format!("sched_setscheduler failed: {}", std::io::Error::last_os_error()).into());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = priority;
+        Err("SCHED_FIFO is only supported on Linux".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let config = RealtimeConfig::default();
+        assert!(!config.enabled);
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_priority_rejected() {
+        for priority in [0, 100, -1] {
+            let config = RealtimeConfig {
+                priority,
+                ..RealtimeConfig::default()
+            };
+            assert!(validate(&config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_invalid_cpu_rejected() {
+        let config = RealtimeConfig {
+            cpu_affinity: vec![0, 100_000],
+            ..RealtimeConfig::default()
+        };
+        assert!(validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_prefault_stack() {
+        prefault_stack(64 * 1024);
+        prefault_stack(0);
+    }
+}